@@ -0,0 +1,592 @@
+use std::collections::{HashMap, VecDeque};
+use std::fs::{self, OpenOptions};
+use std::io::{self, Read, Write};
+use std::net::{TcpStream, ToSocketAddrs};
+use std::os::unix::net::UnixDatagram;
+use std::process::{Command, Stdio};
+use std::sync::mpsc::{self, Receiver, Sender};
+use std::thread;
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+
+use serde_json::json;
+
+use crate::alerts::{Alert, Severity};
+use crate::config::AlertActionConfig;
+use crate::util::time;
+
+const SYSLOG_SOCKET: &str = "/dev/log";
+const WEBHOOK_TIMEOUT: Duration = Duration::from_secs(5);
+const ACTION_LOG_SIZE: usize = 100;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum AlertEvent {
+    Fired,
+    Resolved,
+}
+
+impl AlertEvent {
+    pub fn label(&self) -> &'static str {
+        match self {
+            AlertEvent::Fired => "firing",
+            AlertEvent::Resolved => "resolved",
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+enum ActionKind {
+    Command(String),
+    Log(String),
+    Syslog(String),
+    Webhook {
+        host: String,
+        port: u16,
+        path: String,
+    },
+}
+
+#[derive(Debug, Clone)]
+pub struct Action {
+    kind: ActionKind,
+    on_resolve: bool,
+}
+
+fn parse_url(url: &str) -> Result<(String, u16, String), String> {
+    let rest = url
+        .strip_prefix("http://")
+        .ok_or_else(|| format!("only http:// webhook URLs are supported: `{}`", url))?;
+    let (authority, path) = match rest.find('/') {
+        Some(idx) => (&rest[..idx], &rest[idx..]),
+        None => (rest, "/"),
+    };
+    let (host, port) = match authority.rsplit_once(':') {
+        Some((host, port)) => (
+            host,
+            port.parse()
+                .map_err(|_| format!("invalid port in `{}`", url))?,
+        ),
+        None => (authority, 80),
+    };
+    if host.is_empty() {
+        return Err(format!("missing host in `{}`", url));
+    }
+    Ok((host.to_string(), port, path.to_string()))
+}
+
+impl Action {
+    pub fn from_config(config: &AlertActionConfig) -> Result<Self, String> {
+        let kind = match config.kind.as_str() {
+            "command" if !config.command.is_empty() => ActionKind::Command(config.command.clone()),
+            "log" if !config.path.is_empty() => ActionKind::Log(config.path.clone()),
+            "syslog" => ActionKind::Syslog(if config.path.is_empty() {
+                SYSLOG_SOCKET.to_string()
+            } else {
+                config.path.clone()
+            }),
+            "webhook" => {
+                let (host, port, path) = parse_url(&config.url)?;
+                ActionKind::Webhook { host, port, path }
+            }
+            "command" => return Err("command action needs `command`".to_string()),
+            "log" => return Err("log action needs `path`".to_string()),
+            kind => return Err(format!("unknown action kind `{}`", kind)),
+        };
+        Ok(Self {
+            kind,
+            on_resolve: config.on_resolve,
+        })
+    }
+
+    fn describe(&self) -> String {
+        match &self.kind {
+            ActionKind::Command(command) => format!("run `{}`", command),
+            ActionKind::Log(path) => format!("append to {}", path),
+            ActionKind::Syslog(path) => format!("send to syslog at {}", path),
+            ActionKind::Webhook { host, port, path } => {
+                format!("POST http://{}:{}{}", host, port, path)
+            }
+        }
+    }
+
+    fn execute(&self, payload: &Payload) -> io::Result<String> {
+        match &self.kind {
+            ActionKind::Command(command) => run_command(command, payload),
+            ActionKind::Log(path) => append_log(path, payload),
+            ActionKind::Syslog(path) => send_syslog(path, payload),
+            ActionKind::Webhook { host, port, path } => post_webhook(host, *port, path, payload),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+struct Payload {
+    event: AlertEvent,
+    rule: String,
+    severity: Severity,
+    condition: String,
+    detail: String,
+    started: SystemTime,
+    resolved: Option<SystemTime>,
+    hostname: String,
+}
+
+fn unix_seconds(time: SystemTime) -> u64 {
+    time.duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0)
+}
+
+impl Payload {
+    fn to_json(&self) -> serde_json::Value {
+        json!({
+            "time": time::timestamp(SystemTime::now()),
+            "host": self.hostname,
+            "state": self.event.label(),
+            "rule": self.rule,
+            "severity": self.severity.label(),
+            "condition": self.condition,
+            "detail": self.detail,
+            "started": unix_seconds(self.started),
+            "resolved": self.resolved.map(unix_seconds),
+        })
+    }
+
+    fn summary(&self) -> String {
+        format!(
+            "{} {} [{}]: {} ({})",
+            self.event.label(),
+            self.rule,
+            self.severity.label(),
+            self.condition,
+            self.detail
+        )
+    }
+}
+
+fn run_command(command: &str, payload: &Payload) -> io::Result<String> {
+    let status = Command::new("sh")
+        .arg("-c")
+        .arg(command)
+        .env("RTOP_ALERT_STATE", payload.event.label())
+        .env("RTOP_ALERT_RULE", &payload.rule)
+        .env("RTOP_ALERT_SEVERITY", payload.severity.label())
+        .env("RTOP_ALERT_CONDITION", &payload.condition)
+        .env("RTOP_ALERT_DETAIL", &payload.detail)
+        .env(
+            "RTOP_ALERT_STARTED",
+            unix_seconds(payload.started).to_string(),
+        )
+        .env(
+            "RTOP_ALERT_RESOLVED",
+            payload
+                .resolved
+                .map(|time| unix_seconds(time).to_string())
+                .unwrap_or_default(),
+        )
+        .env("RTOP_HOSTNAME", &payload.hostname)
+        .stdin(Stdio::null())
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .status()?;
+
+    if status.success() {
+        Ok("command succeeded".to_string())
+    } else {
+        Err(io::Error::other(format!("command exited with {}", status)))
+    }
+}
+
+fn append_log(path: &str, payload: &Payload) -> io::Result<String> {
+    let mut file = OpenOptions::new().create(true).append(true).open(path)?;
+    writeln!(file, "{}", payload.to_json())?;
+    Ok(format!("appended to {}", path))
+}
+
+fn send_syslog(path: &str, payload: &Payload) -> io::Result<String> {
+    let level = match (payload.event, payload.severity) {
+        (AlertEvent::Resolved, _) => 5,
+        (AlertEvent::Fired, Severity::Critical) => 2,
+        (AlertEvent::Fired, Severity::Warning) => 4,
+        (AlertEvent::Fired, Severity::Info) => 6,
+    };
+    let facility = 1;
+    let message = format!(
+        "<{}>rtop[{}]: {}",
+        facility * 8 + level,
+        std::process::id(),
+        payload.summary()
+    );
+
+    let socket = UnixDatagram::unbound()?;
+    socket.send_to(message.as_bytes(), path)?;
+    Ok(format!("sent to {}", path))
+}
+
+fn post_webhook(host: &str, port: u16, path: &str, payload: &Payload) -> io::Result<String> {
+    let body = payload.to_json().to_string();
+    let address = (host, port)
+        .to_socket_addrs()?
+        .next()
+        .ok_or_else(|| io::Error::new(io::ErrorKind::NotFound, "webhook host not found"))?;
+
+    let mut stream = TcpStream::connect_timeout(&address, WEBHOOK_TIMEOUT)?;
+    stream.set_read_timeout(Some(WEBHOOK_TIMEOUT))?;
+    stream.set_write_timeout(Some(WEBHOOK_TIMEOUT))?;
+    write!(
+        stream,
+        "POST {} HTTP/1.0\r\nHost: {}\r\nContent-Type: application/json\r\nContent-Length: {}\r\nUser-Agent: rtop\r\n\r\n{}",
+        path,
+        host,
+        body.len(),
+        body
+    )?;
+
+    let mut response = Vec::new();
+    let mut buf = [0u8; 512];
+    while !response.contains(&b'\n') {
+        let read = stream.read(&mut buf)?;
+        if read == 0 {
+            break;
+        }
+        response.extend_from_slice(&buf[..read]);
+    }
+
+    let response = String::from_utf8_lossy(&response);
+    let status = response.lines().next().unwrap_or("").trim();
+    if status
+        .split_whitespace()
+        .nth(1)
+        .unwrap_or("")
+        .starts_with('2')
+    {
+        Ok(format!("webhook returned {}", status))
+    } else {
+        Err(io::Error::other(format!("webhook returned `{}`", status)))
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct ActionRecord {
+    pub time: SystemTime,
+    pub rule: String,
+    pub action: String,
+    pub outcome: String,
+    pub failed: bool,
+}
+
+pub struct ActionRunner {
+    actions: HashMap<String, Action>,
+    dry_run: bool,
+    rate_limit: Duration,
+    last_run: HashMap<(String, String, AlertEvent), Instant>,
+    hostname: String,
+    sender: Sender<ActionRecord>,
+    receiver: Receiver<ActionRecord>,
+    records: VecDeque<ActionRecord>,
+}
+
+impl ActionRunner {
+    pub fn new(actions: HashMap<String, Action>, dry_run: bool, rate_limit: Duration) -> Self {
+        let (sender, receiver) = mpsc::channel();
+        Self {
+            actions,
+            dry_run,
+            rate_limit,
+            last_run: HashMap::new(),
+            hostname: fs::read_to_string("/proc/sys/kernel/hostname")
+                .map(|name| name.trim().to_string())
+                .unwrap_or_default(),
+            sender,
+            receiver,
+            records: VecDeque::new(),
+        }
+    }
+
+    pub fn is_dry_run(&self) -> bool {
+        self.dry_run
+    }
+
+    fn push(&mut self, record: ActionRecord) {
+        self.records.push_front(record);
+        self.records.truncate(ACTION_LOG_SIZE);
+    }
+
+    pub fn dispatch(&mut self, event: AlertEvent, alert: &Alert, names: &[String]) {
+        let payload = Payload {
+            event,
+            rule: alert.rule.clone(),
+            severity: alert.severity,
+            condition: alert.condition.clone(),
+            detail: alert.detail.clone(),
+            started: alert.started,
+            resolved: alert.resolved,
+            hostname: self.hostname.clone(),
+        };
+
+        for name in names {
+            let action = match self.actions.get(name) {
+                Some(action) => action.clone(),
+                None => continue,
+            };
+            if event == AlertEvent::Resolved && !action.on_resolve {
+                continue;
+            }
+
+            let record = |outcome: String, failed: bool| ActionRecord {
+                time: SystemTime::now(),
+                rule: alert.rule.clone(),
+                action: name.clone(),
+                outcome,
+                failed,
+            };
+
+            let key = (alert.rule.clone(), name.clone(), event);
+            let limited = self
+                .last_run
+                .get(&key)
+                .map(|at| at.elapsed() < self.rate_limit)
+                .unwrap_or(false);
+            if limited {
+                self.push(record(format!("{}: rate limited", event.label()), false));
+                continue;
+            }
+            self.last_run.insert(key, Instant::now());
+
+            if self.dry_run {
+                self.push(record(
+                    format!("{}: dry run, would {}", event.label(), action.describe()),
+                    false,
+                ));
+                continue;
+            }
+
+            let sender = self.sender.clone();
+            let payload = payload.clone();
+            let rule = alert.rule.clone();
+            let name = name.clone();
+            thread::spawn(move || {
+                let result = action.execute(&payload);
+                let (outcome, failed) = match result {
+                    Ok(outcome) => (outcome, false),
+                    Err(err) => (err.to_string(), true),
+                };
+                let _ = sender.send(ActionRecord {
+                    time: SystemTime::now(),
+                    rule,
+                    action: name,
+                    outcome: format!("{}: {}", payload.event.label(), outcome),
+                    failed,
+                });
+            });
+        }
+    }
+
+    pub fn collect(&mut self) {
+        while let Ok(record) = self.receiver.try_recv() {
+            self.push(record);
+        }
+    }
+
+    pub fn get_records(&self) -> &VecDeque<ActionRecord> {
+        &self.records
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::BufRead;
+    use std::net::TcpListener;
+
+    fn alert() -> Alert {
+        Alert {
+            rule: "cpu-hot".to_string(),
+            severity: Severity::Critical,
+            condition: "cpu.avg > 90".to_string(),
+            detail: "cpu.avg = 97.0%".to_string(),
+            started: UNIX_EPOCH + Duration::from_secs(1_700_000_000),
+            resolved: None,
+        }
+    }
+
+    fn runner(config: AlertActionConfig, dry_run: bool) -> ActionRunner {
+        let mut actions = HashMap::new();
+        actions.insert("notify".to_string(), Action::from_config(&config).unwrap());
+        ActionRunner::new(actions, dry_run, Duration::from_secs(60))
+    }
+
+    fn webhook(listener: &TcpListener) -> AlertActionConfig {
+        AlertActionConfig {
+            kind: "webhook".to_string(),
+            url: format!("http://{}/alerts", listener.local_addr().unwrap()),
+            ..AlertActionConfig::default()
+        }
+    }
+
+    fn wait_for_records(runner: &mut ActionRunner, count: usize) -> Vec<ActionRecord> {
+        let deadline = Instant::now() + Duration::from_secs(5);
+        while runner.get_records().len() < count && Instant::now() < deadline {
+            thread::sleep(Duration::from_millis(10));
+            runner.collect();
+        }
+        runner.get_records().iter().cloned().collect()
+    }
+
+    fn receive(listener: &TcpListener, status: &str) -> (String, serde_json::Value) {
+        let (stream, _) = listener.accept().unwrap();
+        let mut reader = io::BufReader::new(stream);
+        let mut head = String::new();
+        let mut length = 0;
+        loop {
+            let mut line = String::new();
+            reader.read_line(&mut line).unwrap();
+            if line == "\r\n" {
+                break;
+            }
+            if let Some(value) = line.strip_prefix("Content-Length: ") {
+                length = value.trim().parse().unwrap();
+            }
+            head.push_str(&line);
+        }
+        let mut body = vec![0; length];
+        reader.read_exact(&mut body).unwrap();
+        write!(reader.get_mut(), "{}\r\n\r\n", status).unwrap();
+        (head, serde_json::from_slice(&body).unwrap())
+    }
+
+    fn assert_no_connection(listener: &TcpListener) {
+        listener.set_nonblocking(true).unwrap();
+        thread::sleep(Duration::from_millis(100));
+        let err = listener.accept().unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::WouldBlock);
+    }
+
+    #[test]
+    fn webhook_posts_alert_payload() {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let mut runner = runner(webhook(&listener), false);
+
+        runner.dispatch(AlertEvent::Fired, &alert(), &["notify".to_string()]);
+        let (head, body) = receive(&listener, "HTTP/1.0 204 No Content");
+        let records = wait_for_records(&mut runner, 1);
+
+        assert!(head.starts_with("POST /alerts HTTP/1.0\r\n"));
+        assert!(head.contains("Content-Type: application/json\r\n"));
+        assert_eq!(body["state"], "firing");
+        assert_eq!(body["rule"], "cpu-hot");
+        assert_eq!(body["severity"], "critical");
+        assert_eq!(body["condition"], "cpu.avg > 90");
+        assert_eq!(body["detail"], "cpu.avg = 97.0%");
+        assert_eq!(body["started"], 1_700_000_000);
+        assert!(body["resolved"].is_null());
+        assert_eq!(records[0].action, "notify");
+        assert_eq!(
+            records[0].outcome,
+            "firing: webhook returned HTTP/1.0 204 No Content"
+        );
+        assert!(!records[0].failed);
+    }
+
+    #[test]
+    fn repeated_events_are_rate_limited() {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let mut runner = runner(webhook(&listener), false);
+
+        runner.dispatch(AlertEvent::Fired, &alert(), &["notify".to_string()]);
+        runner.dispatch(AlertEvent::Fired, &alert(), &["notify".to_string()]);
+        receive(&listener, "HTTP/1.0 204 No Content");
+        let records = wait_for_records(&mut runner, 2);
+
+        assert_eq!(records.len(), 2);
+        assert!(records
+            .iter()
+            .any(|record| record.outcome == "firing: rate limited"));
+        assert_no_connection(&listener);
+
+        let mut resolved = alert();
+        resolved.resolved = Some(SystemTime::now());
+        runner.dispatch(AlertEvent::Resolved, &resolved, &["notify".to_string()]);
+        listener.set_nonblocking(false).unwrap();
+        let (_, body) = receive(&listener, "HTTP/1.0 204 No Content");
+        assert_eq!(body["state"], "resolved");
+    }
+
+    #[test]
+    fn dry_run_sends_nothing() {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let mut runner = runner(webhook(&listener), true);
+
+        runner.dispatch(AlertEvent::Fired, &alert(), &["notify".to_string()]);
+        let records = wait_for_records(&mut runner, 1);
+
+        assert_eq!(
+            records[0].outcome,
+            format!(
+                "firing: dry run, would POST http://{}/alerts",
+                listener.local_addr().unwrap()
+            )
+        );
+        assert_no_connection(&listener);
+    }
+
+    #[test]
+    fn webhook_reports_error_status() {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let mut runner = runner(webhook(&listener), false);
+
+        runner.dispatch(AlertEvent::Fired, &alert(), &["notify".to_string()]);
+        receive(&listener, "HTTP/1.0 500 Internal Server Error");
+        let records = wait_for_records(&mut runner, 1);
+
+        assert!(records[0].failed);
+        assert!(records[0].outcome.contains("500 Internal Server Error"));
+    }
+
+    #[test]
+    fn syslog_and_command_receive_the_alert() {
+        let dir = std::env::temp_dir().join(format!("rtop-actions-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let socket_path = dir.join("log.sock");
+        let output_path = dir.join("command.out");
+        let _ = fs::remove_file(&socket_path);
+        let socket = UnixDatagram::bind(&socket_path).unwrap();
+
+        let syslog = Action::from_config(&AlertActionConfig {
+            kind: "syslog".to_string(),
+            path: socket_path.to_string_lossy().to_string(),
+            ..AlertActionConfig::default()
+        })
+        .unwrap();
+        let command = Action::from_config(&AlertActionConfig {
+            kind: "command".to_string(),
+            command: format!(
+                "printf '%s %s' \"$RTOP_ALERT_STATE\" \"$RTOP_ALERT_RULE\" > '{}'",
+                output_path.display()
+            ),
+            ..AlertActionConfig::default()
+        })
+        .unwrap();
+        let mut actions = HashMap::new();
+        actions.insert("syslog".to_string(), syslog);
+        actions.insert("command".to_string(), command);
+        let mut runner = ActionRunner::new(actions, false, Duration::from_secs(60));
+
+        runner.dispatch(
+            AlertEvent::Fired,
+            &alert(),
+            &["syslog".to_string(), "command".to_string()],
+        );
+        let records = wait_for_records(&mut runner, 2);
+
+        let mut buf = [0u8; 512];
+        let len = socket.recv(&mut buf).unwrap();
+        let message = String::from_utf8_lossy(&buf[..len]).to_string();
+        let output = fs::read_to_string(&output_path).unwrap();
+        fs::remove_dir_all(&dir).unwrap();
+
+        assert!(records.iter().all(|record| !record.failed));
+        assert!(message.starts_with("<10>rtop["));
+        assert!(message.ends_with("firing cpu-hot [critical]: cpu.avg > 90 (cpu.avg = 97.0%)"));
+        assert_eq!(output, "firing cpu-hot");
+    }
+}
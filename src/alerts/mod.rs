@@ -0,0 +1,233 @@
+pub mod actions;
+pub mod rule;
+
+use std::collections::{HashMap, VecDeque};
+use std::time::{Duration, Instant, SystemTime};
+
+use crate::config::{AlertsConfig, UnitsConfig};
+use crate::system::SystemState;
+use crate::util::Units;
+
+pub use actions::{Action, ActionRecord, ActionRunner, AlertEvent};
+pub use rule::{Condition, Evaluation};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Severity {
+    Info,
+    Warning,
+    Critical,
+}
+
+impl Severity {
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name.to_lowercase().as_str() {
+            "info" => Some(Severity::Info),
+            "warning" | "warn" => Some(Severity::Warning),
+            "critical" | "crit" => Some(Severity::Critical),
+            _ => None,
+        }
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            Severity::Info => "info",
+            Severity::Warning => "warning",
+            Severity::Critical => "critical",
+        }
+    }
+}
+
+pub struct AlertRule {
+    pub name: String,
+    pub source: String,
+    pub severity: Severity,
+    pub condition: Condition,
+    pub actions: Vec<String>,
+    hysteresis: f64,
+}
+
+impl AlertRule {
+    pub fn evaluate(&self, system: &SystemState, units: &Units) -> Evaluation {
+        self.condition.evaluate(system, self.hysteresis, units)
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Alert {
+    pub rule: String,
+    pub severity: Severity,
+    pub condition: String,
+    pub detail: String,
+    pub started: SystemTime,
+    pub resolved: Option<SystemTime>,
+}
+
+#[derive(Default)]
+struct RuleState {
+    pending_since: Option<Instant>,
+    active: Option<Alert>,
+}
+
+pub struct AlertState {
+    rules: Vec<AlertRule>,
+    states: Vec<RuleState>,
+    resolved: VecDeque<Alert>,
+    history: usize,
+    errors: Vec<String>,
+    units: Units,
+    runner: ActionRunner,
+}
+
+impl AlertState {
+    pub fn new(config: &AlertsConfig, units: &UnitsConfig) -> Self {
+        let mut rules = Vec::new();
+        let mut errors = Vec::new();
+
+        let mut actions = HashMap::new();
+        for (name, action) in &config.actions {
+            match Action::from_config(action) {
+                Ok(action) => {
+                    actions.insert(name.clone(), action);
+                }
+                Err(err) => errors.push(format!("action {}: {}", name, err)),
+            }
+        }
+        let runner = ActionRunner::new(
+            actions,
+            config.dry_run,
+            Duration::from_secs(config.rate_limit),
+        );
+
+        for rule in &config.rules {
+            let severity = match Severity::from_name(&rule.severity) {
+                Some(severity) => severity,
+                None => {
+                    errors.push(format!(
+                        "{}: unknown severity `{}`",
+                        rule.name, rule.severity
+                    ));
+                    continue;
+                }
+            };
+            for action in &rule.actions {
+                if !config.actions.contains_key(action) {
+                    errors.push(format!("{}: unknown action `{}`", rule.name, action));
+                }
+            }
+            match Condition::parse(&rule.condition) {
+                Ok(condition) => rules.push(AlertRule {
+                    name: rule.name.clone(),
+                    source: rule.condition.clone(),
+                    severity,
+                    condition,
+                    actions: rule.actions.clone(),
+                    hysteresis: rule.hysteresis.unwrap_or(config.hysteresis) / 100.0,
+                }),
+                Err(err) => errors.push(format!("{}: {}", rule.name, err)),
+            }
+        }
+
+        Self {
+            states: rules.iter().map(|_| RuleState::default()).collect(),
+            rules,
+            resolved: VecDeque::new(),
+            history: config.history,
+            errors,
+            units: Units::from_config(units),
+            runner,
+        }
+    }
+
+    pub fn evaluate(&self, system: &SystemState) -> Vec<Evaluation> {
+        self.rules
+            .iter()
+            .map(|rule| rule.evaluate(system, &self.units))
+            .collect()
+    }
+
+    pub fn record(&mut self, evaluations: Vec<Evaluation>) {
+        let now = Instant::now();
+        self.runner.collect();
+
+        for ((rule, state), evaluation) in self
+            .rules
+            .iter()
+            .zip(self.states.iter_mut())
+            .zip(evaluations)
+        {
+            if let Some(alert) = &mut state.active {
+                if evaluation.holding {
+                    alert.detail = evaluation.detail;
+                    continue;
+                }
+
+                let mut alert = state.active.take().unwrap();
+                alert.resolved = Some(SystemTime::now());
+                self.runner
+                    .dispatch(AlertEvent::Resolved, &alert, &rule.actions);
+                self.resolved.push_front(alert);
+                self.resolved.truncate(self.history);
+                state.pending_since = None;
+                continue;
+            }
+
+            if !evaluation.triggered {
+                state.pending_since = None;
+                continue;
+            }
+
+            let since = *state.pending_since.get_or_insert(now);
+            if now.duration_since(since) >= rule.condition.get_duration() {
+                let alert = Alert {
+                    rule: rule.name.clone(),
+                    severity: rule.severity,
+                    condition: rule.source.clone(),
+                    detail: evaluation.detail,
+                    started: SystemTime::now(),
+                    resolved: None,
+                };
+                self.runner
+                    .dispatch(AlertEvent::Fired, &alert, &rule.actions);
+                state.active = Some(alert);
+            }
+        }
+    }
+
+    pub fn get_rules(&self) -> &[AlertRule] {
+        &self.rules
+    }
+
+    pub fn get_active(&self) -> Vec<&Alert> {
+        let mut active: Vec<&Alert> = self
+            .states
+            .iter()
+            .filter_map(|state| state.active.as_ref())
+            .collect();
+        active.sort_by(|a, b| b.severity.cmp(&a.severity).then(a.started.cmp(&b.started)));
+        active
+    }
+
+    pub fn get_resolved(&self) -> &VecDeque<Alert> {
+        &self.resolved
+    }
+
+    pub fn get_errors(&self) -> &[String] {
+        &self.errors
+    }
+
+    pub fn get_action_records(&self) -> &VecDeque<ActionRecord> {
+        self.runner.get_records()
+    }
+
+    pub fn is_dry_run(&self) -> bool {
+        self.runner.is_dry_run()
+    }
+
+    pub fn highest_severity(&self) -> Option<Severity> {
+        self.states
+            .iter()
+            .filter_map(|state| state.active.as_ref())
+            .map(|alert| alert.severity)
+            .max()
+    }
+}
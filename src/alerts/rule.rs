@@ -0,0 +1,646 @@
+use std::time::Duration;
+
+use sysinfo::PidExt;
+
+use crate::system::process::Process;
+use crate::system::SystemState;
+use crate::util::glob::glob_match;
+use crate::util::Units;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Operator {
+    Gt,
+    Ge,
+    Lt,
+    Le,
+    Eq,
+    Ne,
+}
+
+impl Operator {
+    fn parse(token: &str) -> Option<Self> {
+        match token {
+            ">" => Some(Operator::Gt),
+            ">=" => Some(Operator::Ge),
+            "<" => Some(Operator::Lt),
+            "<=" => Some(Operator::Le),
+            "==" => Some(Operator::Eq),
+            "!=" => Some(Operator::Ne),
+            _ => None,
+        }
+    }
+
+    fn label(&self) -> &'static str {
+        match self {
+            Operator::Gt => ">",
+            Operator::Ge => ">=",
+            Operator::Lt => "<",
+            Operator::Le => "<=",
+            Operator::Eq => "==",
+            Operator::Ne => "!=",
+        }
+    }
+
+    fn compare(&self, value: f64, threshold: f64) -> bool {
+        match self {
+            Operator::Gt => value > threshold,
+            Operator::Ge => value >= threshold,
+            Operator::Lt => value < threshold,
+            Operator::Le => value <= threshold,
+            Operator::Eq => value == threshold,
+            Operator::Ne => value != threshold,
+        }
+    }
+
+    fn relax(&self, threshold: f64, margin: f64) -> f64 {
+        match self {
+            Operator::Gt | Operator::Ge => threshold - margin,
+            Operator::Lt | Operator::Le => threshold + margin,
+            Operator::Eq | Operator::Ne => threshold,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Kind {
+    Percent,
+    Bytes,
+    Rate,
+    Count,
+    Text,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Metric {
+    CpuAvg,
+    CpuMax,
+    MemUsed,
+    MemAvailable,
+    SwapUsed,
+    DiskUsed(String),
+    DiskAvailable(String),
+    DiskInodes(String),
+    NetRx(Option<String>),
+    NetTx(Option<String>),
+    Pressure(usize),
+    ProcessName,
+    ProcessUser,
+    ProcessPid,
+    ProcessCpu,
+    ProcessMem,
+}
+
+impl Metric {
+    fn parse(name: &str) -> Result<Self, String> {
+        let metric = match name {
+            "cpu" | "cpu.avg" => Metric::CpuAvg,
+            "cpu.max" => Metric::CpuMax,
+            "mem" | "mem.used" => Metric::MemUsed,
+            "mem.available" => Metric::MemAvailable,
+            "swap.used" => Metric::SwapUsed,
+            "net.rx" => Metric::NetRx(None),
+            "net.tx" => Metric::NetTx(None),
+            "psi.cpu" => Metric::Pressure(0),
+            "psi.memory" => Metric::Pressure(1),
+            "psi.io" => Metric::Pressure(2),
+            "process.name" => Metric::ProcessName,
+            "process.user" => Metric::ProcessUser,
+            "process.pid" => Metric::ProcessPid,
+            "process.cpu" => Metric::ProcessCpu,
+            "process.mem" => Metric::ProcessMem,
+            _ => {
+                if let Some(rest) = name.strip_prefix("disk.") {
+                    match rest.rsplit_once('.') {
+                        Some((mount, "used")) => Metric::DiskUsed(mount.to_string()),
+                        Some((mount, "available")) => Metric::DiskAvailable(mount.to_string()),
+                        Some((mount, "inodes")) => Metric::DiskInodes(mount.to_string()),
+                        _ => return Err(format!("unknown disk metric `{}`", name)),
+                    }
+                } else if let Some(rest) = name.strip_prefix("net.") {
+                    match rest.rsplit_once('.') {
+                        Some((interface, "rx")) => Metric::NetRx(Some(interface.to_string())),
+                        Some((interface, "tx")) => Metric::NetTx(Some(interface.to_string())),
+                        _ => return Err(format!("unknown network metric `{}`", name)),
+                    }
+                } else {
+                    return Err(format!("unknown metric `{}`", name));
+                }
+            }
+        };
+        Ok(metric)
+    }
+
+    fn kind(&self) -> Kind {
+        match self {
+            Metric::CpuAvg
+            | Metric::CpuMax
+            | Metric::Pressure(_)
+            | Metric::ProcessCpu
+            | Metric::DiskInodes(_) => Kind::Percent,
+            Metric::MemUsed
+            | Metric::MemAvailable
+            | Metric::SwapUsed
+            | Metric::DiskUsed(_)
+            | Metric::DiskAvailable(_)
+            | Metric::ProcessMem => Kind::Bytes,
+            Metric::NetRx(_) | Metric::NetTx(_) => Kind::Rate,
+            Metric::ProcessPid => Kind::Count,
+            Metric::ProcessName | Metric::ProcessUser => Kind::Text,
+        }
+    }
+
+    fn for_process(self) -> Self {
+        match self {
+            Metric::CpuAvg => Metric::ProcessCpu,
+            Metric::MemUsed => Metric::ProcessMem,
+            metric => metric,
+        }
+    }
+
+    fn is_process(&self) -> bool {
+        matches!(
+            self,
+            Metric::ProcessName
+                | Metric::ProcessUser
+                | Metric::ProcessPid
+                | Metric::ProcessCpu
+                | Metric::ProcessMem
+        )
+    }
+
+    fn sample(&self, system: &SystemState, process: Option<&Process>) -> Option<Sample> {
+        let number = |value: f64, percent: Option<f64>| Some(Sample::Number { value, percent });
+        let percent = |part: u64, total: u64| {
+            if total > 0 {
+                Some(part as f64 / total as f64 * 100.0)
+            } else {
+                None
+            }
+        };
+
+        match self {
+            Metric::CpuAvg => {
+                let usage = system.cpu.get_average_usage() as f64;
+                number(usage, Some(usage))
+            }
+            Metric::CpuMax => {
+                let usage = (0..system.cpu.get_core_count())
+                    .filter_map(|core| system.cpu.get_core_usage(core))
+                    .fold(0.0f32, f32::max) as f64;
+                number(usage, Some(usage))
+            }
+            Metric::MemUsed => {
+                let total = system.memory.get_total_memory();
+                let used = system.memory.get_used_memory();
+                number(used as f64, percent(used, total))
+            }
+            Metric::MemAvailable => {
+                let total = system.memory.get_total_memory();
+                let available = total.saturating_sub(system.memory.get_used_memory());
+                number(available as f64, percent(available, total))
+            }
+            Metric::SwapUsed => {
+                let total = system.memory.get_total_swap();
+                let used = system.memory.get_used_swap();
+                number(used as f64, percent(used, total))
+            }
+            Metric::DiskUsed(mount) | Metric::DiskAvailable(mount) | Metric::DiskInodes(mount) => {
+                let disk = system
+                    .disk
+                    .get_disks()
+                    .iter()
+                    .find(|disk| disk.get_mount_point() == mount)?;
+                let total = disk.get_total_space();
+                match self {
+                    Metric::DiskUsed(_) => {
+                        let used = disk.get_used_space();
+                        number(used as f64, percent(used, total))
+                    }
+                    Metric::DiskAvailable(_) => {
+                        let available = disk.get_available_space();
+                        number(available as f64, percent(available, total))
+                    }
+                    _ => {
+                        let usage = disk.get_inode_usage_percent()?;
+                        number(usage, Some(usage))
+                    }
+                }
+            }
+            Metric::NetRx(interface) | Metric::NetTx(interface) => {
+                let rx = matches!(self, Metric::NetRx(_));
+                let rate = match interface {
+                    Some(name) => {
+                        let interface = system.network.get_interface(name)?;
+                        if rx {
+                            interface.get_receive_rate()
+                        } else {
+                            interface.get_transmit_rate()
+                        }
+                    }
+                    None if rx => system.network.get_total_receive_rate(),
+                    None => system.network.get_total_transmit_rate(),
+                };
+                number(rate, None)
+            }
+            Metric::Pressure(idx) => {
+                let resource = system.pressure.get_resources()[*idx];
+                if !resource.is_available() {
+                    return None;
+                }
+                let avg10 = resource.get_some_avg10();
+                number(avg10, Some(avg10))
+            }
+            Metric::ProcessName => Some(Sample::Text(process?.get_name().to_string())),
+            Metric::ProcessUser => Some(Sample::Text(process?.get_user_id()?.to_string())),
+            Metric::ProcessPid => number(process?.get_pid().as_u32() as f64, None),
+            Metric::ProcessCpu => {
+                let usage = process?.get_cpu_usage() as f64;
+                number(usage, Some(usage))
+            }
+            Metric::ProcessMem => {
+                let process = process?;
+                let used = process.get_memory_usage();
+                number(used as f64, percent(used, system.memory.get_total_memory()))
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+enum Sample {
+    Number { value: f64, percent: Option<f64> },
+    Text(String),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Literal {
+    Number(f64),
+    Percent(f64),
+    Text(String),
+}
+
+fn parse_literal(token: &str) -> Result<Literal, String> {
+    if let Some(text) = token
+        .strip_prefix('"')
+        .and_then(|rest| rest.strip_suffix('"'))
+    {
+        return Ok(Literal::Text(text.to_string()));
+    }
+    if let Some(number) = token.strip_suffix('%') {
+        return number
+            .parse()
+            .map(Literal::Percent)
+            .map_err(|_| format!("invalid percentage `{}`", token));
+    }
+
+    let unit_start = token
+        .find(|c: char| !(c.is_ascii_digit() || c == '.'))
+        .unwrap_or(token.len());
+    let (number, unit) = token.split_at(unit_start);
+    let number: f64 = number
+        .parse()
+        .map_err(|_| format!("invalid number `{}`", token))?;
+    let multiplier = match unit.strip_suffix("/s").unwrap_or(unit) {
+        "" | "B" => 1.0,
+        "k" | "K" | "kB" | "KB" => 1e3,
+        "M" | "MB" => 1e6,
+        "G" | "GB" => 1e9,
+        "T" | "TB" => 1e12,
+        "KiB" => 1024.0,
+        "MiB" => 1024.0 * 1024.0,
+        "GiB" => 1024.0 * 1024.0 * 1024.0,
+        "TiB" => 1024.0 * 1024.0 * 1024.0 * 1024.0,
+        _ => return Err(format!("unknown unit in `{}`", token)),
+    };
+    Ok(Literal::Number(number * multiplier))
+}
+
+pub fn parse_duration(token: &str) -> Result<Duration, String> {
+    let unit_start = token
+        .find(|c: char| !(c.is_ascii_digit() || c == '.'))
+        .unwrap_or(token.len());
+    let (number, unit) = token.split_at(unit_start);
+    let number: f64 = number
+        .parse()
+        .map_err(|_| format!("invalid duration `{}`", token))?;
+    let seconds = match unit {
+        "ms" => number / 1000.0,
+        "" | "s" => number,
+        "m" => number * 60.0,
+        "h" => number * 3600.0,
+        _ => return Err(format!("unknown duration unit in `{}`", token)),
+    };
+    Duration::try_from_secs_f64(seconds).map_err(|_| format!("invalid duration `{}`", token))
+}
+
+fn tokenize(source: &str) -> Result<Vec<String>, String> {
+    let mut tokens = Vec::new();
+    let mut chars = source.chars().peekable();
+
+    while let Some(&c) = chars.peek() {
+        if c.is_whitespace() {
+            chars.next();
+        } else if c == '"' {
+            let mut token = String::from(chars.next().unwrap());
+            loop {
+                match chars.next() {
+                    Some('"') => break,
+                    Some(c) => token.push(c),
+                    None => return Err("unterminated string".to_string()),
+                }
+            }
+            token.push('"');
+            tokens.push(token);
+        } else if "<>=!&".contains(c) {
+            let mut token = String::new();
+            while let Some(&c) = chars.peek() {
+                if !"<>=!&".contains(c) {
+                    break;
+                }
+                token.push(c);
+                chars.next();
+            }
+            tokens.push(token);
+        } else {
+            let mut token = String::new();
+            while let Some(&c) = chars.peek() {
+                if c.is_whitespace() || "<>=!&\"".contains(c) {
+                    break;
+                }
+                token.push(c);
+                chars.next();
+            }
+            tokens.push(token);
+        }
+    }
+
+    Ok(tokens)
+}
+
+#[derive(Debug, Clone)]
+struct Clause {
+    metric: Metric,
+    metric_name: String,
+    operator: Operator,
+    literal: Literal,
+}
+
+impl Clause {
+    fn holds(&self, sample: &Sample, margin: f64) -> bool {
+        match (sample, &self.literal) {
+            (Sample::Text(value), Literal::Text(pattern)) => {
+                let matched = glob_match(pattern, value);
+                match self.operator {
+                    Operator::Eq => matched,
+                    Operator::Ne => !matched,
+                    _ => false,
+                }
+            }
+            (Sample::Number { value, .. }, Literal::Number(threshold)) => {
+                let threshold = self.operator.relax(*threshold, threshold.abs() * margin);
+                self.operator.compare(*value, threshold)
+            }
+            (
+                Sample::Number {
+                    percent: Some(percent),
+                    ..
+                },
+                Literal::Percent(threshold),
+            ) => {
+                let threshold = self.operator.relax(*threshold, threshold.abs() * margin);
+                self.operator.compare(*percent, threshold)
+            }
+            _ => false,
+        }
+    }
+
+    fn describe(&self, sample: &Sample, units: &Units) -> String {
+        let value = match (sample, &self.literal) {
+            (Sample::Text(value), _) => value.clone(),
+            (Sample::Number { percent, .. }, Literal::Percent(_)) => {
+                format!("{:.1}%", percent.unwrap_or(0.0))
+            }
+            (Sample::Number { value, .. }, _) => match self.metric.kind() {
+                Kind::Percent => format!("{:.1}%", value),
+                Kind::Bytes => units.bytes(*value as u64),
+                Kind::Rate => units.rate(*value),
+                Kind::Count | Kind::Text => format!("{:.0}", value),
+            },
+        };
+        format!("{} = {}", self.metric_name, value)
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Condition {
+    clauses: Vec<Clause>,
+    duration: Duration,
+}
+
+impl Condition {
+    pub fn parse(source: &str) -> Result<Self, String> {
+        let tokens = tokenize(source)?;
+        let mut clauses = Vec::new();
+        let mut duration = Duration::ZERO;
+        let mut idx = 0;
+
+        loop {
+            let metric_name = tokens.get(idx).ok_or("expected a metric")?;
+            let operator = tokens
+                .get(idx + 1)
+                .and_then(|token| Operator::parse(token))
+                .ok_or_else(|| format!("expected an operator after `{}`", metric_name))?;
+            let literal = tokens
+                .get(idx + 2)
+                .ok_or_else(|| format!("expected a value after `{}`", operator.label()))?;
+
+            let metric = Metric::parse(metric_name)?;
+            let literal = parse_literal(literal)?;
+            let text_metric = metric.kind() == Kind::Text;
+            let text_literal = matches!(literal, Literal::Text(_));
+            if text_metric != text_literal {
+                return Err(format!(
+                    "`{}` cannot be compared with that value",
+                    metric_name
+                ));
+            }
+            if text_metric && !matches!(operator, Operator::Eq | Operator::Ne) {
+                return Err(format!("`{}` only supports == and !=", metric_name));
+            }
+
+            clauses.push(Clause {
+                metric,
+                metric_name: metric_name.clone(),
+                operator,
+                literal,
+            });
+            idx += 3;
+
+            match tokens.get(idx).map(String::as_str) {
+                None => break,
+                Some("&&") => idx += 1,
+                Some("for") => {
+                    let token = tokens
+                        .get(idx + 1)
+                        .ok_or("expected a duration after `for`")?;
+                    duration = parse_duration(token)?;
+                    if idx + 2 != tokens.len() {
+                        return Err("`for` must come last".to_string());
+                    }
+                    break;
+                }
+                Some(token) => return Err(format!("unexpected `{}`", token)),
+            }
+        }
+
+        let bare = |clause: &Clause| matches!(clause.metric_name.as_str(), "cpu" | "mem");
+        let process_rule = clauses.iter().any(|clause| clause.metric.is_process());
+        let system_clause = clauses
+            .iter()
+            .any(|clause| !bare(clause) && !clause.metric.is_process());
+        if process_rule && system_clause {
+            return Err("process and system metrics cannot be combined".to_string());
+        }
+        if process_rule {
+            for clause in clauses.iter_mut().filter(|clause| bare(clause)) {
+                clause.metric = clause.metric.clone().for_process();
+            }
+        }
+
+        Ok(Self { clauses, duration })
+    }
+
+    pub fn get_duration(&self) -> Duration {
+        self.duration
+    }
+
+    fn is_process_rule(&self) -> bool {
+        self.clauses.iter().any(|clause| clause.metric.is_process())
+    }
+
+    fn matches(
+        &self,
+        system: &SystemState,
+        process: Option<&Process>,
+        margin: f64,
+    ) -> Option<Vec<Sample>> {
+        let mut samples = Vec::with_capacity(self.clauses.len());
+        for clause in &self.clauses {
+            let sample = clause.metric.sample(system, process)?;
+            if !clause.holds(&sample, margin) {
+                return None;
+            }
+            samples.push(sample);
+        }
+        Some(samples)
+    }
+
+    pub fn evaluate(&self, system: &SystemState, hysteresis: f64, units: &Units) -> Evaluation {
+        if !self.is_process_rule() {
+            let triggered = self.matches(system, None, 0.0);
+            let holding = self.matches(system, None, hysteresis);
+            let detail = holding
+                .as_ref()
+                .map(|samples| self.describe(samples, units))
+                .unwrap_or_default();
+            return Evaluation {
+                triggered: triggered.is_some(),
+                holding: holding.is_some(),
+                detail,
+            };
+        }
+
+        let mut triggered = false;
+        let mut matched = Vec::new();
+        for process in system.processes.get_processes() {
+            if let Some(samples) = self.matches(system, Some(process), hysteresis) {
+                triggered |= self.matches(system, Some(process), 0.0).is_some();
+                matched.push(
+                    format!(
+                        "{}[{}] {}",
+                        process.get_name(),
+                        process.get_pid(),
+                        self.describe(&samples, units)
+                    )
+                    .trim_end()
+                    .to_string(),
+                );
+            }
+        }
+        matched.sort();
+
+        let detail = match matched.len() {
+            0..=2 => matched.join("; "),
+            count => format!("{}; +{} more", matched[..2].join("; "), count - 2),
+        };
+        Evaluation {
+            triggered,
+            holding: !matched.is_empty(),
+            detail,
+        }
+    }
+
+    fn describe(&self, samples: &[Sample], units: &Units) -> String {
+        self.clauses
+            .iter()
+            .zip(samples)
+            .filter(|(clause, _)| clause.metric.kind() != Kind::Text)
+            .map(|(clause, sample)| clause.describe(sample, units))
+            .collect::<Vec<_>>()
+            .join(", ")
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct Evaluation {
+    pub triggered: bool,
+    pub holding: bool,
+    pub detail: String,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn metrics(source: &str) -> Vec<Metric> {
+        Condition::parse(source)
+            .unwrap()
+            .clauses
+            .into_iter()
+            .map(|clause| clause.metric)
+            .collect()
+    }
+
+    #[test]
+    fn bare_metrics_follow_process_clauses() {
+        assert_eq!(
+            metrics("process.name == \"java\" && mem > 8GiB"),
+            [Metric::ProcessName, Metric::ProcessMem]
+        );
+        assert_eq!(
+            metrics("cpu > 50 && process.user == \"root\""),
+            [Metric::ProcessCpu, Metric::ProcessUser]
+        );
+    }
+
+    #[test]
+    fn bare_metrics_default_to_system() {
+        assert_eq!(metrics("cpu > 90 for 30s"), [Metric::CpuAvg]);
+        assert_eq!(
+            metrics("mem > 90% && swap.used > 1GiB"),
+            [Metric::MemUsed, Metric::SwapUsed]
+        );
+        assert_eq!(
+            metrics("cpu > 90 && mem > 90%"),
+            [Metric::CpuAvg, Metric::MemUsed]
+        );
+    }
+
+    #[test]
+    fn process_and_system_metrics_are_not_combined() {
+        assert_eq!(
+            Condition::parse("process.cpu > 50 && swap.used > 1GiB").unwrap_err(),
+            "process and system metrics cannot be combined"
+        );
+    }
+}
@@ -33,4 +33,13 @@ pub struct Args {
 
     #[arg(short, long)]
     pub filter: Option<String>,
+
+    #[arg(long)]
+    pub dry_run: bool,
+
+    #[arg(long)]
+    pub list_keys: bool,
+
+    #[arg(long)]
+    pub no_mouse: bool,
 }
@@ -1,15 +1,47 @@
-use anyhow::Result;
+use anyhow::{bail, Result};
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 use std::fs;
-use std::path::Path;
+use std::path::{Path, PathBuf};
 
 #[derive(Debug, Serialize, Deserialize)]
+#[serde(default)]
 pub struct Config {
     pub update_interval: u64,
     pub theme: String,
     pub layout: LayoutConfig,
     pub sort_by: String,
     pub filters: Vec<String>,
+    #[serde(default)]
+    pub pressure_cgroups: Vec<String>,
+    #[serde(default)]
+    pub process: ProcessConfig,
+    #[serde(default)]
+    pub disk: DiskConfig,
+    #[serde(default)]
+    pub connections: ConnectionsConfig,
+    #[serde(default)]
+    pub network: NetworkConfig,
+    #[serde(default)]
+    pub units: UnitsConfig,
+    #[serde(default)]
+    pub containers: ContainersConfig,
+    #[serde(default)]
+    pub systemd: SystemdConfig,
+    #[serde(default)]
+    pub alerts: AlertsConfig,
+    #[serde(default)]
+    pub keys: KeysConfig,
+    #[serde(default)]
+    pub mouse: MouseConfig,
+    #[serde(default)]
+    pub layouts: BTreeMap<String, LayoutDefinition>,
+    #[serde(default)]
+    pub tabs: TabsConfig,
+    #[serde(default)]
+    pub graphs: GraphsConfig,
+    #[serde(skip)]
+    pub path: Option<String>,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -21,6 +53,351 @@ pub struct LayoutConfig {
     pub show_process_details: bool,
 }
 
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ProcessConfig {
+    pub smaps_accounting: bool,
+    pub smaps_interval: u64,
+    pub memory_column: String,
+}
+
+impl Default for ProcessConfig {
+    fn default() -> Self {
+        Self {
+            smaps_accounting: false,
+            smaps_interval: 5000,
+            memory_column: "rss".to_string(),
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(default)]
+pub struct DiskConfig {
+    pub hide_pseudo: bool,
+    pub include_fs: Vec<String>,
+    pub exclude_fs: Vec<String>,
+    pub include_devices: Vec<String>,
+    pub exclude_devices: Vec<String>,
+    pub include_mounts: Vec<String>,
+    pub exclude_mounts: Vec<String>,
+    pub forecast_window: u64,
+    pub forecast_warning: u64,
+}
+
+impl Default for DiskConfig {
+    fn default() -> Self {
+        Self {
+            hide_pseudo: true,
+            include_fs: vec![],
+            exclude_fs: vec![],
+            include_devices: vec![],
+            exclude_devices: vec![],
+            include_mounts: vec![],
+            exclude_mounts: vec![],
+            forecast_window: 1800,
+            forecast_warning: 86400,
+        }
+    }
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ConnectionsConfig {
+    pub filter: String,
+    pub show_unix: bool,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(default)]
+pub struct NetworkConfig {
+    pub interface: String,
+    pub include: Vec<String>,
+    pub exclude: Vec<String>,
+    pub sort_by: String,
+    pub group_virtual: bool,
+    pub collapse_virtual: bool,
+}
+
+impl Default for NetworkConfig {
+    fn default() -> Self {
+        Self {
+            interface: String::new(),
+            include: vec![],
+            exclude: vec![],
+            sort_by: "name".to_string(),
+            group_virtual: true,
+            collapse_virtual: true,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(default)]
+pub struct UnitsConfig {
+    pub byte_base: u32,
+    pub network_bits: bool,
+    pub precision: usize,
+}
+
+impl Default for UnitsConfig {
+    fn default() -> Self {
+        Self {
+            byte_base: 1024,
+            network_bits: false,
+            precision: 1,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ContainersConfig {
+    pub resolve_names: bool,
+    pub sockets: Vec<String>,
+    pub refresh_interval: u64,
+}
+
+impl Default for ContainersConfig {
+    fn default() -> Self {
+        Self {
+            resolve_names: true,
+            sockets: vec![
+                "/var/run/docker.sock".to_string(),
+                "/run/podman/podman.sock".to_string(),
+            ],
+            refresh_interval: 10000,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SystemdConfig {
+    pub bus: String,
+    pub refresh_interval: u64,
+}
+
+impl Default for SystemdConfig {
+    fn default() -> Self {
+        Self {
+            bus: "system".to_string(),
+            refresh_interval: 5000,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(default)]
+pub struct AlertsConfig {
+    pub hysteresis: f64,
+    pub history: usize,
+    pub dry_run: bool,
+    pub rate_limit: u64,
+    pub actions: BTreeMap<String, AlertActionConfig>,
+    pub rules: Vec<AlertRuleConfig>,
+}
+
+impl Default for AlertsConfig {
+    fn default() -> Self {
+        Self {
+            hysteresis: 5.0,
+            history: 50,
+            dry_run: false,
+            rate_limit: 300,
+            actions: BTreeMap::new(),
+            rules: vec![],
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(default)]
+pub struct AlertRuleConfig {
+    pub name: String,
+    pub condition: String,
+    pub severity: String,
+    pub hysteresis: Option<f64>,
+    pub actions: Vec<String>,
+}
+
+impl Default for AlertRuleConfig {
+    fn default() -> Self {
+        Self {
+            name: String::new(),
+            condition: String::new(),
+            severity: "warning".to_string(),
+            hysteresis: None,
+            actions: vec![],
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(default)]
+pub struct AlertActionConfig {
+    pub kind: String,
+    pub command: String,
+    pub path: String,
+    pub url: String,
+    pub on_resolve: bool,
+}
+
+impl Default for AlertActionConfig {
+    fn default() -> Self {
+        Self {
+            kind: "command".to_string(),
+            command: String::new(),
+            path: String::new(),
+            url: String::new(),
+            on_resolve: true,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(default)]
+pub struct KeysConfig {
+    pub preset: String,
+    pub bindings: BTreeMap<String, Vec<String>>,
+}
+
+impl Default for KeysConfig {
+    fn default() -> Self {
+        Self {
+            preset: "default".to_string(),
+            bindings: BTreeMap::new(),
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(default)]
+pub struct MouseConfig {
+    pub enabled: bool,
+    pub scroll_lines: usize,
+}
+
+impl Default for MouseConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            scroll_lines: 3,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(default)]
+pub struct TabsConfig {
+    pub enabled: bool,
+    pub pages: Vec<String>,
+    pub selected: String,
+}
+
+impl Default for TabsConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            pages: [
+                "default",
+                "processes",
+                "cpu",
+                "memory",
+                "disks",
+                "network",
+                "sensors",
+            ]
+            .iter()
+            .map(|page| page.to_string())
+            .collect(),
+            selected: String::new(),
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(default)]
+pub struct GraphsConfig {
+    pub history: String,
+    pub window: String,
+    pub windows: Vec<String>,
+    pub marker: String,
+}
+
+impl Default for GraphsConfig {
+    fn default() -> Self {
+        Self {
+            history: "30m".to_string(),
+            window: "5m".to_string(),
+            windows: vec!["1m".to_string(), "5m".to_string(), "30m".to_string()],
+            marker: "braille".to_string(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct LayoutDefinition {
+    #[serde(skip_serializing_if = "String::is_empty")]
+    pub key: String,
+    #[serde(skip_serializing_if = "String::is_empty")]
+    pub title: String,
+    #[serde(flatten)]
+    pub root: LayoutNodeConfig,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct LayoutNodeConfig {
+    #[serde(skip_serializing_if = "String::is_empty")]
+    pub widget: String,
+    #[serde(skip_serializing_if = "String::is_empty")]
+    pub size: String,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub rows: Vec<LayoutNodeConfig>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub cols: Vec<LayoutNodeConfig>,
+}
+
+pub fn builtin_layouts() -> BTreeMap<String, LayoutDefinition> {
+    toml::from_str(include_str!("layouts.toml")).expect("built-in layouts are valid")
+}
+
+fn last_position(item: &toml_edit::Item) -> usize {
+    let tables: Vec<&toml_edit::Table> = match item {
+        toml_edit::Item::Table(table) => vec![table],
+        toml_edit::Item::ArrayOfTables(array) => array.iter().collect(),
+        _ => Vec::new(),
+    };
+    tables
+        .into_iter()
+        .flat_map(|table| {
+            let own = table.position().unwrap_or(0);
+            table
+                .iter()
+                .map(|(_, child)| last_position(child))
+                .chain([own])
+        })
+        .max()
+        .unwrap_or(0)
+}
+
+fn place_tables(item: &mut toml_edit::Item, position: &mut usize) {
+    let tables: Vec<&mut toml_edit::Table> = match item {
+        toml_edit::Item::Table(table) => vec![table],
+        toml_edit::Item::ArrayOfTables(array) => array.iter_mut().collect(),
+        _ => Vec::new(),
+    };
+    for table in tables {
+        table.set_position(*position);
+        *position += 1;
+        for (_, child) in table.iter_mut() {
+            place_tables(child, position);
+        }
+    }
+}
+
 impl Default for Config {
     fn default() -> Self {
         Self {
@@ -35,6 +412,21 @@ impl Default for Config {
             },
             sort_by: "cpu".to_string(),
             filters: vec![],
+            pressure_cgroups: vec![],
+            process: ProcessConfig::default(),
+            disk: DiskConfig::default(),
+            connections: ConnectionsConfig::default(),
+            network: NetworkConfig::default(),
+            units: UnitsConfig::default(),
+            containers: ContainersConfig::default(),
+            systemd: SystemdConfig::default(),
+            alerts: AlertsConfig::default(),
+            keys: KeysConfig::default(),
+            mouse: MouseConfig::default(),
+            layouts: BTreeMap::new(),
+            tabs: TabsConfig::default(),
+            graphs: GraphsConfig::default(),
+            path: None,
         }
     }
 }
@@ -44,8 +436,11 @@ impl Config {
         if let Some(config_path) = path {
             if Path::new(config_path).exists() {
                 match fs::read_to_string(config_path) {
-                    Ok(content) => match toml::from_str(&content) {
-                        Ok(config) => return Ok(config),
+                    Ok(content) => match toml::from_str::<Config>(&content) {
+                        Ok(mut config) => {
+                            config.path = Some(config_path.to_string());
+                            return Ok(config);
+                        }
                         Err(e) => eprintln!("Error parsing config file: {}", e),
                     },
                     Err(e) => eprintln!("Error reading config file: {}", e),
@@ -60,10 +455,10 @@ impl Config {
                     match fs::read_to_string(&path) {
                         Ok(content) => {
                             if *ext == "toml" {
-                                if let Ok(config) = toml::from_str(&content) {
+                                if let Ok(mut config) = toml::from_str::<Config>(&content) {
+                                    config.path = Some(path.to_string_lossy().to_string());
                                     return Ok(config);
                                 }
-                            } else {
                             }
                         }
                         Err(e) => eprintln!("Error reading config at {:?}: {}", path, e),
@@ -77,13 +472,153 @@ impl Config {
             eprintln!("Using pkg/config.yaml as fallback");
         }
 
-        Ok(Config::default())
+        Ok(Config {
+            path: path.map(|path| path.to_string()).or_else(|| {
+                dirs::config_dir()
+                    .map(|dir| dir.join("rtop/config.toml").to_string_lossy().to_string())
+            }),
+            ..Config::default()
+        })
+    }
+
+    pub fn save_layouts(path: &str, layouts: &BTreeMap<String, LayoutDefinition>) -> Result<()> {
+        let content = if Path::new(path).exists() {
+            fs::read_to_string(path)?
+        } else {
+            String::new()
+        };
+        let mut document: toml_edit::Document = content.parse()?;
+        let rendered: toml_edit::Document =
+            toml::to_string(&BTreeMap::from([("layouts", layouts)]))?.parse()?;
+
+        if !document.contains_table("layouts") {
+            let mut table = toml_edit::Table::new();
+            table.set_implicit(true);
+            document.insert("layouts", toml_edit::Item::Table(table));
+        }
+        let mut position = last_position(document.as_item()) + 1;
+        let saved = match document["layouts"].as_table_mut() {
+            Some(table) => table,
+            None => bail!("`layouts` in {} is not a table", path),
+        };
+        for name in layouts.keys() {
+            let mut layout = rendered["layouts"][name.as_str()].clone();
+            place_tables(&mut layout, &mut position);
+            if let Some(table) = layout.as_table_mut() {
+                table.decor_mut().set_prefix("\n");
+            }
+            saved.insert(name, layout);
+        }
+
+        if let Some(dir) = Path::new(path).parent() {
+            fs::create_dir_all(dir)?;
+        }
+        fs::write(path, document.to_string())?;
+        Ok(())
+    }
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct State {
+    pub tab: String,
+}
+
+impl State {
+    fn path() -> Option<PathBuf> {
+        dirs::state_dir()
+            .or_else(dirs::data_local_dir)
+            .map(|dir| dir.join("rtop/state.toml"))
+    }
+
+    pub fn load() -> Self {
+        Self::path()
+            .and_then(|path| fs::read_to_string(path).ok())
+            .and_then(|content| toml::from_str(&content).ok())
+            .unwrap_or_default()
     }
 
-    #[allow(dead_code)]
-    pub fn save(&self, path: &str) -> Result<()> {
-        let content = toml::to_string_pretty(self)?;
-        fs::write(path, content)?;
+    pub fn save(&self) -> Result<()> {
+        let path = match Self::path() {
+            Some(path) => path,
+            None => bail!("no state directory"),
+        };
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        fs::write(path, toml::to_string(self)?)?;
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn save_layouts_keeps_the_rest_of_the_file() {
+        let path = std::env::temp_dir().join(format!("rtop-{}-layouts.toml", std::process::id()));
+        let original = "# refresh quickly\n\
+                        update_interval = 500 # ms\n\
+                        theme = \"dark\"\n\
+                        sort_by = \"cpu\"\n\
+                        filters = []\n\
+                        \n\
+                        [layout]\n\
+                        show_cpu = true\n\
+                        show_memory = true\n\
+                        show_network = false\n\
+                        show_disk = true\n\
+                        show_process_details = true\n\
+                        \n\
+                        [layouts.other]\n\
+                        widget = \"processes\"\n";
+        fs::write(&path, original).unwrap();
+
+        let layout: LayoutDefinition = toml::from_str(
+            "key = \"e\"\n\
+             [[cols]]\n\
+             widget = \"cpu_graph\"\n\
+             size = \"40%\"\n\
+             [[cols]]\n\
+             widget = \"processes\"\n",
+        )
+        .unwrap();
+        let path_text = path.to_string_lossy().to_string();
+        Config::save_layouts(&path_text, &BTreeMap::from([("mine".to_string(), layout)])).unwrap();
+
+        let saved = fs::read_to_string(&path).unwrap();
+        fs::remove_file(&path).unwrap();
+        assert!(saved.starts_with("# refresh quickly\nupdate_interval = 500 # ms\n"));
+        assert!(!saved.contains("[process]"));
+
+        let config: Config = toml::from_str(&saved).unwrap();
+        assert_eq!(config.theme, "dark");
+        assert_eq!(config.layouts["other"].root.widget, "processes");
+        let mine = &config.layouts["mine"];
+        assert_eq!(mine.key, "e");
+        assert_eq!(mine.root.cols.len(), 2);
+        assert_eq!(mine.root.cols[0].size, "40%");
+    }
+
+    #[test]
+    fn save_layouts_creates_a_loadable_file() {
+        let path = std::env::temp_dir().join(format!("rtop-{}-new.toml", std::process::id()));
+        let _ = fs::remove_file(&path);
+        let path_text = path.to_string_lossy().to_string();
+        let layout = LayoutDefinition {
+            root: LayoutNodeConfig {
+                widget: "processes".to_string(),
+                ..LayoutNodeConfig::default()
+            },
+            ..LayoutDefinition::default()
+        };
+        Config::save_layouts(&path_text, &BTreeMap::from([("mine".to_string(), layout)])).unwrap();
+
+        let config = Config::load(Some(&path_text)).unwrap();
+        fs::remove_file(&path).unwrap();
+        assert_eq!(config.path.as_deref(), Some(path_text.as_str()));
+        assert_eq!(config.update_interval, Config::default().update_interval);
+        assert_eq!(config.layouts["mine"].root.widget, "processes");
+    }
+}
@@ -1,7 +1,9 @@
+mod alerts;
 mod cli;
 mod config;
 mod system;
 mod ui;
+mod util;
 
 use anyhow::Result;
 use clap::Parser;
@@ -9,9 +11,22 @@ use clap::Parser;
 fn main() -> Result<()> {
     let args = cli::Args::parse();
 
-    let config = config::Config::load(args.config.as_deref())?;
+    let mut config = config::Config::load(args.config.as_deref())?;
+    if args.dry_run {
+        config.alerts.dry_run = true;
+    }
+    if args.no_mouse {
+        config.mouse.enabled = false;
+    }
 
-    let mut app = ui::App::new(config);
+    if args.list_keys {
+        let layouts = ui::layout::LayoutSet::from_config(&config)?;
+        let keymap = ui::Keymap::from_config(&config.keys, &layouts.key_bindings())?;
+        print!("{}", keymap.help_text());
+        return Ok(());
+    }
+
+    let mut app = ui::App::new(config)?;
     app.run()?;
 
     Ok(())
@@ -0,0 +1,318 @@
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::system::disk::parse_mountinfo;
+
+const SYS_BLOCK: &str = "/sys/block";
+const SYS_CLASS_BLOCK: &str = "/sys/class/block";
+const PROC_MDSTAT: &str = "/proc/mdstat";
+const PROC_MOUNTINFO: &str = "/proc/self/mountinfo";
+const MAX_DEPTH: usize = 8;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BlockKind {
+    Disk,
+    Partition,
+    Lvm,
+    Crypt,
+    Raid,
+    DeviceMapper,
+    Loop,
+}
+
+impl BlockKind {
+    pub fn label(&self) -> &'static str {
+        match self {
+            BlockKind::Disk => "disk",
+            BlockKind::Partition => "part",
+            BlockKind::Lvm => "lvm",
+            BlockKind::Crypt => "crypt",
+            BlockKind::Raid => "raid",
+            BlockKind::DeviceMapper => "dm",
+            BlockKind::Loop => "loop",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct RaidStatus {
+    pub level: String,
+    pub active: bool,
+    pub members: String,
+    pub degraded: bool,
+    pub sync_action: Option<String>,
+    pub sync_progress: Option<f64>,
+}
+
+impl RaidStatus {
+    pub fn summary(&self) -> String {
+        let mut parts = vec![self.level.clone()];
+        if !self.members.is_empty() {
+            parts.push(format!("[{}]", self.members));
+        }
+        if !self.active {
+            parts.push("inactive".to_string());
+        }
+        if self.degraded {
+            parts.push("DEGRADED".to_string());
+        }
+        if let Some(action) = &self.sync_action {
+            match self.sync_progress {
+                Some(progress) => parts.push(format!("{} {:.1}%", action, progress)),
+                None => parts.push(action.clone()),
+            }
+        }
+        parts.join(" ")
+    }
+}
+
+pub fn parse_mdstat(content: &str) -> HashMap<String, RaidStatus> {
+    let mut arrays: HashMap<String, RaidStatus> = HashMap::new();
+    let mut current: Option<String> = None;
+
+    for line in content.lines() {
+        if let Some((name, rest)) = line.split_once(" : ") {
+            let name = name.trim();
+            if name.starts_with("md") {
+                let mut fields = rest.split_whitespace();
+                let state = fields.next().unwrap_or("");
+                let level = fields
+                    .next()
+                    .filter(|field| field.starts_with("raid") || *field == "linear")
+                    .unwrap_or("")
+                    .to_string();
+
+                arrays.insert(
+                    name.to_string(),
+                    RaidStatus {
+                        level,
+                        active: state == "active",
+                        ..RaidStatus::default()
+                    },
+                );
+                current = Some(name.to_string());
+                continue;
+            }
+        }
+
+        let status = match current.as_ref().and_then(|name| arrays.get_mut(name)) {
+            Some(status) => status,
+            None => continue,
+        };
+
+        if line.trim().is_empty() {
+            current = None;
+            continue;
+        }
+
+        if let Some(start) = line.rfind('[') {
+            let members = &line[start + 1..];
+            if let Some(end) = members.find(']') {
+                let members = &members[..end];
+                if !members.is_empty() && members.chars().all(|c| c == 'U' || c == '_') {
+                    status.members = members.to_string();
+                    status.degraded = members.contains('_');
+                }
+            }
+        }
+
+        for action in ["resync", "recovery", "reshape", "check", "repair"] {
+            if let Some(pos) = line.find(&format!("{} =", action)) {
+                status.sync_action = Some(action.to_string());
+                status.sync_progress = line[pos + action.len() + 2..]
+                    .split('%')
+                    .next()
+                    .and_then(|value| value.trim().parse().ok());
+            } else if line.contains(&format!("{}=DELAYED", action))
+                || line.contains(&format!("{}=PENDING", action))
+            {
+                status.sync_action = Some(format!("{} pending", action));
+            }
+        }
+    }
+
+    arrays
+}
+
+#[derive(Debug, Clone)]
+pub struct BlockNode {
+    pub name: String,
+    pub display_name: String,
+    pub kind: BlockKind,
+    pub size: u64,
+    pub mount_point: Option<String>,
+    pub raid: Option<RaidStatus>,
+    pub children: Vec<BlockNode>,
+}
+
+impl BlockNode {
+    pub fn is_degraded(&self) -> bool {
+        self.raid.as_ref().map(|r| r.degraded).unwrap_or(false)
+            || self.children.iter().any(BlockNode::is_degraded)
+    }
+}
+
+struct TreeContext {
+    mounts: HashMap<String, String>,
+    raid: HashMap<String, RaidStatus>,
+}
+
+fn read_trimmed(path: &Path) -> Option<String> {
+    fs::read_to_string(path)
+        .ok()
+        .map(|content| content.trim().to_string())
+}
+
+fn list_dir(path: &Path) -> Vec<String> {
+    let mut names: Vec<String> = fs::read_dir(path)
+        .map(|entries| {
+            entries
+                .filter_map(|entry| entry.ok())
+                .map(|entry| entry.file_name().to_string_lossy().to_string())
+                .collect()
+        })
+        .unwrap_or_default();
+    names.sort();
+    names
+}
+
+fn class_path(name: &str) -> PathBuf {
+    Path::new(SYS_CLASS_BLOCK).join(name)
+}
+
+fn device_kind(name: &str, path: &Path) -> BlockKind {
+    if path.join("partition").exists() {
+        return BlockKind::Partition;
+    }
+    if name.starts_with("loop") {
+        return BlockKind::Loop;
+    }
+    if path.join("md").exists() {
+        return BlockKind::Raid;
+    }
+    if path.join("dm").exists() {
+        let uuid = read_trimmed(&path.join("dm/uuid")).unwrap_or_default();
+        return if uuid.starts_with("LVM-") {
+            BlockKind::Lvm
+        } else if uuid.starts_with("CRYPT-") {
+            BlockKind::Crypt
+        } else {
+            BlockKind::DeviceMapper
+        };
+    }
+    BlockKind::Disk
+}
+
+fn build_node(name: &str, context: &TreeContext, depth: usize) -> BlockNode {
+    let path = class_path(name);
+    let kind = device_kind(name, &path);
+
+    let display_name = match kind {
+        BlockKind::Lvm | BlockKind::Crypt | BlockKind::DeviceMapper => {
+            match read_trimmed(&path.join("dm/name")) {
+                Some(dm_name) => format!("{} ({})", dm_name, name),
+                None => name.to_string(),
+            }
+        }
+        _ => name.to_string(),
+    };
+
+    let size = read_trimmed(&path.join("size"))
+        .and_then(|value| value.parse::<u64>().ok())
+        .unwrap_or(0)
+        * 512;
+
+    let mount_point =
+        read_trimmed(&path.join("dev")).and_then(|dev| context.mounts.get(&dev).cloned());
+
+    let mut children = Vec::new();
+    if depth < MAX_DEPTH {
+        if kind != BlockKind::Partition {
+            for entry in list_dir(&path) {
+                if entry.starts_with(name) && path.join(&entry).join("partition").exists() {
+                    children.push(build_node(&entry, context, depth + 1));
+                }
+            }
+        }
+
+        for holder in list_dir(&path.join("holders")) {
+            children.push(build_node(&holder, context, depth + 1));
+        }
+    }
+
+    BlockNode {
+        name: name.to_string(),
+        display_name,
+        kind,
+        size,
+        mount_point,
+        raid: context.raid.get(name).cloned(),
+        children,
+    }
+}
+
+pub struct BlockTree {
+    roots: Vec<BlockNode>,
+    raid: HashMap<String, RaidStatus>,
+    enabled: bool,
+}
+
+impl BlockTree {
+    pub fn new() -> Self {
+        let mut tree = Self {
+            roots: Vec::new(),
+            raid: HashMap::new(),
+            enabled: true,
+        };
+        tree.update();
+        tree
+    }
+
+    pub fn update(&mut self) {
+        if !self.enabled {
+            return;
+        }
+        let mounts = fs::read_to_string(PROC_MOUNTINFO)
+            .map(|content| {
+                parse_mountinfo(&content)
+                    .into_iter()
+                    .map(|entry| (entry.major_minor, entry.mount_point))
+                    .collect()
+            })
+            .unwrap_or_default();
+
+        let raid = fs::read_to_string(PROC_MDSTAT)
+            .map(|content| parse_mdstat(&content))
+            .unwrap_or_default();
+
+        let context = TreeContext { mounts, raid };
+
+        self.roots = list_dir(Path::new(SYS_BLOCK))
+            .into_iter()
+            .filter(|name| {
+                list_dir(&class_path(name).join("slaves")).is_empty()
+                    && read_trimmed(&class_path(name).join("size")).as_deref() != Some("0")
+            })
+            .map(|name| build_node(&name, &context, 0))
+            .collect();
+
+        self.raid = context.raid;
+    }
+
+    pub fn set_enabled(&mut self, enabled: bool) {
+        self.enabled = enabled;
+    }
+
+    pub fn get_roots(&self) -> &[BlockNode] {
+        &self.roots
+    }
+
+    pub fn get_raid_arrays(&self) -> &HashMap<String, RaidStatus> {
+        &self.raid
+    }
+
+    pub fn has_degraded_raid(&self) -> bool {
+        self.raid.values().any(|status| status.degraded)
+    }
+}
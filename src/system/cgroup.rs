@@ -0,0 +1,315 @@
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::Instant;
+
+const CGROUP_ROOT: &str = "/sys/fs/cgroup";
+const CGROUP_UNIFIED: &str = "/sys/fs/cgroup/unified";
+const MAX_DEPTH: usize = 16;
+
+#[derive(Debug, Clone, Copy, Default)]
+pub struct CgroupStats {
+    pub cpu_usage_usec: Option<u64>,
+    pub memory_current: Option<u64>,
+    pub memory_max: Option<u64>,
+    pub io_read_bytes: Option<u64>,
+    pub io_write_bytes: Option<u64>,
+    pub pids_current: Option<u64>,
+}
+
+impl CgroupStats {
+    fn aggregate(&mut self, child: &CgroupStats) {
+        fn add(total: &mut Option<u64>, value: Option<u64>) {
+            if let Some(value) = value {
+                *total = Some(total.unwrap_or(0) + value);
+            }
+        }
+
+        add(&mut self.cpu_usage_usec, child.cpu_usage_usec);
+        add(&mut self.memory_current, child.memory_current);
+        add(&mut self.io_read_bytes, child.io_read_bytes);
+        add(&mut self.io_write_bytes, child.io_write_bytes);
+        add(&mut self.pids_current, child.pids_current);
+    }
+
+    fn fill_missing(&mut self, aggregated: &CgroupStats) {
+        self.cpu_usage_usec = self.cpu_usage_usec.or(aggregated.cpu_usage_usec);
+        self.memory_current = self.memory_current.or(aggregated.memory_current);
+        self.io_read_bytes = self.io_read_bytes.or(aggregated.io_read_bytes);
+        self.io_write_bytes = self.io_write_bytes.or(aggregated.io_write_bytes);
+        self.pids_current = self.pids_current.or(aggregated.pids_current);
+    }
+}
+
+pub fn parse_cpu_stat(content: &str) -> Option<u64> {
+    content.lines().find_map(|line| {
+        line.strip_prefix("usage_usec ")
+            .and_then(|value| value.trim().parse().ok())
+    })
+}
+
+pub fn parse_io_stat(content: &str) -> (u64, u64) {
+    let mut read = 0;
+    let mut write = 0;
+    for field in content.split_whitespace() {
+        if let Some(value) = field.strip_prefix("rbytes=") {
+            read += value.parse::<u64>().unwrap_or(0);
+        } else if let Some(value) = field.strip_prefix("wbytes=") {
+            write += value.parse::<u64>().unwrap_or(0);
+        }
+    }
+    (read, write)
+}
+
+fn read_value(path: &Path) -> Option<u64> {
+    fs::read_to_string(path)
+        .ok()
+        .and_then(|content| content.trim().parse().ok())
+}
+
+fn read_stats(path: &Path) -> CgroupStats {
+    let io = fs::read_to_string(path.join("io.stat"))
+        .ok()
+        .map(|content| parse_io_stat(&content));
+
+    CgroupStats {
+        cpu_usage_usec: fs::read_to_string(path.join("cpu.stat"))
+            .ok()
+            .and_then(|content| parse_cpu_stat(&content)),
+        memory_current: read_value(&path.join("memory.current")),
+        memory_max: read_value(&path.join("memory.max")),
+        io_read_bytes: io.map(|(read, _)| read),
+        io_write_bytes: io.map(|(_, write)| write),
+        pids_current: read_value(&path.join("pids.current")),
+    }
+}
+
+fn read_procs(path: &Path) -> Vec<u32> {
+    fs::read_to_string(path.join("cgroup.procs"))
+        .map(|content| {
+            content
+                .lines()
+                .filter_map(|line| line.trim().parse().ok())
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+pub fn read_process_cgroup(pid: u32) -> Option<String> {
+    fs::read_to_string(format!("/proc/{}/cgroup", pid))
+        .ok()?
+        .lines()
+        .find_map(|line| line.strip_prefix("0::").map(|path| path.to_string()))
+}
+
+pub fn read_cgroup_paths(pid: u32) -> Vec<String> {
+    fs::read_to_string(format!("/proc/{}/cgroup", pid))
+        .map(|content| {
+            content
+                .lines()
+                .filter_map(|line| line.splitn(3, ':').nth(2).map(|path| path.to_string()))
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+pub fn is_in_cgroup(process_cgroup: &str, cgroup: &str) -> bool {
+    cgroup == "/"
+        || process_cgroup == cgroup
+        || process_cgroup
+            .strip_prefix(cgroup)
+            .map(|rest| rest.starts_with('/'))
+            .unwrap_or(false)
+}
+
+#[derive(Debug, Clone)]
+pub struct CgroupNode {
+    pub name: String,
+    pub path: String,
+    pub stats: CgroupStats,
+    pub cpu_percent: f64,
+    pub io_read_rate: f64,
+    pub io_write_rate: f64,
+    pub procs: Vec<u32>,
+    pub children: Vec<CgroupNode>,
+}
+
+impl CgroupNode {
+    pub fn get_memory_percent(&self) -> Option<f64> {
+        match (self.stats.memory_current, self.stats.memory_max) {
+            (Some(current), Some(max)) if max > 0 => Some(current as f64 / max as f64 * 100.0),
+            _ => None,
+        }
+    }
+
+    pub fn process_count(&self) -> usize {
+        self.procs.len()
+            + self
+                .children
+                .iter()
+                .map(CgroupNode::process_count)
+                .sum::<usize>()
+    }
+
+    pub fn find(&self, path: &str) -> Option<&CgroupNode> {
+        if self.path == path {
+            return Some(self);
+        }
+        self.children.iter().find_map(|child| child.find(path))
+    }
+}
+
+#[derive(Clone, Copy)]
+struct Sample {
+    at: Instant,
+    cpu_usage_usec: u64,
+    io_read_bytes: u64,
+    io_write_bytes: u64,
+}
+
+pub struct CgroupState {
+    root_path: Option<PathBuf>,
+    root: Option<CgroupNode>,
+    samples: HashMap<String, Sample>,
+    enabled: bool,
+}
+
+impl CgroupState {
+    pub fn new() -> Self {
+        let root_path = [CGROUP_ROOT, CGROUP_UNIFIED]
+            .iter()
+            .map(PathBuf::from)
+            .find(|path| path.join("cgroup.controllers").exists());
+
+        let mut state = Self {
+            root_path,
+            root: None,
+            samples: HashMap::new(),
+            enabled: true,
+        };
+        state.update();
+        state
+    }
+
+    pub fn update(&mut self) {
+        let root_path = match &self.root_path {
+            Some(path) if self.enabled => path.clone(),
+            _ => return,
+        };
+
+        let now = Instant::now();
+        let mut samples = HashMap::new();
+        let root = self.build_node(&root_path, "/", now, &mut samples, 0);
+
+        self.root = Some(root);
+        self.samples = samples;
+    }
+
+    pub fn set_enabled(&mut self, enabled: bool) {
+        if !enabled {
+            self.samples.clear();
+        }
+        self.enabled = enabled;
+    }
+
+    fn build_node(
+        &self,
+        path: &Path,
+        cgroup_path: &str,
+        now: Instant,
+        samples: &mut HashMap<String, Sample>,
+        depth: usize,
+    ) -> CgroupNode {
+        let mut children = Vec::new();
+        if depth < MAX_DEPTH {
+            let mut entries: Vec<PathBuf> = fs::read_dir(path)
+                .map(|entries| {
+                    entries
+                        .filter_map(|entry| entry.ok())
+                        .filter(|entry| entry.file_type().map(|t| t.is_dir()).unwrap_or(false))
+                        .map(|entry| entry.path())
+                        .collect()
+                })
+                .unwrap_or_default();
+            entries.sort();
+
+            for entry in entries {
+                let name = entry
+                    .file_name()
+                    .map(|name| name.to_string_lossy().to_string())
+                    .unwrap_or_default();
+                let child_path = if cgroup_path == "/" {
+                    format!("/{}", name)
+                } else {
+                    format!("{}/{}", cgroup_path, name)
+                };
+                children.push(self.build_node(&entry, &child_path, now, samples, depth + 1));
+            }
+        }
+
+        let mut stats = read_stats(path);
+        let procs = read_procs(path);
+        let mut aggregated = CgroupStats::default();
+        for child in &children {
+            aggregated.aggregate(&child.stats);
+        }
+        stats.fill_missing(&aggregated);
+
+        let sample = Sample {
+            at: now,
+            cpu_usage_usec: stats.cpu_usage_usec.unwrap_or(0),
+            io_read_bytes: stats.io_read_bytes.unwrap_or(0),
+            io_write_bytes: stats.io_write_bytes.unwrap_or(0),
+        };
+
+        let (mut cpu_percent, mut io_read_rate, mut io_write_rate) = (0.0, 0.0, 0.0);
+        if let Some(previous) = self.samples.get(cgroup_path) {
+            let time_delta = now.duration_since(previous.at).as_secs_f64();
+            if time_delta > 0.001 {
+                cpu_percent = sample
+                    .cpu_usage_usec
+                    .saturating_sub(previous.cpu_usage_usec) as f64
+                    / (time_delta * 1_000_000.0)
+                    * 100.0;
+                io_read_rate =
+                    sample.io_read_bytes.saturating_sub(previous.io_read_bytes) as f64 / time_delta;
+                io_write_rate = sample
+                    .io_write_bytes
+                    .saturating_sub(previous.io_write_bytes) as f64
+                    / time_delta;
+            }
+        }
+        samples.insert(cgroup_path.to_string(), sample);
+
+        CgroupNode {
+            name: if cgroup_path == "/" {
+                "/".to_string()
+            } else {
+                cgroup_path.rsplit('/').next().unwrap_or("").to_string()
+            },
+            path: cgroup_path.to_string(),
+            stats,
+            cpu_percent,
+            io_read_rate,
+            io_write_rate,
+            procs,
+            children,
+        }
+    }
+
+    pub fn is_available(&self) -> bool {
+        self.root.is_some()
+    }
+
+    pub fn get_root(&self) -> Option<&CgroupNode> {
+        self.root.as_ref()
+    }
+
+    pub fn get_roots(&self) -> &[CgroupNode] {
+        self.root.as_slice()
+    }
+
+    pub fn find(&self, path: &str) -> Option<&CgroupNode> {
+        self.root.as_ref().and_then(|root| root.find(path))
+    }
+}
@@ -0,0 +1,439 @@
+use std::collections::HashMap;
+use std::fs;
+use std::net::{Ipv4Addr, Ipv6Addr};
+use std::time::{Duration, Instant};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Protocol {
+    Tcp,
+    Tcp6,
+    Udp,
+    Udp6,
+    Unix,
+}
+
+impl Protocol {
+    pub fn label(&self) -> &'static str {
+        match self {
+            Protocol::Tcp => "tcp",
+            Protocol::Tcp6 => "tcp6",
+            Protocol::Udp => "udp",
+            Protocol::Udp6 => "udp6",
+            Protocol::Unix => "unix",
+        }
+    }
+
+    fn path(&self) -> &'static str {
+        match self {
+            Protocol::Tcp => "/proc/net/tcp",
+            Protocol::Tcp6 => "/proc/net/tcp6",
+            Protocol::Udp => "/proc/net/udp",
+            Protocol::Udp6 => "/proc/net/udp6",
+            Protocol::Unix => "/proc/net/unix",
+        }
+    }
+
+    pub fn is_tcp(&self) -> bool {
+        matches!(self, Protocol::Tcp | Protocol::Tcp6)
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Connection {
+    pub protocol: Protocol,
+    pub local_address: String,
+    pub local_port: Option<u16>,
+    pub remote_address: String,
+    pub remote_port: Option<u16>,
+    pub state: String,
+    pub inode: u64,
+    pub uid: Option<u32>,
+    pub pid: Option<u32>,
+    pub process: Option<String>,
+}
+
+impl Connection {
+    pub fn local(&self) -> String {
+        format_endpoint(&self.local_address, self.local_port)
+    }
+
+    pub fn remote(&self) -> String {
+        format_endpoint(&self.remote_address, self.remote_port)
+    }
+}
+
+fn format_endpoint(address: &str, port: Option<u16>) -> String {
+    match port {
+        Some(port) if address.contains(':') => format!("[{}]:{}", address, port),
+        Some(port) => format!("{}:{}", address, port),
+        None => address.to_string(),
+    }
+}
+
+fn tcp_state(code: &str) -> &'static str {
+    match code {
+        "01" => "ESTAB",
+        "02" => "SYN-SENT",
+        "03" => "SYN-RECV",
+        "04" => "FIN-WAIT-1",
+        "05" => "FIN-WAIT-2",
+        "06" => "TIME-WAIT",
+        "07" => "CLOSE",
+        "08" => "CLOSE-WAIT",
+        "09" => "LAST-ACK",
+        "0A" => "LISTEN",
+        "0B" => "CLOSING",
+        _ => "UNKNOWN",
+    }
+}
+
+fn udp_state(code: &str) -> &'static str {
+    match code {
+        "01" => "ESTAB",
+        _ => "UNCONN",
+    }
+}
+
+fn parse_hex_address(hex: &str) -> Option<(String, u16)> {
+    let (address, port) = hex.split_once(':')?;
+    let port = u16::from_str_radix(port, 16).ok()?;
+
+    let address = match address.len() {
+        8 => {
+            let raw = u32::from_str_radix(address, 16).ok()?;
+            Ipv4Addr::from(u32::from_be(raw)).to_string()
+        }
+        32 => {
+            let mut octets = [0u8; 16];
+            for word in 0..4 {
+                let raw = u32::from_str_radix(&address[word * 8..word * 8 + 8], 16).ok()?;
+                octets[word * 4..word * 4 + 4].copy_from_slice(&raw.to_ne_bytes());
+            }
+            let ip = Ipv6Addr::from(octets);
+            match ip.to_ipv4_mapped() {
+                Some(v4) => v4.to_string(),
+                None => ip.to_string(),
+            }
+        }
+        _ => return None,
+    };
+
+    Some((address, port))
+}
+
+pub fn parse_inet(protocol: Protocol, content: &str) -> Vec<Connection> {
+    content
+        .lines()
+        .skip(1)
+        .filter_map(|line| {
+            let fields: Vec<&str> = line.split_whitespace().collect();
+            if fields.len() < 10 {
+                return None;
+            }
+
+            let (local_address, local_port) = parse_hex_address(fields[1])?;
+            let (remote_address, remote_port) = parse_hex_address(fields[2])?;
+            let state = if protocol.is_tcp() {
+                tcp_state(fields[3])
+            } else {
+                udp_state(fields[3])
+            };
+
+            Some(Connection {
+                protocol,
+                local_address,
+                local_port: Some(local_port),
+                remote_address,
+                remote_port: Some(remote_port),
+                state: state.to_string(),
+                inode: fields[9].parse().ok()?,
+                uid: fields[7].parse().ok(),
+                pid: None,
+                process: None,
+            })
+        })
+        .collect()
+}
+
+pub fn parse_unix(content: &str) -> Vec<Connection> {
+    content
+        .lines()
+        .skip(1)
+        .filter_map(|line| {
+            let fields: Vec<&str> = line.split_whitespace().collect();
+            if fields.len() < 7 {
+                return None;
+            }
+            let mut path = line;
+            for _ in 0..7 {
+                path = path
+                    .trim_start()
+                    .split_once(char::is_whitespace)
+                    .map(|(_, rest)| rest)
+                    .unwrap_or("");
+            }
+            let path = path.trim_start();
+
+            let flags = u32::from_str_radix(fields[3], 16).unwrap_or(0);
+            let state = if flags & 0x10000 != 0 {
+                "LISTEN"
+            } else {
+                match fields[5] {
+                    "01" => "UNCONN",
+                    "02" => "CONNECTING",
+                    "03" => "ESTAB",
+                    "04" => "DISCONNECTING",
+                    _ => "UNKNOWN",
+                }
+            };
+            let kind = match fields[4] {
+                "0001" => "stream",
+                "0002" => "dgram",
+                "0005" => "seqpacket",
+                _ => "unknown",
+            };
+
+            Some(Connection {
+                protocol: Protocol::Unix,
+                local_address: if path.is_empty() { "*" } else { path }.to_string(),
+                local_port: None,
+                remote_address: kind.to_string(),
+                remote_port: None,
+                state: state.to_string(),
+                inode: fields[6].parse().ok()?,
+                uid: None,
+                pid: None,
+                process: None,
+            })
+        })
+        .collect()
+}
+
+pub fn socket_owners() -> HashMap<u64, (u32, String)> {
+    let mut owners = HashMap::new();
+
+    let entries = match fs::read_dir("/proc") {
+        Ok(entries) => entries,
+        Err(_) => return owners,
+    };
+
+    for entry in entries.filter_map(|e| e.ok()) {
+        let pid: u32 = match entry.file_name().to_string_lossy().parse() {
+            Ok(pid) => pid,
+            Err(_) => continue,
+        };
+
+        let fds = match fs::read_dir(entry.path().join("fd")) {
+            Ok(fds) => fds,
+            Err(_) => continue,
+        };
+
+        let mut name: Option<String> = None;
+        for fd in fds.filter_map(|e| e.ok()) {
+            let target = match fs::read_link(fd.path()) {
+                Ok(target) => target,
+                Err(_) => continue,
+            };
+            let target = target.to_string_lossy();
+            let inode = match target
+                .strip_prefix("socket:[")
+                .and_then(|rest| rest.strip_suffix(']'))
+                .and_then(|inode| inode.parse::<u64>().ok())
+            {
+                Some(inode) => inode,
+                None => continue,
+            };
+
+            let name = name
+                .get_or_insert_with(|| {
+                    fs::read_to_string(entry.path().join("comm"))
+                        .map(|comm| comm.trim().to_string())
+                        .unwrap_or_default()
+                })
+                .clone();
+            owners.entry(inode).or_insert((pid, name));
+        }
+    }
+
+    owners
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct ConnectionFilter {
+    states: Vec<String>,
+    ports: Vec<u16>,
+    processes: Vec<String>,
+    protocols: Vec<String>,
+    terms: Vec<String>,
+}
+
+impl ConnectionFilter {
+    pub fn parse(spec: &str) -> Self {
+        let mut filter = Self::default();
+
+        for token in spec.split_whitespace() {
+            let lower = token.to_lowercase();
+            match lower.split_once(':') {
+                Some(("state", value)) => filter.states.push(value.to_string()),
+                Some(("port", value)) => {
+                    if let Ok(port) = value.parse() {
+                        filter.ports.push(port);
+                    }
+                }
+                Some(("proc", value)) | Some(("process", value)) => {
+                    filter.processes.push(value.to_string())
+                }
+                Some(("proto", value)) => filter.protocols.push(value.to_string()),
+                _ => filter.terms.push(lower),
+            }
+        }
+
+        filter
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.states.is_empty()
+            && self.ports.is_empty()
+            && self.processes.is_empty()
+            && self.protocols.is_empty()
+            && self.terms.is_empty()
+    }
+
+    pub fn matches(&self, connection: &Connection) -> bool {
+        let state = connection.state.to_lowercase();
+        let process = connection.process.as_deref().unwrap_or("").to_lowercase();
+        let pid = connection.pid.map(|p| p.to_string()).unwrap_or_default();
+
+        (self.states.is_empty() || self.states.iter().any(|s| state.starts_with(s.as_str())))
+            && (self.ports.is_empty()
+                || self.ports.iter().any(|p| {
+                    connection.local_port == Some(*p) || connection.remote_port == Some(*p)
+                }))
+            && (self.processes.is_empty()
+                || self
+                    .processes
+                    .iter()
+                    .any(|p| process.contains(p.as_str()) || pid == *p))
+            && (self.protocols.is_empty()
+                || self
+                    .protocols
+                    .iter()
+                    .any(|p| connection.protocol.label().starts_with(p.as_str())))
+            && self.terms.iter().all(|term| {
+                process.contains(term.as_str())
+                    || connection.local().to_lowercase().contains(term.as_str())
+                    || connection.remote().to_lowercase().contains(term.as_str())
+            })
+    }
+}
+
+pub struct ConnectionState {
+    connections: Vec<Connection>,
+    owners: HashMap<u64, (u32, String)>,
+    include_unix: bool,
+    owner_interval: Option<Duration>,
+    last_owner_scan: Option<Instant>,
+    owners_scanned: bool,
+}
+
+impl ConnectionState {
+    pub fn new(include_unix: bool) -> Self {
+        let mut state = Self {
+            connections: Vec::new(),
+            owners: HashMap::new(),
+            include_unix,
+            owner_interval: Some(Duration::ZERO),
+            last_owner_scan: None,
+            owners_scanned: false,
+        };
+        state.update();
+        state
+    }
+
+    pub fn update(&mut self) {
+        let mut protocols = vec![Protocol::Tcp, Protocol::Tcp6, Protocol::Udp, Protocol::Udp6];
+        if self.include_unix {
+            protocols.push(Protocol::Unix);
+        }
+
+        let mut connections: Vec<Connection> = protocols
+            .into_iter()
+            .flat_map(|protocol| {
+                let content = fs::read_to_string(protocol.path()).unwrap_or_default();
+                match protocol {
+                    Protocol::Unix => parse_unix(&content),
+                    _ => parse_inet(protocol, &content),
+                }
+            })
+            .collect();
+
+        let due = self
+            .last_owner_scan
+            .zip(self.owner_interval)
+            .map(|(at, interval)| at.elapsed() >= interval)
+            .unwrap_or(true);
+        self.owners_scanned = self.owner_interval.is_some() && due;
+        let owners = if self.owners_scanned {
+            self.last_owner_scan = Some(Instant::now());
+            socket_owners()
+        } else if self.owner_interval.is_some() {
+            std::mem::take(&mut self.owners)
+        } else {
+            HashMap::new()
+        };
+        for connection in &mut connections {
+            if let Some((pid, name)) = owners.get(&connection.inode) {
+                connection.pid = Some(*pid);
+                connection.process = Some(name.clone());
+            }
+        }
+
+        connections.sort_by(|a, b| {
+            (a.protocol.label(), a.local_port, &a.local_address).cmp(&(
+                b.protocol.label(),
+                b.local_port,
+                &b.local_address,
+            ))
+        });
+
+        self.connections = connections;
+        self.owners = owners;
+    }
+
+    pub fn set_owner_interval(&mut self, interval: Option<Duration>) {
+        self.owner_interval = interval;
+    }
+
+    pub fn is_resolving_owners(&self) -> bool {
+        self.owner_interval.is_some()
+    }
+
+    pub fn has_scanned_owners(&self) -> bool {
+        self.owners_scanned
+    }
+
+    pub fn get_connections(&self) -> &[Connection] {
+        &self.connections
+    }
+
+    pub fn get_owners(&self) -> &HashMap<u64, (u32, String)> {
+        &self.owners
+    }
+
+    pub fn get_filtered(&self, filter: &ConnectionFilter) -> Vec<&Connection> {
+        self.connections
+            .iter()
+            .filter(|connection| filter.matches(connection))
+            .collect()
+    }
+
+    pub fn count_by_pid(&self) -> HashMap<u32, usize> {
+        let mut counts = HashMap::new();
+        for connection in &self.connections {
+            if let (Some(pid), false) = (connection.pid, connection.protocol == Protocol::Unix) {
+                *counts.entry(pid).or_insert(0) += 1;
+            }
+        }
+        counts
+    }
+}
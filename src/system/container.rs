@@ -0,0 +1,650 @@
+use std::collections::HashMap;
+use std::fs;
+use std::io::{self, Read, Write};
+use std::os::unix::net::UnixStream;
+use std::path::Path;
+use std::sync::mpsc::{self, Receiver, Sender};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use serde::Deserialize;
+use sysinfo::PidExt;
+
+use crate::config::ContainersConfig;
+use crate::system::cgroup::read_cgroup_paths;
+use crate::system::nettraffic::NetTrafficState;
+use crate::system::ProcessList;
+
+const ENGINE_TIMEOUT: Duration = Duration::from_millis(500);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ContainerRuntime {
+    Docker,
+    Podman,
+    Containerd,
+    CriO,
+    Lxc,
+    Unknown,
+}
+
+impl ContainerRuntime {
+    pub fn label(&self) -> &'static str {
+        match self {
+            ContainerRuntime::Docker => "docker",
+            ContainerRuntime::Podman => "podman",
+            ContainerRuntime::Containerd => "containerd",
+            ContainerRuntime::CriO => "cri-o",
+            ContainerRuntime::Lxc => "lxc",
+            ContainerRuntime::Unknown => "ns",
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct ContainerRef {
+    pub runtime: ContainerRuntime,
+    pub id: String,
+    pub pod: Option<String>,
+}
+
+impl ContainerRef {
+    pub fn short_id(&self) -> &str {
+        &self.id[..self.id.len().min(12)]
+    }
+}
+
+fn is_container_id(id: &str) -> bool {
+    id.len() >= 32 && id.chars().all(|c| c.is_ascii_hexdigit())
+}
+
+fn strip_scope<'a>(segment: &'a str, prefix: &str) -> Option<&'a str> {
+    segment
+        .strip_prefix(prefix)
+        .map(|rest| rest.strip_suffix(".scope").unwrap_or(rest))
+        .filter(|id| is_container_id(id))
+}
+
+fn parse_pod(segment: &str) -> Option<String> {
+    let segment = segment.strip_suffix(".slice").unwrap_or(segment);
+    let uid = segment.rsplit('-').next()?.strip_prefix("pod")?;
+    if uid.is_empty() {
+        return None;
+    }
+    Some(uid.replace('_', "-"))
+}
+
+pub fn parse_container_cgroup(path: &str) -> Option<ContainerRef> {
+    let segments: Vec<&str> = path.split('/').filter(|s| !s.is_empty()).collect();
+
+    let pod = if path.contains("kubepods") {
+        segments.iter().find_map(|segment| parse_pod(segment))
+    } else {
+        None
+    };
+
+    for (idx, segment) in segments.iter().enumerate().rev() {
+        let found = if let Some(id) = strip_scope(segment, "docker-") {
+            Some((ContainerRuntime::Docker, id))
+        } else if let Some(id) = strip_scope(segment, "libpod-") {
+            Some((ContainerRuntime::Podman, id))
+        } else if let Some(id) = strip_scope(segment, "cri-containerd-") {
+            Some((ContainerRuntime::Containerd, id))
+        } else if let Some(id) = strip_scope(segment, "crio-") {
+            Some((ContainerRuntime::CriO, id))
+        } else if is_container_id(segment) && idx > 0 {
+            let runtime = match segments[idx - 1] {
+                "docker" => ContainerRuntime::Docker,
+                "libpod_parent" => ContainerRuntime::Podman,
+                _ if pod.is_some() => ContainerRuntime::Containerd,
+                _ => continue,
+            };
+            Some((runtime, *segment))
+        } else if let Some(name) = segment.strip_prefix("lxc.payload.") {
+            Some((ContainerRuntime::Lxc, name))
+        } else if idx > 0 && segments[idx - 1] == "lxc" {
+            Some((ContainerRuntime::Lxc, *segment))
+        } else {
+            None
+        };
+
+        if let Some((runtime, id)) = found {
+            return Some(ContainerRef {
+                runtime,
+                id: id.to_string(),
+                pod,
+            });
+        }
+    }
+
+    None
+}
+
+fn read_namespace(pid: &str, kind: &str) -> Option<u64> {
+    let target = fs::read_link(format!("/proc/{}/ns/{}", pid, kind)).ok()?;
+    let target = target.to_string_lossy();
+    target.split_once('[')?.1.strip_suffix(']')?.parse().ok()
+}
+
+fn read_net_dev(pid: u32) -> Option<(u64, u64)> {
+    let content = fs::read_to_string(format!("/proc/{}/net/dev", pid)).ok()?;
+    let mut rx = 0;
+    let mut tx = 0;
+    for line in content.lines().skip(2) {
+        let (name, counters) = match line.split_once(':') {
+            Some(parts) => parts,
+            None => continue,
+        };
+        if name.trim() == "lo" {
+            continue;
+        }
+        let fields: Vec<u64> = counters
+            .split_whitespace()
+            .filter_map(|value| value.parse().ok())
+            .collect();
+        if fields.len() >= 9 {
+            rx += fields[0];
+            tx += fields[8];
+        }
+    }
+    Some((rx, tx))
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+struct EngineContainer {
+    id: String,
+    #[serde(default)]
+    names: Vec<String>,
+    #[serde(default)]
+    image: String,
+    #[serde(default)]
+    labels: Option<HashMap<String, String>>,
+}
+
+#[derive(Debug, Clone)]
+pub struct ContainerDetails {
+    pub name: String,
+    pub image: String,
+    pub pod_name: Option<String>,
+}
+
+fn malformed(message: &str) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, message.to_string())
+}
+
+fn split_at_separator<'a>(data: &'a [u8], separator: &[u8]) -> Option<(&'a [u8], &'a [u8])> {
+    let position = data
+        .windows(separator.len())
+        .position(|window| window == separator)?;
+    Some((&data[..position], &data[position + separator.len()..]))
+}
+
+fn decode_chunked(mut body: &[u8]) -> io::Result<Vec<u8>> {
+    let mut decoded = Vec::new();
+    loop {
+        let (size, rest) =
+            split_at_separator(body, b"\r\n").ok_or_else(|| malformed("truncated chunk"))?;
+        let size = String::from_utf8_lossy(size);
+        let size = size.split(';').next().unwrap_or("").trim();
+        let size = usize::from_str_radix(size, 16).map_err(|_| malformed("invalid chunk size"))?;
+        if size == 0 {
+            return Ok(decoded);
+        }
+        let chunk = rest
+            .get(..size)
+            .ok_or_else(|| malformed("truncated chunk"))?;
+        decoded.extend_from_slice(chunk);
+        body = rest[size..]
+            .strip_prefix(b"\r\n")
+            .ok_or_else(|| malformed("truncated chunk"))?;
+    }
+}
+
+fn http_body(response: &[u8]) -> io::Result<String> {
+    let (head, body) = split_at_separator(response, b"\r\n\r\n")
+        .ok_or_else(|| malformed("malformed HTTP response"))?;
+    let head = String::from_utf8_lossy(head);
+    let mut lines = head.lines();
+    let status = lines.next().unwrap_or("");
+    if !status
+        .split_whitespace()
+        .nth(1)
+        .unwrap_or("")
+        .starts_with('2')
+    {
+        return Err(io::Error::other(format!("engine returned {}", status)));
+    }
+
+    let chunked = lines.any(|line| {
+        line.split_once(':').is_some_and(|(name, value)| {
+            name.trim().eq_ignore_ascii_case("transfer-encoding")
+                && value.trim().eq_ignore_ascii_case("chunked")
+        })
+    });
+    let body = if chunked {
+        decode_chunked(body)?
+    } else {
+        body.to_vec()
+    };
+    String::from_utf8(body).map_err(|_| malformed("response is not valid UTF-8"))
+}
+
+pub fn query_engine(socket: &Path) -> io::Result<HashMap<String, ContainerDetails>> {
+    let mut stream = UnixStream::connect(socket)?;
+    stream.set_read_timeout(Some(ENGINE_TIMEOUT))?;
+    stream.set_write_timeout(Some(ENGINE_TIMEOUT))?;
+    stream.write_all(b"GET /containers/json HTTP/1.0\r\nHost: localhost\r\n\r\n")?;
+
+    let mut response = Vec::new();
+    stream.read_to_end(&mut response)?;
+
+    let containers: Vec<EngineContainer> = serde_json::from_str(&http_body(&response)?)
+        .map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err))?;
+
+    Ok(containers
+        .into_iter()
+        .map(|container| {
+            let name = container
+                .names
+                .first()
+                .map(|name| name.trim_start_matches('/').to_string())
+                .unwrap_or_else(|| container.id[..container.id.len().min(12)].to_string());
+            let pod_name = container
+                .labels
+                .as_ref()
+                .and_then(|labels| labels.get("io.kubernetes.pod.name").cloned());
+            (
+                container.id,
+                ContainerDetails {
+                    name,
+                    image: container.image,
+                    pod_name,
+                },
+            )
+        })
+        .collect())
+}
+
+type EngineResult = (HashMap<String, ContainerDetails>, Option<String>);
+
+fn query_engines(sockets: &[String]) -> EngineResult {
+    let mut details = HashMap::new();
+    let mut errors = Vec::new();
+    for socket in sockets {
+        let path = Path::new(socket);
+        if !path.exists() {
+            continue;
+        }
+        match query_engine(path) {
+            Ok(containers) => details.extend(containers),
+            Err(err) => errors.push(format!("{}: {}", socket, err)),
+        }
+    }
+
+    let error = if errors.is_empty() {
+        None
+    } else {
+        Some(errors.join("; "))
+    };
+    (details, error)
+}
+
+#[derive(Debug, Clone)]
+pub struct ContainerSummary {
+    pub container: ContainerRef,
+    pub name: String,
+    pub image: Option<String>,
+    pub pod: Option<String>,
+    pub processes: usize,
+    pub cpu_usage: f32,
+    pub memory: u64,
+    pub rx_rate: f64,
+    pub tx_rate: f64,
+}
+
+pub struct ContainerState {
+    resolve_names: bool,
+    sockets: Vec<String>,
+    refresh_interval: Duration,
+    last_query: Option<Instant>,
+    engine_error: Option<String>,
+    details: HashMap<String, ContainerDetails>,
+    querying: bool,
+    sender: Sender<EngineResult>,
+    receiver: Receiver<EngineResult>,
+    host_pid_ns: Option<u64>,
+    host_net_ns: Option<u64>,
+    processes: HashMap<u32, ContainerRef>,
+    known: HashMap<u32, Option<ContainerRef>>,
+    summaries: Vec<ContainerSummary>,
+    net_counters: HashMap<ContainerRef, (Instant, u64, u64)>,
+}
+
+impl ContainerState {
+    pub fn new(config: &ContainersConfig) -> Self {
+        let (sender, receiver) = mpsc::channel();
+        Self {
+            resolve_names: config.resolve_names,
+            sockets: config.sockets.clone(),
+            refresh_interval: Duration::from_millis(config.refresh_interval),
+            last_query: None,
+            engine_error: None,
+            details: HashMap::new(),
+            querying: false,
+            sender,
+            receiver,
+            host_pid_ns: read_namespace("self", "pid"),
+            host_net_ns: read_namespace("self", "net"),
+            processes: HashMap::new(),
+            known: HashMap::new(),
+            summaries: Vec::new(),
+            net_counters: HashMap::new(),
+        }
+    }
+
+    pub fn update(&mut self, processes: &ProcessList, traffic: &NetTrafficState) {
+        let mut known = HashMap::new();
+        for process in processes.get_processes() {
+            let pid = process.get_pid().as_u32();
+            let container = match self.known.remove(&pid) {
+                Some(container) => container,
+                None => self.detect(pid, process.get_cgroup()),
+            };
+            known.insert(pid, container);
+        }
+        self.known = known;
+        self.processes = self
+            .known
+            .iter()
+            .filter_map(|(pid, container)| container.clone().map(|container| (*pid, container)))
+            .collect();
+
+        self.collect_details();
+        if self.resolve_names && !self.processes.is_empty() && !self.querying {
+            self.refresh_details();
+        }
+
+        self.update_summaries(processes, traffic);
+    }
+
+    fn detect(&self, pid: u32, cgroup: Option<&str>) -> Option<ContainerRef> {
+        if let Some(container) = cgroup.and_then(parse_container_cgroup) {
+            return Some(container);
+        }
+        if let Some(container) = read_cgroup_paths(pid)
+            .iter()
+            .find_map(|path| parse_container_cgroup(path))
+        {
+            return Some(container);
+        }
+
+        let pid_ns = read_namespace(&pid.to_string(), "pid")?;
+        if Some(pid_ns) != self.host_pid_ns && self.host_pid_ns.is_some() {
+            Some(ContainerRef {
+                runtime: ContainerRuntime::Unknown,
+                id: pid_ns.to_string(),
+                pod: None,
+            })
+        } else {
+            None
+        }
+    }
+
+    fn refresh_details(&mut self) {
+        let due = self
+            .last_query
+            .map(|at| at.elapsed() >= self.refresh_interval)
+            .unwrap_or(true);
+        if !due {
+            return;
+        }
+        self.last_query = Some(Instant::now());
+        self.querying = true;
+
+        let sockets = self.sockets.clone();
+        let sender = self.sender.clone();
+        thread::spawn(move || {
+            let _ = sender.send(query_engines(&sockets));
+        });
+    }
+
+    fn collect_details(&mut self) {
+        while let Ok((details, error)) = self.receiver.try_recv() {
+            self.querying = false;
+            self.details = details;
+            self.engine_error = error;
+        }
+    }
+
+    fn update_summaries(&mut self, processes: &ProcessList, traffic: &NetTrafficState) {
+        let now = Instant::now();
+        let mut summaries: HashMap<ContainerRef, ContainerSummary> = HashMap::new();
+        let mut net_pids: HashMap<ContainerRef, u32> = HashMap::new();
+
+        for process in processes.get_processes() {
+            let pid = process.get_pid().as_u32();
+            let container = match self.processes.get(&pid) {
+                Some(container) => container,
+                None => continue,
+            };
+
+            let summary = summaries.entry(container.clone()).or_insert_with(|| {
+                let details = self.details.get(&container.id);
+                ContainerSummary {
+                    container: container.clone(),
+                    name: self.get_name(container),
+                    image: details.map(|d| d.image.clone()),
+                    pod: details
+                        .and_then(|d| d.pod_name.clone())
+                        .or_else(|| container.pod.clone()),
+                    processes: 0,
+                    cpu_usage: 0.0,
+                    memory: 0,
+                    rx_rate: 0.0,
+                    tx_rate: 0.0,
+                }
+            });
+            summary.processes += 1;
+            summary.cpu_usage += process.get_cpu_usage();
+            summary.memory += process.get_memory_usage();
+
+            let own_net_ns = read_namespace(&pid.to_string(), "net")
+                .map(|ns| Some(ns) != self.host_net_ns)
+                .unwrap_or(false);
+            if own_net_ns {
+                net_pids.entry(container.clone()).or_insert(pid);
+            } else if let Some(talker) = traffic.get_process(pid) {
+                summary.rx_rate += talker.rx_rate;
+                summary.tx_rate += talker.tx_rate;
+            }
+        }
+
+        let mut net_counters = HashMap::new();
+        for (container, pid) in net_pids {
+            let (rx, tx) = match read_net_dev(pid) {
+                Some(counters) => counters,
+                None => continue,
+            };
+            if let (Some((at, prev_rx, prev_tx)), Some(summary)) = (
+                self.net_counters.get(&container),
+                summaries.get_mut(&container),
+            ) {
+                let time_delta = now.duration_since(*at).as_secs_f64();
+                if time_delta > 0.001 {
+                    summary.rx_rate = rx.saturating_sub(*prev_rx) as f64 / time_delta;
+                    summary.tx_rate = tx.saturating_sub(*prev_tx) as f64 / time_delta;
+                }
+            }
+            net_counters.insert(container, (now, rx, tx));
+        }
+
+        let mut summaries: Vec<ContainerSummary> = summaries.into_values().collect();
+        summaries.sort_by(|a, b| a.name.cmp(&b.name));
+
+        self.summaries = summaries;
+        self.net_counters = net_counters;
+    }
+
+    pub fn get_name(&self, container: &ContainerRef) -> String {
+        match self.details.get(&container.id) {
+            Some(details) => details.name.clone(),
+            None => match container.runtime {
+                ContainerRuntime::Unknown => format!("pidns:{}", container.id),
+                _ => container.short_id().to_string(),
+            },
+        }
+    }
+
+    pub fn get_process_container(&self, pid: u32) -> Option<&ContainerRef> {
+        self.processes.get(&pid)
+    }
+
+    pub fn get_process_label(&self, pid: u32) -> Option<String> {
+        self.processes
+            .get(&pid)
+            .map(|container| self.get_name(container))
+    }
+
+    pub fn has_containers(&self) -> bool {
+        !self.processes.is_empty()
+    }
+
+    pub fn get_summaries(&self) -> &[ContainerSummary] {
+        &self.summaries
+    }
+
+    pub fn get_engine_error(&self) -> Option<&str> {
+        self.engine_error.as_deref()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::BufRead;
+    use std::os::unix::net::UnixListener;
+    use std::path::PathBuf;
+    use std::thread;
+
+    fn socket_path(name: &str) -> PathBuf {
+        let path = std::env::temp_dir().join(format!("rtop-{}-{}.sock", std::process::id(), name));
+        let _ = fs::remove_file(&path);
+        path
+    }
+
+    fn serve(name: &str, response: &'static [u8]) -> (PathBuf, thread::JoinHandle<String>) {
+        let path = socket_path(name);
+        let listener = UnixListener::bind(&path).unwrap();
+        let handle = thread::spawn(move || {
+            let (stream, _) = listener.accept().unwrap();
+            let mut reader = io::BufReader::new(stream);
+            let mut request = String::new();
+            loop {
+                let mut line = String::new();
+                if reader.read_line(&mut line).unwrap() == 0 || line == "\r\n" {
+                    break;
+                }
+                request.push_str(&line);
+            }
+            reader.get_mut().write_all(response).unwrap();
+            request
+        });
+        (path, handle)
+    }
+
+    #[test]
+    fn query_engine_reads_container_list() {
+        let (path, server) = serve(
+            "list",
+            b"HTTP/1.0 200 OK\r\nContent-Type: application/json\r\n\r\n\
+             [{\"Id\":\"0123456789abcdef\",\"Names\":[\"/web\"],\"Image\":\"nginx:latest\",\
+             \"Labels\":{\"io.kubernetes.pod.name\":\"web-0\"}},\
+             {\"Id\":\"fedcba9876543210ffff\",\"Labels\":null}]",
+        );
+
+        let details = query_engine(&path).unwrap();
+        let request = server.join().unwrap();
+        fs::remove_file(&path).unwrap();
+
+        assert!(request.starts_with("GET /containers/json HTTP/1.0\r\n"));
+        assert_eq!(details.len(), 2);
+        let web = &details["0123456789abcdef"];
+        assert_eq!(web.name, "web");
+        assert_eq!(web.image, "nginx:latest");
+        assert_eq!(web.pod_name.as_deref(), Some("web-0"));
+        assert_eq!(details["fedcba9876543210ffff"].name, "fedcba987654");
+    }
+
+    #[test]
+    fn query_engine_reports_error_status() {
+        let (path, server) = serve(
+            "status",
+            b"HTTP/1.0 500 Internal Server Error\r\n\r\n{\"message\":\"boom\"}",
+        );
+
+        let err = query_engine(&path).unwrap_err();
+        server.join().unwrap();
+        fs::remove_file(&path).unwrap();
+
+        assert!(err.to_string().contains("500 Internal Server Error"));
+    }
+
+    #[test]
+    fn query_engine_decodes_chunked_body() {
+        let (path, server) = serve(
+            "chunked",
+            b"HTTP/1.1 200 OK\r\nTransfer-Encoding: chunked\r\n\r\n\
+             9\r\n[{\"Id\":\"a\r\n15;ext=1\r\nbc\",\"Names\":[\"/db\"]}]\r\n0\r\n\r\n",
+        );
+
+        let details = query_engine(&path).unwrap();
+        server.join().unwrap();
+        fs::remove_file(&path).unwrap();
+
+        assert_eq!(details["abc"].name, "db");
+    }
+
+    #[test]
+    fn query_engine_joins_chunks_split_inside_a_character() {
+        let (path, server) = serve(
+            "multibyte",
+            b"HTTP/1.1 200 OK\r\nTransfer-Encoding: chunked\r\n\r\n\
+              1c\r\n[{\"Id\":\"abc\",\"Names\":[\"/caf\xc3\r\n5\r\n\xa9\"]}]\r\n0\r\n\r\n",
+        );
+
+        let details = query_engine(&path).unwrap();
+        server.join().unwrap();
+        fs::remove_file(&path).unwrap();
+
+        assert_eq!(details["abc"].name, "caf\u{e9}");
+    }
+
+    #[test]
+    fn query_engine_rejects_malformed_body() {
+        let (path, server) = serve("malformed", b"HTTP/1.0 200 OK\r\n\r\nnot json");
+        let err = query_engine(&path).unwrap_err();
+        server.join().unwrap();
+        fs::remove_file(&path).unwrap();
+        assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+
+        let (path, server) = serve(
+            "truncated",
+            b"HTTP/1.1 200 OK\r\nTransfer-Encoding: chunked\r\n\r\n20\r\n[]",
+        );
+        let err = query_engine(&path).unwrap_err();
+        server.join().unwrap();
+        fs::remove_file(&path).unwrap();
+        assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+
+        assert_eq!(
+            http_body(b"HTTP/1.0 200 OK\r\n").unwrap_err().kind(),
+            io::ErrorKind::InvalidData
+        );
+    }
+
+    #[test]
+    fn query_engine_fails_without_socket() {
+        let err = query_engine(&socket_path("missing")).unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::NotFound);
+    }
+}
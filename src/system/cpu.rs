@@ -2,18 +2,20 @@ use std::collections::VecDeque;
 use std::time::Duration;
 use sysinfo::{CpuExt, System, SystemExt};
 
-const HISTORY_SIZE: usize = 100;
-
 pub struct CpuState {
     system: System,
     usage_per_core: Vec<f32>,
     average_usage: f32,
     core_count: usize,
     history: VecDeque<f32>,
+    history_size: usize,
+    frequencies: Vec<u64>,
+    brand: String,
+    load_average: (f64, f64, f64),
 }
 
 impl CpuState {
-    pub fn new() -> Self {
+    pub fn new(history_size: usize) -> Self {
         let mut system = System::new_all();
         system.refresh_cpu();
 
@@ -22,7 +24,11 @@ impl CpuState {
             usage_per_core: Vec::new(),
             average_usage: 0.0,
             core_count: 0,
-            history: VecDeque::with_capacity(HISTORY_SIZE),
+            history: VecDeque::with_capacity(history_size),
+            history_size,
+            frequencies: Vec::new(),
+            brand: String::new(),
+            load_average: (0.0, 0.0, 0.0),
         }
     }
 
@@ -39,6 +45,20 @@ impl CpuState {
             .map(|cpu| cpu.cpu_usage().min(100.0))
             .collect();
         self.core_count = self.usage_per_core.len();
+        self.frequencies = self
+            .system
+            .cpus()
+            .iter()
+            .map(|cpu| cpu.frequency())
+            .collect();
+        self.brand = self
+            .system
+            .cpus()
+            .first()
+            .map(|cpu| cpu.brand().trim().to_string())
+            .unwrap_or_default();
+        let load = self.system.load_average();
+        self.load_average = (load.one, load.five, load.fifteen);
 
         if self.core_count > 0 {
             self.average_usage =
@@ -48,7 +68,7 @@ impl CpuState {
         }
 
         self.history.push_back(self.average_usage);
-        if self.history.len() > HISTORY_SIZE {
+        if self.history.len() > self.history_size {
             self.history.pop_front();
         }
     }
@@ -68,4 +88,16 @@ impl CpuState {
     pub fn get_history(&self) -> &VecDeque<f32> {
         &self.history
     }
+
+    pub fn get_core_frequency(&self, core_idx: usize) -> Option<u64> {
+        self.frequencies.get(core_idx).copied()
+    }
+
+    pub fn get_brand(&self) -> &str {
+        &self.brand
+    }
+
+    pub fn get_load_average(&self) -> (f64, f64, f64) {
+        self.load_average
+    }
 }
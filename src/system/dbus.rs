@@ -0,0 +1,739 @@
+use std::env;
+use std::io::{self, BufRead, BufReader, Read, Write};
+use std::os::linux::net::SocketAddrExt;
+use std::os::unix::net::{SocketAddr, UnixStream};
+use std::time::Duration;
+
+const SYSTEM_BUS: &str = "unix:path=/run/dbus/system_bus_socket";
+const BUS_TIMEOUT: Duration = Duration::from_secs(2);
+const MAX_MESSAGE_SIZE: usize = 128 * 1024 * 1024;
+
+const METHOD_CALL: u8 = 1;
+const METHOD_RETURN: u8 = 2;
+const ERROR: u8 = 3;
+
+const FIELD_PATH: u8 = 1;
+const FIELD_INTERFACE: u8 = 2;
+const FIELD_MEMBER: u8 = 3;
+const FIELD_ERROR_NAME: u8 = 4;
+const FIELD_REPLY_SERIAL: u8 = 5;
+const FIELD_DESTINATION: u8 = 6;
+const FIELD_SIGNATURE: u8 = 8;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Value {
+    Byte(u8),
+    Bool(bool),
+    Int(i64),
+    UInt(u64),
+    Double(f64),
+    Str(String),
+    Array(Vec<Value>),
+    Struct(Vec<Value>),
+    Variant(Box<Value>),
+}
+
+impl Value {
+    pub fn as_str(&self) -> Option<&str> {
+        match self {
+            Value::Str(value) => Some(value),
+            Value::Variant(inner) => inner.as_str(),
+            _ => None,
+        }
+    }
+
+    pub fn as_u64(&self) -> Option<u64> {
+        match self {
+            Value::Byte(value) => Some(*value as u64),
+            Value::UInt(value) => Some(*value),
+            Value::Int(value) => u64::try_from(*value).ok(),
+            Value::Variant(inner) => inner.as_u64(),
+            _ => None,
+        }
+    }
+
+    pub fn as_slice(&self) -> Option<&[Value]> {
+        match self {
+            Value::Array(items) | Value::Struct(items) => Some(items),
+            Value::Variant(inner) => inner.as_slice(),
+            _ => None,
+        }
+    }
+}
+
+fn alignment(code: u8) -> usize {
+    match code {
+        b'n' | b'q' => 2,
+        b'b' | b'i' | b'u' | b's' | b'o' | b'a' | b'h' => 4,
+        b'x' | b't' | b'd' | b'(' | b'{' => 8,
+        _ => 1,
+    }
+}
+
+fn invalid(message: &str) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, message.to_string())
+}
+
+fn single_type(signature: &[u8]) -> io::Result<usize> {
+    match signature.first() {
+        Some(b'a') => Ok(1 + single_type(&signature[1..])?),
+        Some(open @ (b'(' | b'{')) => {
+            let close = if *open == b'(' { b')' } else { b'}' };
+            if signature.get(1) == Some(&close) {
+                return Err(invalid("empty struct in signature"));
+            }
+            let mut len = 1;
+            while signature.get(len) != Some(&close) {
+                if len >= signature.len() {
+                    return Err(invalid("unterminated signature"));
+                }
+                len += single_type(&signature[len..])?;
+            }
+            Ok(len + 1)
+        }
+        Some(_) => Ok(1),
+        None => Err(invalid("empty signature")),
+    }
+}
+
+fn type_code(signature: &str) -> io::Result<u8> {
+    signature
+        .as_bytes()
+        .first()
+        .copied()
+        .ok_or_else(|| invalid("empty signature"))
+}
+
+fn split_signature(signature: &str) -> io::Result<Vec<&str>> {
+    let mut types = Vec::new();
+    let mut rest = signature;
+    while !rest.is_empty() {
+        let len = single_type(rest.as_bytes())?;
+        types.push(&rest[..len]);
+        rest = &rest[len..];
+    }
+    Ok(types)
+}
+
+struct Writer {
+    buf: Vec<u8>,
+}
+
+impl Writer {
+    fn align(&mut self, n: usize) {
+        while !self.buf.len().is_multiple_of(n) {
+            self.buf.push(0);
+        }
+    }
+
+    fn u8(&mut self, value: u8) {
+        self.buf.push(value);
+    }
+
+    fn u32(&mut self, value: u32) {
+        self.align(4);
+        self.buf.extend_from_slice(&value.to_le_bytes());
+    }
+
+    fn string(&mut self, value: &str) {
+        self.u32(value.len() as u32);
+        self.buf.extend_from_slice(value.as_bytes());
+        self.buf.push(0);
+    }
+
+    fn signature(&mut self, value: &str) {
+        self.u8(value.len() as u8);
+        self.buf.extend_from_slice(value.as_bytes());
+        self.buf.push(0);
+    }
+
+    fn header_field(&mut self, code: u8, signature: &str, value: &str) {
+        self.align(8);
+        self.u8(code);
+        self.signature(signature);
+        if signature == "g" {
+            self.signature(value);
+        } else {
+            self.string(value);
+        }
+    }
+
+    fn value(&mut self, signature: &str, value: &Value) -> io::Result<()> {
+        let code = type_code(signature)?;
+        self.align(alignment(code));
+        match (code, value) {
+            (b'y', Value::Byte(v)) => self.u8(*v),
+            (b'b', Value::Bool(v)) => self.u32(*v as u32),
+            (b'u', Value::UInt(v)) => self.u32(*v as u32),
+            (b'i', Value::Int(v)) => self.buf.extend_from_slice(&(*v as i32).to_le_bytes()),
+            (b't', Value::UInt(v)) => self.buf.extend_from_slice(&v.to_le_bytes()),
+            (b'x', Value::Int(v)) => self.buf.extend_from_slice(&v.to_le_bytes()),
+            (b's' | b'o', Value::Str(v)) => self.string(v),
+            (b'g', Value::Str(v)) => self.signature(v),
+            (b'a', Value::Array(items)) => {
+                let element = &signature[1..];
+                self.u32(0);
+                let len_at = self.buf.len() - 4;
+                self.align(alignment(type_code(element)?));
+                let start = self.buf.len();
+                for item in items {
+                    self.value(element, item)?;
+                }
+                let len = (self.buf.len() - start) as u32;
+                self.buf[len_at..len_at + 4].copy_from_slice(&len.to_le_bytes());
+            }
+            (b'(' | b'{', Value::Struct(items)) => {
+                let inner = &signature[1..signature.len() - 1];
+                for (item_signature, item) in split_signature(inner)?.into_iter().zip(items) {
+                    self.value(item_signature, item)?;
+                }
+            }
+            (b'v', Value::Variant(inner)) => {
+                let inner_signature = match inner.as_ref() {
+                    Value::Byte(_) => "y",
+                    Value::Bool(_) => "b",
+                    Value::UInt(_) => "u",
+                    Value::Int(_) => "i",
+                    Value::Str(_) => "s",
+                    _ => return Err(invalid("unsupported variant")),
+                };
+                self.signature(inner_signature);
+                self.value(inner_signature, inner)?;
+            }
+            _ => return Err(invalid("value does not match signature")),
+        }
+        Ok(())
+    }
+}
+
+struct Reader<'a> {
+    buf: &'a [u8],
+    pos: usize,
+}
+
+impl<'a> Reader<'a> {
+    fn align(&mut self, n: usize) {
+        self.pos = self.pos.div_ceil(n) * n;
+    }
+
+    fn take(&mut self, len: usize) -> io::Result<&'a [u8]> {
+        let bytes = self
+            .buf
+            .get(self.pos..self.pos + len)
+            .ok_or_else(|| invalid("truncated message"))?;
+        self.pos += len;
+        Ok(bytes)
+    }
+
+    fn u32(&mut self) -> io::Result<u32> {
+        self.align(4);
+        Ok(u32::from_le_bytes(self.take(4)?.try_into().unwrap()))
+    }
+
+    fn string(&mut self, len: usize) -> io::Result<String> {
+        let bytes = self.take(len)?;
+        self.take(1)?;
+        Ok(String::from_utf8_lossy(bytes).to_string())
+    }
+
+    fn value(&mut self, signature: &str) -> io::Result<Value> {
+        let code = type_code(signature)?;
+        self.align(alignment(code));
+        let value = match code {
+            b'y' => Value::Byte(self.take(1)?[0]),
+            b'b' => Value::Bool(self.u32()? != 0),
+            b'n' => Value::Int(i16::from_le_bytes(self.take(2)?.try_into().unwrap()) as i64),
+            b'q' => Value::UInt(u16::from_le_bytes(self.take(2)?.try_into().unwrap()) as u64),
+            b'i' | b'h' => Value::Int(i32::from_le_bytes(self.take(4)?.try_into().unwrap()) as i64),
+            b'u' => Value::UInt(self.u32()? as u64),
+            b'x' => Value::Int(i64::from_le_bytes(self.take(8)?.try_into().unwrap())),
+            b't' => Value::UInt(u64::from_le_bytes(self.take(8)?.try_into().unwrap())),
+            b'd' => Value::Double(f64::from_le_bytes(self.take(8)?.try_into().unwrap())),
+            b's' | b'o' => {
+                let len = self.u32()? as usize;
+                Value::Str(self.string(len)?)
+            }
+            b'g' => {
+                let len = self.take(1)?[0] as usize;
+                Value::Str(self.string(len)?)
+            }
+            b'v' => {
+                let len = self.take(1)?[0] as usize;
+                let inner = self.string(len)?;
+                if split_signature(&inner)?.len() != 1 {
+                    return Err(invalid("variant must hold a single type"));
+                }
+                Value::Variant(Box::new(self.value(&inner)?))
+            }
+            b'a' => {
+                let len = self.u32()? as usize;
+                let element = &signature[1..];
+                self.align(alignment(type_code(element)?));
+                let end = self.pos + len;
+                let mut items = Vec::new();
+                while self.pos < end {
+                    items.push(self.value(element)?);
+                }
+                Value::Array(items)
+            }
+            b'(' | b'{' => {
+                let inner = &signature[1..signature.len() - 1];
+                let mut items = Vec::new();
+                for item_signature in split_signature(inner)? {
+                    items.push(self.value(item_signature)?);
+                }
+                Value::Struct(items)
+            }
+            _ => return Err(invalid("unsupported type in signature")),
+        };
+        Ok(value)
+    }
+
+    fn values(&mut self, signature: &str) -> io::Result<Vec<Value>> {
+        split_signature(signature)?
+            .into_iter()
+            .map(|item_signature| self.value(item_signature))
+            .collect()
+    }
+}
+
+pub fn bus_address(bus: &str) -> String {
+    match bus {
+        "system" => env::var("DBUS_SYSTEM_BUS_ADDRESS").unwrap_or_else(|_| SYSTEM_BUS.to_string()),
+        "session" => env::var("DBUS_SESSION_BUS_ADDRESS").unwrap_or_default(),
+        address => address.to_string(),
+    }
+}
+
+fn connect_address(address: &str) -> io::Result<UnixStream> {
+    let mut last_error = io::Error::new(io::ErrorKind::NotFound, "no usable bus address");
+    for entry in address.split(';') {
+        let params = match entry.strip_prefix("unix:") {
+            Some(params) => params,
+            None => continue,
+        };
+        for param in params.split(',') {
+            let result = match param.split_once('=') {
+                Some(("path", path)) => UnixStream::connect(path),
+                Some(("abstract", name)) => SocketAddr::from_abstract_name(name.as_bytes())
+                    .and_then(|addr| UnixStream::connect_addr(&addr)),
+                _ => continue,
+            };
+            match result {
+                Ok(stream) => return Ok(stream),
+                Err(err) => last_error = err,
+            }
+        }
+    }
+    Err(last_error)
+}
+
+pub struct Message {
+    pub kind: u8,
+    pub serial: u32,
+    pub member: Option<String>,
+    pub reply_serial: Option<u32>,
+    pub error_name: Option<String>,
+    pub body: Vec<Value>,
+}
+
+pub struct Connection {
+    stream: UnixStream,
+    serial: u32,
+}
+
+impl Connection {
+    pub fn open(bus: &str) -> io::Result<Self> {
+        let stream = connect_address(&bus_address(bus))?;
+        stream.set_read_timeout(Some(BUS_TIMEOUT))?;
+        stream.set_write_timeout(Some(BUS_TIMEOUT))?;
+
+        let mut connection = Self { stream, serial: 0 };
+        connection.authenticate()?;
+        connection.call(
+            "org.freedesktop.DBus",
+            "/org/freedesktop/DBus",
+            "org.freedesktop.DBus",
+            "Hello",
+            "",
+            &[],
+        )?;
+        Ok(connection)
+    }
+
+    fn authenticate(&mut self) -> io::Result<()> {
+        let uid = unsafe { libc::getuid() }.to_string();
+        let hex: String = uid.bytes().map(|b| format!("{:02x}", b)).collect();
+        self.stream.write_all(b"\0")?;
+        self.stream
+            .write_all(format!("AUTH EXTERNAL {}\r\n", hex).as_bytes())?;
+
+        let mut line = String::new();
+        BufReader::new(&self.stream).read_line(&mut line)?;
+        if !line.starts_with("OK ") {
+            return Err(io::Error::new(
+                io::ErrorKind::PermissionDenied,
+                format!("bus authentication failed: {}", line.trim()),
+            ));
+        }
+        self.stream.write_all(b"BEGIN\r\n")
+    }
+
+    pub fn call(
+        &mut self,
+        destination: &str,
+        path: &str,
+        interface: &str,
+        member: &str,
+        signature: &str,
+        args: &[Value],
+    ) -> io::Result<Vec<Value>> {
+        self.serial += 1;
+        let serial = self.serial;
+
+        let mut body = Writer { buf: Vec::new() };
+        for (item_signature, arg) in split_signature(signature)?.into_iter().zip(args) {
+            body.value(item_signature, arg)?;
+        }
+
+        let mut message = Writer { buf: Vec::new() };
+        message.buf.extend_from_slice(&[b'l', METHOD_CALL, 0, 1]);
+        message.u32(body.buf.len() as u32);
+        message.u32(serial);
+        message.u32(0);
+        message.header_field(FIELD_PATH, "o", path);
+        message.header_field(FIELD_INTERFACE, "s", interface);
+        message.header_field(FIELD_MEMBER, "s", member);
+        message.header_field(FIELD_DESTINATION, "s", destination);
+        if !signature.is_empty() {
+            message.header_field(FIELD_SIGNATURE, "g", signature);
+        }
+        let fields_len = (message.buf.len() - 16) as u32;
+        message.buf[12..16].copy_from_slice(&fields_len.to_le_bytes());
+        message.align(8);
+        message.buf.extend_from_slice(&body.buf);
+
+        self.stream.write_all(&message.buf)?;
+
+        loop {
+            let message = self.read_message()?;
+            if message.reply_serial != Some(serial) {
+                continue;
+            }
+            return match message.kind {
+                METHOD_RETURN => Ok(message.body),
+                ERROR => {
+                    let name = message.error_name.unwrap_or_default();
+                    let detail = message
+                        .body
+                        .first()
+                        .and_then(Value::as_str)
+                        .unwrap_or("")
+                        .to_string();
+                    let kind = if name.ends_with("AccessDenied")
+                        || name.ends_with("InteractiveAuthorizationRequired")
+                    {
+                        io::ErrorKind::PermissionDenied
+                    } else {
+                        io::ErrorKind::Other
+                    };
+                    Err(io::Error::new(kind, format!("{}: {}", name, detail)))
+                }
+                _ => continue,
+            };
+        }
+    }
+
+    fn read_message(&mut self) -> io::Result<Message> {
+        read_message(&mut self.stream)
+    }
+}
+
+fn read_message(stream: &mut impl Read) -> io::Result<Message> {
+    let mut fixed = [0u8; 16];
+    stream.read_exact(&mut fixed)?;
+    if fixed[0] != b'l' {
+        return Err(invalid("big-endian messages are not supported"));
+    }
+    let body_len = u32::from_le_bytes(fixed[4..8].try_into().unwrap()) as usize;
+    let fields_len = u32::from_le_bytes(fixed[12..16].try_into().unwrap()) as usize;
+    let header_len = (16 + fields_len).div_ceil(8) * 8;
+    if header_len + body_len > MAX_MESSAGE_SIZE {
+        return Err(invalid("message exceeds the maximum size"));
+    }
+
+    let mut rest = vec![0u8; header_len - 16 + body_len];
+    stream.read_exact(&mut rest)?;
+    let mut buf = fixed.to_vec();
+    buf.extend_from_slice(&rest);
+
+    let mut reader = Reader {
+        buf: &buf[..16 + fields_len],
+        pos: 12,
+    };
+    let fields = reader.value("a(yv)")?;
+
+    let mut message = Message {
+        kind: fixed[1],
+        serial: u32::from_le_bytes(fixed[8..12].try_into().unwrap()),
+        member: None,
+        reply_serial: None,
+        error_name: None,
+        body: Vec::new(),
+    };
+    let mut signature = String::new();
+    for field in fields.as_slice().unwrap_or(&[]) {
+        let items = field.as_slice().unwrap_or(&[]);
+        let (code, value) = match items {
+            [Value::Byte(code), value] => (*code, value),
+            _ => continue,
+        };
+        match code {
+            FIELD_MEMBER => message.member = value.as_str().map(str::to_string),
+            FIELD_REPLY_SERIAL => message.reply_serial = value.as_u64().map(|v| v as u32),
+            FIELD_ERROR_NAME => message.error_name = value.as_str().map(str::to_string),
+            FIELD_SIGNATURE => signature = value.as_str().unwrap_or("").to_string(),
+            _ => {}
+        }
+    }
+
+    let mut reader = Reader {
+        buf: &buf[header_len..],
+        pos: 0,
+    };
+    message.body = reader.values(&signature)?;
+    Ok(message)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use std::os::unix::net::UnixListener;
+    use std::path::PathBuf;
+    use std::thread;
+
+    fn encode(
+        kind: u8,
+        reply_serial: u32,
+        error_name: Option<&str>,
+        signature: &str,
+        body: &[Value],
+    ) -> Vec<u8> {
+        let mut payload = Writer { buf: Vec::new() };
+        for (item_signature, value) in split_signature(signature).unwrap().into_iter().zip(body) {
+            payload.value(item_signature, value).unwrap();
+        }
+
+        let mut message = Writer { buf: Vec::new() };
+        message.buf.extend_from_slice(&[b'l', kind, 0, 1]);
+        message.u32(payload.buf.len() as u32);
+        message.u32(reply_serial + 1000);
+        message.u32(0);
+        message.align(8);
+        message.u8(FIELD_REPLY_SERIAL);
+        message.signature("u");
+        message.u32(reply_serial);
+        if let Some(name) = error_name {
+            message.header_field(FIELD_ERROR_NAME, "s", name);
+        }
+        if !signature.is_empty() {
+            message.header_field(FIELD_SIGNATURE, "g", signature);
+        }
+        let fields_len = (message.buf.len() - 16) as u32;
+        message.buf[12..16].copy_from_slice(&fields_len.to_le_bytes());
+        message.align(8);
+        message.buf.extend_from_slice(&payload.buf);
+        message.buf
+    }
+
+    fn serve(name: &str, respond: fn(&Message) -> Vec<u8>) -> (String, PathBuf) {
+        let path = std::env::temp_dir().join(format!("rtop-{}-{}.bus", std::process::id(), name));
+        let _ = fs::remove_file(&path);
+        let listener = UnixListener::bind(&path).unwrap();
+        thread::spawn(move || {
+            let (stream, _) = listener.accept().unwrap();
+            let mut reader = BufReader::new(stream.try_clone().unwrap());
+            let mut writer = stream;
+            let mut line = Vec::new();
+            reader.read_until(b'\n', &mut line).unwrap();
+            assert!(line.starts_with(b"\0AUTH EXTERNAL "));
+            writer.write_all(b"OK 0123456789abcdef\r\n").unwrap();
+            line.clear();
+            reader.read_until(b'\n', &mut line).unwrap();
+            assert_eq!(line, b"BEGIN\r\n");
+
+            while let Ok(message) = read_message(&mut reader) {
+                let reply = match message.member.as_deref() {
+                    Some("Hello") => encode(
+                        METHOD_RETURN,
+                        message.serial,
+                        None,
+                        "s",
+                        &[Value::Str(":1.1".to_string())],
+                    ),
+                    _ => respond(&message),
+                };
+                writer.write_all(&reply).unwrap();
+            }
+        });
+        (format!("unix:path={}", path.display()), path)
+    }
+
+    #[test]
+    fn values_round_trip() {
+        let signature = "ybiuxtsoga(sv)(ias)";
+        let values = vec![
+            Value::Byte(7),
+            Value::Bool(true),
+            Value::Int(-70000),
+            Value::UInt(4_000_000_000),
+            Value::Int(-5_000_000_000),
+            Value::UInt(u64::MAX),
+            Value::Str("text".to_string()),
+            Value::Str("/org/example".to_string()),
+            Value::Str("a{sv}".to_string()),
+            Value::Array(vec![
+                Value::Struct(vec![
+                    Value::Str("MainPID".to_string()),
+                    Value::Variant(Box::new(Value::UInt(42))),
+                ]),
+                Value::Struct(vec![
+                    Value::Str("Id".to_string()),
+                    Value::Variant(Box::new(Value::Str("ssh.service".to_string()))),
+                ]),
+            ]),
+            Value::Struct(vec![Value::Int(1), Value::Array(vec![])]),
+        ];
+
+        let mut writer = Writer { buf: Vec::new() };
+        for (item_signature, value) in split_signature(signature).unwrap().into_iter().zip(&values)
+        {
+            writer.value(item_signature, value).unwrap();
+        }
+
+        let mut reader = Reader {
+            buf: &writer.buf,
+            pos: 0,
+        };
+        assert_eq!(reader.values(signature).unwrap(), values);
+        assert_eq!(reader.pos, writer.buf.len());
+    }
+
+    #[test]
+    fn reader_rejects_bad_variant_signatures() {
+        for signature in ["", "(", "a", "ss", "()"] {
+            let mut buf = vec![signature.len() as u8];
+            buf.extend_from_slice(signature.as_bytes());
+            buf.extend_from_slice(&[0; 16]);
+            let mut reader = Reader { buf: &buf, pos: 0 };
+            let err = reader.value("v").unwrap_err();
+            assert_eq!(err.kind(), io::ErrorKind::InvalidData, "{:?}", signature);
+        }
+
+        let mut reader = Reader {
+            buf: &[1, 2, 3],
+            pos: 0,
+        };
+        assert_eq!(
+            reader.value("u").unwrap_err().kind(),
+            io::ErrorKind::InvalidData
+        );
+    }
+
+    #[test]
+    fn call_returns_reply_body() {
+        let (address, path) = serve("call", |message| {
+            assert_eq!(message.member.as_deref(), Some("Get"));
+            assert_eq!(
+                message.body,
+                vec![
+                    Value::Str("org.example.Service".to_string()),
+                    Value::Str("MainPID".to_string()),
+                ]
+            );
+            encode(
+                METHOD_RETURN,
+                message.serial,
+                None,
+                "v",
+                &[Value::Variant(Box::new(Value::UInt(42)))],
+            )
+        });
+
+        let mut connection = Connection::open(&address).unwrap();
+        let reply = connection
+            .call(
+                "org.example",
+                "/org/example",
+                "org.freedesktop.DBus.Properties",
+                "Get",
+                "ss",
+                &[
+                    Value::Str("org.example.Service".to_string()),
+                    Value::Str("MainPID".to_string()),
+                ],
+            )
+            .unwrap();
+        fs::remove_file(&path).unwrap();
+
+        assert_eq!(reply.first().and_then(Value::as_u64), Some(42));
+    }
+
+    #[test]
+    fn call_maps_access_denied() {
+        let (address, path) = serve("denied", |message| {
+            encode(
+                ERROR,
+                message.serial,
+                Some("org.freedesktop.DBus.Error.AccessDenied"),
+                "s",
+                &[Value::Str("not allowed".to_string())],
+            )
+        });
+
+        let mut connection = Connection::open(&address).unwrap();
+        let err = connection
+            .call("org.example", "/", "org.example", "Stop", "", &[])
+            .unwrap_err();
+        fs::remove_file(&path).unwrap();
+
+        assert_eq!(err.kind(), io::ErrorKind::PermissionDenied);
+        assert!(err.to_string().contains("not allowed"));
+    }
+
+    #[test]
+    fn call_rejects_empty_variant_signature() {
+        let (address, path) = serve("variant", |message| {
+            let mut reply = encode(
+                METHOD_RETURN,
+                message.serial,
+                None,
+                "v",
+                &[Value::Variant(Box::new(Value::Byte(0)))],
+            );
+            let body = reply.len() - 4;
+            reply[body..].copy_from_slice(&[0, 0, 0, 0]);
+            reply
+        });
+
+        let mut connection = Connection::open(&address).unwrap();
+        let err = connection
+            .call("org.example", "/", "org.example", "Get", "", &[])
+            .unwrap_err();
+        fs::remove_file(&path).unwrap();
+
+        assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+    }
+
+    #[test]
+    fn read_message_rejects_oversized_lengths() {
+        let mut header = vec![b'l', METHOD_RETURN, 0, 1];
+        header.extend_from_slice(&u32::MAX.to_le_bytes());
+        header.extend_from_slice(&1u32.to_le_bytes());
+        header.extend_from_slice(&0u32.to_le_bytes());
+
+        let err = read_message(&mut header.as_slice()).err().unwrap();
+        assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+    }
+}
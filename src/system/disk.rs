@@ -1,11 +1,230 @@
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::ffi::CString;
+use std::fs;
+use std::sync::mpsc::{self, Receiver, Sender};
+use std::thread;
+use std::time::{Duration, Instant};
 use sysinfo::{DiskExt, System, SystemExt};
 
+use crate::config::DiskConfig;
+use crate::util::matches_any;
+
+const PROC_MOUNTINFO: &str = "/proc/self/mountinfo";
+const MIN_FORECAST_SAMPLES: usize = 3;
+
+const PSEUDO_FILESYSTEMS: &[&str] = &[
+    "autofs",
+    "binfmt_misc",
+    "bpf",
+    "cgroup",
+    "cgroup2",
+    "configfs",
+    "debugfs",
+    "devpts",
+    "devtmpfs",
+    "efivarfs",
+    "fusectl",
+    "fuse.lxcfs",
+    "fuse.portal",
+    "hugetlbfs",
+    "mqueue",
+    "nsfs",
+    "overlay",
+    "proc",
+    "pstore",
+    "ramfs",
+    "rpc_pipefs",
+    "securityfs",
+    "selinuxfs",
+    "squashfs",
+    "sysfs",
+    "tmpfs",
+    "tracefs",
+];
+
+const NETWORK_FILESYSTEMS: &[&str] = &[
+    "9p",
+    "afs",
+    "ceph",
+    "cifs",
+    "fuse.davfs",
+    "fuse.glusterfs",
+    "fuse.sshfs",
+    "glusterfs",
+    "ncpfs",
+    "nfs",
+    "nfs4",
+    "smb3",
+    "smbfs",
+];
+
+fn is_pseudo_mount(file_system: &str, mount_point: &str) -> bool {
+    PSEUDO_FILESYSTEMS.contains(&file_system)
+        || mount_point.starts_with("/proc")
+        || mount_point.starts_with("/sys")
+}
+
+pub fn is_network_filesystem(file_system: &str) -> bool {
+    NETWORK_FILESYSTEMS.contains(&file_system)
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct MountEntry {
+    pub major_minor: String,
+    pub device: String,
+    pub mount_point: String,
+    pub file_system: String,
+    pub options: Vec<String>,
+}
+
+impl MountEntry {
+    pub fn has_option(&self, option: &str) -> bool {
+        self.options.iter().any(|o| o == option)
+    }
+}
+
+fn unescape_mount_field(field: &str) -> String {
+    let mut result = String::with_capacity(field.len());
+    let mut chars = field.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        if c == '\\' {
+            let octal: String = chars.clone().take(3).collect();
+            if octal.len() == 3 {
+                if let Ok(value) = u8::from_str_radix(&octal, 8) {
+                    result.push(value as char);
+                    for _ in 0..3 {
+                        chars.next();
+                    }
+                    continue;
+                }
+            }
+        }
+        result.push(c);
+    }
+
+    result
+}
+
+pub fn parse_mountinfo(content: &str) -> Vec<MountEntry> {
+    content
+        .lines()
+        .filter_map(|line| {
+            let (left, right) = line.split_once(" - ")?;
+            let left: Vec<&str> = left.split_whitespace().collect();
+            let right: Vec<&str> = right.split_whitespace().collect();
+            if left.len() < 6 || right.len() < 2 {
+                return None;
+            }
+
+            let mut options: Vec<String> = left[5].split(',').map(str::to_string).collect();
+            if let Some(super_options) = right.get(2) {
+                for option in super_options.split(',') {
+                    if !options.iter().any(|o| o == option) {
+                        options.push(option.to_string());
+                    }
+                }
+            }
+
+            Some(MountEntry {
+                major_minor: left[2].to_string(),
+                device: unescape_mount_field(right[1]),
+                mount_point: unescape_mount_field(left[4]),
+                file_system: right[0].to_string(),
+                options,
+            })
+        })
+        .collect()
+}
+
+#[derive(Debug, Clone, Copy, Default)]
+pub struct FsStats {
+    pub total_space: u64,
+    pub available_space: u64,
+    pub total_inodes: u64,
+    pub free_inodes: u64,
+}
+
+pub fn statvfs(path: &str) -> Option<FsStats> {
+    let c_path = CString::new(path).ok()?;
+    let mut stat: libc::statvfs = unsafe { std::mem::zeroed() };
+
+    if unsafe { libc::statvfs(c_path.as_ptr(), &mut stat) } != 0 {
+        return None;
+    }
+
+    let fragment = stat.f_frsize as u64;
+    Some(FsStats {
+        total_space: stat.f_blocks as u64 * fragment,
+        available_space: stat.f_bavail as u64 * fragment,
+        total_inodes: stat.f_files as u64,
+        free_inodes: stat.f_ffree as u64,
+    })
+}
+
+pub struct DiskFilter {
+    hide_pseudo: bool,
+    include_fs: Vec<String>,
+    exclude_fs: Vec<String>,
+    include_devices: Vec<String>,
+    exclude_devices: Vec<String>,
+    include_mounts: Vec<String>,
+    exclude_mounts: Vec<String>,
+}
+
+impl DiskFilter {
+    pub fn from_config(config: &DiskConfig) -> Self {
+        Self {
+            hide_pseudo: config.hide_pseudo,
+            include_fs: config.include_fs.clone(),
+            exclude_fs: config.exclude_fs.clone(),
+            include_devices: config.include_devices.clone(),
+            exclude_devices: config.exclude_devices.clone(),
+            include_mounts: config.include_mounts.clone(),
+            exclude_mounts: config.exclude_mounts.clone(),
+        }
+    }
+
+    fn included(patterns: &[String], text: &str) -> bool {
+        patterns.is_empty() || matches_any(patterns, text)
+    }
+
+    fn matches_fields(&self, file_system: &str, device: &str, mount_point: &str) -> bool {
+        Self::included(&self.include_fs, file_system)
+            && !matches_any(&self.exclude_fs, file_system)
+            && Self::included(&self.include_devices, device)
+            && !matches_any(&self.exclude_devices, device)
+            && Self::included(&self.include_mounts, mount_point)
+            && !matches_any(&self.exclude_mounts, mount_point)
+    }
+
+    pub fn matches_mount(&self, entry: &MountEntry) -> bool {
+        if self.hide_pseudo && is_pseudo_mount(&entry.file_system, &entry.mount_point) {
+            return false;
+        }
+
+        self.matches_fields(&entry.file_system, &entry.device, &entry.mount_point)
+    }
+
+    pub fn matches(&self, disk: &DiskInfo) -> bool {
+        if self.hide_pseudo && disk.is_pseudo() {
+            return false;
+        }
+
+        self.matches_fields(&disk.file_system, &disk.name, &disk.mount_point)
+    }
+}
+
 pub struct DiskInfo {
     name: String,
     mount_point: String,
     total_space: u64,
     available_space: u64,
     file_system: String,
+    total_inodes: Option<u64>,
+    free_inodes: Option<u64>,
+    options: Vec<String>,
+    growth_rate: Option<f64>,
 }
 
 impl DiskInfo {
@@ -16,6 +235,24 @@ impl DiskInfo {
             total_space: disk.total_space(),
             available_space: disk.available_space(),
             file_system: String::from_utf8_lossy(disk.file_system()).to_string(),
+            total_inodes: None,
+            free_inodes: None,
+            options: Vec::new(),
+            growth_rate: None,
+        }
+    }
+
+    fn from_mount(entry: &MountEntry, stats: FsStats) -> Self {
+        Self {
+            name: entry.device.clone(),
+            mount_point: entry.mount_point.clone(),
+            total_space: stats.total_space,
+            available_space: stats.available_space.min(stats.total_space),
+            file_system: entry.file_system.clone(),
+            total_inodes: Some(stats.total_inodes),
+            free_inodes: Some(stats.free_inodes.min(stats.total_inodes)),
+            options: entry.options.clone(),
+            growth_rate: None,
         }
     }
 
@@ -49,36 +286,229 @@ impl DiskInfo {
     pub fn get_file_system(&self) -> &str {
         &self.file_system
     }
+
+    pub fn get_total_inodes(&self) -> Option<u64> {
+        self.total_inodes
+    }
+
+    pub fn get_used_inodes(&self) -> Option<u64> {
+        Some(self.total_inodes? - self.free_inodes?)
+    }
+
+    pub fn get_inode_usage_percent(&self) -> Option<f64> {
+        let total = self.total_inodes?;
+        if total == 0 {
+            return None;
+        }
+        Some(self.get_used_inodes()? as f64 / total as f64 * 100.0)
+    }
+
+    pub fn get_options(&self) -> &[String] {
+        &self.options
+    }
+
+    pub fn is_read_only(&self) -> bool {
+        self.options.iter().any(|o| o == "ro")
+    }
+
+    pub fn is_noexec(&self) -> bool {
+        self.options.iter().any(|o| o == "noexec")
+    }
+
+    pub fn get_growth_rate(&self) -> Option<f64> {
+        self.growth_rate
+    }
+
+    pub fn get_time_to_full(&self) -> Option<Duration> {
+        let rate = self.growth_rate?;
+        if rate <= 0.0 {
+            return None;
+        }
+        Some(
+            Duration::try_from_secs_f64(self.available_space as f64 / rate)
+                .unwrap_or(Duration::MAX),
+        )
+    }
+
+    pub fn is_pseudo(&self) -> bool {
+        self.total_space == 0 || is_pseudo_mount(&self.file_system, &self.mount_point)
+    }
+}
+
+pub fn fit_growth_rate(samples: &VecDeque<(Instant, u64)>) -> Option<f64> {
+    if samples.len() < MIN_FORECAST_SAMPLES {
+        return None;
+    }
+
+    let start = samples.front()?.0;
+    let points: Vec<(f64, f64)> = samples
+        .iter()
+        .map(|(at, used)| (at.duration_since(start).as_secs_f64(), *used as f64))
+        .collect();
+
+    let n = points.len() as f64;
+    let mean_x = points.iter().map(|(x, _)| x).sum::<f64>() / n;
+    let mean_y = points.iter().map(|(_, y)| y).sum::<f64>() / n;
+
+    let covariance: f64 = points
+        .iter()
+        .map(|(x, y)| (x - mean_x) * (y - mean_y))
+        .sum();
+    let variance: f64 = points.iter().map(|(x, _)| (x - mean_x).powi(2)).sum();
+
+    if variance <= f64::EPSILON {
+        return None;
+    }
+
+    Some(covariance / variance)
 }
 
 pub struct DiskState {
     system: System,
     disks: Vec<DiskInfo>,
+    filter: DiskFilter,
+    forecast_window: Duration,
+    forecast_warning: Duration,
+    usage_samples: HashMap<String, VecDeque<(Instant, u64)>>,
+    network_stats: HashMap<String, FsStats>,
+    pending_stats: HashSet<String>,
+    sender: Sender<(String, Option<FsStats>)>,
+    receiver: Receiver<(String, Option<FsStats>)>,
 }
 
 impl DiskState {
-    pub fn new() -> Self {
+    pub fn new(config: &DiskConfig) -> Self {
         let mut system = System::new_all();
         system.refresh_disks_list();
 
-        let disks = system
-            .disks()
-            .iter()
-            .map(|disk| DiskInfo::new(disk))
-            .collect();
-
-        Self { system, disks }
+        let (sender, receiver) = mpsc::channel();
+        let mut state = Self {
+            system,
+            disks: Vec::new(),
+            filter: DiskFilter::from_config(config),
+            forecast_window: Duration::from_secs(config.forecast_window),
+            forecast_warning: Duration::from_secs(config.forecast_warning),
+            usage_samples: HashMap::new(),
+            network_stats: HashMap::new(),
+            pending_stats: HashSet::new(),
+            sender,
+            receiver,
+        };
+        state.collect();
+        state
     }
 
     pub fn update(&mut self) {
-        self.system.refresh_disks();
+        self.collect();
+    }
+
+    fn collect(&mut self) {
+        let disks = match fs::read_to_string(PROC_MOUNTINFO) {
+            Ok(content) => self.collect_mounts(&content),
+            Err(_) => {
+                self.system.refresh_disks();
+                self.system.disks().iter().map(DiskInfo::new).collect()
+            }
+        };
 
-        self.disks = self
-            .system
-            .disks()
-            .iter()
-            .map(|disk| DiskInfo::new(disk))
+        self.disks = disks
+            .into_iter()
+            .filter(|disk| self.filter.matches(disk))
             .collect();
+
+        self.update_forecasts();
+    }
+
+    fn update_forecasts(&mut self) {
+        let now = Instant::now();
+        let window = self.forecast_window;
+
+        self.usage_samples
+            .retain(|mount, _| self.disks.iter().any(|d| &d.mount_point == mount));
+
+        for disk in &mut self.disks {
+            let samples = self
+                .usage_samples
+                .entry(disk.mount_point.clone())
+                .or_default();
+
+            samples.push_back((now, disk.get_used_space()));
+            while let Some((at, _)) = samples.front() {
+                if now.duration_since(*at) > window {
+                    samples.pop_front();
+                } else {
+                    break;
+                }
+            }
+
+            disk.growth_rate = fit_growth_rate(samples);
+        }
+    }
+
+    fn collect_mounts(&mut self, content: &str) -> Vec<DiskInfo> {
+        let mut disks: Vec<DiskInfo> = Vec::new();
+
+        while let Ok((mount_point, stats)) = self.receiver.try_recv() {
+            self.pending_stats.remove(&mount_point);
+            match stats {
+                Some(stats) => self.network_stats.insert(mount_point, stats),
+                None => self.network_stats.remove(&mount_point),
+            };
+        }
+
+        for entry in parse_mountinfo(content) {
+            if !self.filter.matches_mount(&entry) {
+                continue;
+            }
+
+            let stats = if is_network_filesystem(&entry.file_system) {
+                self.request_network_stats(&entry.mount_point);
+                self.network_stats.get(&entry.mount_point).copied()
+            } else {
+                statvfs(&entry.mount_point)
+            };
+            let stats = match stats {
+                Some(stats) => stats,
+                None => continue,
+            };
+
+            disks.retain(|disk| disk.mount_point != entry.mount_point);
+            disks.push(DiskInfo::from_mount(&entry, stats));
+        }
+
+        disks
+    }
+
+    fn request_network_stats(&mut self, mount_point: &str) {
+        if !self.pending_stats.insert(mount_point.to_string()) {
+            return;
+        }
+
+        let sender = self.sender.clone();
+        let mount_point = mount_point.to_string();
+        thread::spawn(move || {
+            let stats = statvfs(&mount_point);
+            let _ = sender.send((mount_point, stats));
+        });
+    }
+
+    pub fn is_hiding_pseudo(&self) -> bool {
+        self.filter.hide_pseudo
+    }
+
+    pub fn get_forecast_warning(&self) -> Duration {
+        self.forecast_warning
+    }
+
+    pub fn is_filling_soon(&self, disk: &DiskInfo) -> bool {
+        disk.get_time_to_full()
+            .map(|eta| eta < self.forecast_warning)
+            .unwrap_or(false)
+    }
+
+    pub fn toggle_pseudo(&mut self) {
+        self.filter.hide_pseudo = !self.filter.hide_pseudo;
+        self.collect();
     }
 
     pub fn get_disks(&self) -> &[DiskInfo] {
@@ -0,0 +1,320 @@
+use std::collections::{HashMap, VecDeque};
+use std::fs;
+use std::path::Path;
+use std::time::Instant;
+
+const PROC_DISKSTATS: &str = "/proc/diskstats";
+const SECTOR_SIZE: u64 = 512;
+
+#[derive(Debug, Clone, Copy, Default)]
+pub struct DiskStatsCounters {
+    pub reads_completed: u64,
+    pub sectors_read: u64,
+    pub read_ticks: u64,
+    pub writes_completed: u64,
+    pub sectors_written: u64,
+    pub write_ticks: u64,
+    pub in_flight: u64,
+    pub io_ticks: u64,
+    pub time_in_queue: u64,
+}
+
+pub fn parse_diskstats(content: &str) -> Vec<(String, DiskStatsCounters)> {
+    content
+        .lines()
+        .filter_map(|line| {
+            let fields: Vec<&str> = line.split_whitespace().collect();
+            if fields.len() < 14 {
+                return None;
+            }
+            let num = |idx: usize| fields[idx].parse::<u64>().unwrap_or(0);
+
+            Some((
+                fields[2].to_string(),
+                DiskStatsCounters {
+                    reads_completed: num(3),
+                    sectors_read: num(5),
+                    read_ticks: num(6),
+                    writes_completed: num(7),
+                    sectors_written: num(9),
+                    write_ticks: num(10),
+                    in_flight: num(11),
+                    io_ticks: num(12),
+                    time_in_queue: num(13),
+                },
+            ))
+        })
+        .collect()
+}
+
+pub struct BlockDevice {
+    name: String,
+    is_partition: bool,
+    is_stacked: bool,
+    counters: DiskStatsCounters,
+    read_rate: f64,
+    write_rate: f64,
+    read_iops: f64,
+    write_iops: f64,
+    latency_ms: f64,
+    queue_depth: f64,
+    utilization: f64,
+    last_update: Instant,
+    read_rate_history: VecDeque<f64>,
+    write_rate_history: VecDeque<f64>,
+    utilization_history: VecDeque<f64>,
+    history_size: usize,
+}
+
+impl BlockDevice {
+    fn new(name: &str, counters: DiskStatsCounters, history_size: usize) -> Self {
+        let sys_path = Path::new("/sys/class/block").join(name);
+
+        Self {
+            name: name.to_string(),
+            is_partition: sys_path.join("partition").exists(),
+            is_stacked: fs::read_dir(sys_path.join("slaves"))
+                .map(|mut entries| entries.next().is_some())
+                .unwrap_or(false),
+            counters,
+            read_rate: 0.0,
+            write_rate: 0.0,
+            read_iops: 0.0,
+            write_iops: 0.0,
+            latency_ms: 0.0,
+            queue_depth: 0.0,
+            utilization: 0.0,
+            last_update: Instant::now(),
+            read_rate_history: VecDeque::with_capacity(history_size),
+            write_rate_history: VecDeque::with_capacity(history_size),
+            utilization_history: VecDeque::with_capacity(history_size),
+            history_size,
+        }
+    }
+
+    fn update(&mut self, counters: DiskStatsCounters) {
+        let now = Instant::now();
+        let time_delta = now.duration_since(self.last_update).as_secs_f64();
+        let prev = self.counters;
+
+        if time_delta > 0.001 {
+            let elapsed_ms = time_delta * 1000.0;
+            let reads = counters
+                .reads_completed
+                .saturating_sub(prev.reads_completed);
+            let writes = counters
+                .writes_completed
+                .saturating_sub(prev.writes_completed);
+            let sectors_read = counters.sectors_read.saturating_sub(prev.sectors_read);
+            let sectors_written = counters
+                .sectors_written
+                .saturating_sub(prev.sectors_written);
+            let ticks = counters.read_ticks.saturating_sub(prev.read_ticks)
+                + counters.write_ticks.saturating_sub(prev.write_ticks);
+
+            self.read_rate = (sectors_read * SECTOR_SIZE) as f64 / time_delta;
+            self.write_rate = (sectors_written * SECTOR_SIZE) as f64 / time_delta;
+            self.read_iops = reads as f64 / time_delta;
+            self.write_iops = writes as f64 / time_delta;
+            self.latency_ms = if reads + writes > 0 {
+                ticks as f64 / (reads + writes) as f64
+            } else {
+                0.0
+            };
+            self.queue_depth =
+                counters.time_in_queue.saturating_sub(prev.time_in_queue) as f64 / elapsed_ms;
+            self.utilization =
+                (counters.io_ticks.saturating_sub(prev.io_ticks) as f64 / elapsed_ms * 100.0)
+                    .min(100.0);
+        }
+
+        self.counters = counters;
+        self.last_update = now;
+
+        self.read_rate_history.push_back(self.read_rate);
+        self.write_rate_history.push_back(self.write_rate);
+        self.utilization_history.push_back(self.utilization);
+
+        if self.read_rate_history.len() > self.history_size {
+            self.read_rate_history.pop_front();
+        }
+
+        if self.write_rate_history.len() > self.history_size {
+            self.write_rate_history.pop_front();
+        }
+
+        if self.utilization_history.len() > self.history_size {
+            self.utilization_history.pop_front();
+        }
+    }
+
+    pub fn get_name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn is_partition(&self) -> bool {
+        self.is_partition
+    }
+
+    pub fn is_stacked(&self) -> bool {
+        self.is_stacked
+    }
+
+    pub fn is_virtual(&self) -> bool {
+        self.name.starts_with("loop") || self.name.starts_with("ram")
+    }
+
+    pub fn get_counters(&self) -> &DiskStatsCounters {
+        &self.counters
+    }
+
+    pub fn get_read_rate(&self) -> f64 {
+        self.read_rate
+    }
+
+    pub fn get_write_rate(&self) -> f64 {
+        self.write_rate
+    }
+
+    pub fn get_read_iops(&self) -> f64 {
+        self.read_iops
+    }
+
+    pub fn get_write_iops(&self) -> f64 {
+        self.write_iops
+    }
+
+    pub fn get_latency_ms(&self) -> f64 {
+        self.latency_ms
+    }
+
+    pub fn get_queue_depth(&self) -> f64 {
+        self.queue_depth
+    }
+
+    pub fn get_utilization(&self) -> f64 {
+        self.utilization
+    }
+
+    pub fn get_in_flight(&self) -> u64 {
+        self.counters.in_flight
+    }
+
+    pub fn get_read_rate_history(&self) -> &VecDeque<f64> {
+        &self.read_rate_history
+    }
+
+    pub fn get_write_rate_history(&self) -> &VecDeque<f64> {
+        &self.write_rate_history
+    }
+
+    pub fn get_utilization_history(&self) -> &VecDeque<f64> {
+        &self.utilization_history
+    }
+}
+
+pub struct DiskIoState {
+    devices: HashMap<String, BlockDevice>,
+    read_rate_history: VecDeque<f64>,
+    write_rate_history: VecDeque<f64>,
+    history_size: usize,
+}
+
+impl DiskIoState {
+    pub fn new(history_size: usize) -> Self {
+        let devices = fs::read_to_string(PROC_DISKSTATS)
+            .map(|content| parse_diskstats(&content))
+            .unwrap_or_default()
+            .into_iter()
+            .map(|(name, counters)| {
+                let device = BlockDevice::new(&name, counters, history_size);
+                (name.clone(), device)
+            })
+            .collect();
+
+        Self {
+            devices,
+            read_rate_history: VecDeque::with_capacity(history_size),
+            write_rate_history: VecDeque::with_capacity(history_size),
+            history_size,
+        }
+    }
+
+    pub fn update(&mut self) {
+        let stats = match fs::read_to_string(PROC_DISKSTATS) {
+            Ok(content) => parse_diskstats(&content),
+            Err(_) => return,
+        };
+
+        self.devices
+            .retain(|name, _| stats.iter().any(|(stat_name, _)| stat_name == name));
+
+        for (name, counters) in stats {
+            if let Some(device) = self.devices.get_mut(&name) {
+                device.update(counters);
+            } else {
+                self.devices.insert(
+                    name.clone(),
+                    BlockDevice::new(&name, counters, self.history_size),
+                );
+            }
+        }
+
+        self.read_rate_history.push_back(self.get_total_read_rate());
+        self.write_rate_history
+            .push_back(self.get_total_write_rate());
+
+        if self.read_rate_history.len() > self.history_size {
+            self.read_rate_history.pop_front();
+        }
+
+        if self.write_rate_history.len() > self.history_size {
+            self.write_rate_history.pop_front();
+        }
+    }
+
+    pub fn is_available(&self) -> bool {
+        !self.devices.is_empty()
+    }
+
+    pub fn get_device(&self, name: &str) -> Option<&BlockDevice> {
+        self.devices.get(name)
+    }
+
+    pub fn get_devices(&self) -> Vec<&BlockDevice> {
+        let mut devices: Vec<&BlockDevice> = self.devices.values().collect();
+        devices.sort_by(|a, b| a.name.cmp(&b.name));
+        devices
+    }
+
+    pub fn get_physical_devices(&self) -> Vec<&BlockDevice> {
+        self.get_devices()
+            .into_iter()
+            .filter(|d| !d.is_partition() && !d.is_virtual())
+            .collect()
+    }
+
+    pub fn get_total_read_rate(&self) -> f64 {
+        self.get_physical_devices()
+            .iter()
+            .filter(|d| !d.is_stacked())
+            .map(|d| d.read_rate)
+            .sum()
+    }
+
+    pub fn get_total_write_rate(&self) -> f64 {
+        self.get_physical_devices()
+            .iter()
+            .filter(|d| !d.is_stacked())
+            .map(|d| d.write_rate)
+            .sum()
+    }
+
+    pub fn get_read_rate_history(&self) -> &VecDeque<f64> {
+        &self.read_rate_history
+    }
+
+    pub fn get_write_rate_history(&self) -> &VecDeque<f64> {
+        &self.write_rate_history
+    }
+}
@@ -1,7 +1,35 @@
 use std::collections::VecDeque;
+use std::fs;
 use sysinfo::{System, SystemExt};
 
-const HISTORY_SIZE: usize = 100;
+const PROC_MEMINFO: &str = "/proc/meminfo";
+const MEMINFO_FIELDS: [(&str, &str); 12] = [
+    ("MemAvailable", "Available"),
+    ("MemFree", "Free"),
+    ("Buffers", "Buffers"),
+    ("Cached", "Cached"),
+    ("Shmem", "Shared"),
+    ("AnonPages", "Anonymous"),
+    ("Mapped", "Mapped"),
+    ("Slab", "Slab"),
+    ("PageTables", "Page tables"),
+    ("Dirty", "Dirty"),
+    ("Writeback", "Writeback"),
+    ("SwapCached", "Swap cached"),
+];
+
+pub fn parse_meminfo(content: &str) -> Vec<(&'static str, u64)> {
+    MEMINFO_FIELDS
+        .iter()
+        .filter_map(|(key, label)| {
+            let line = content
+                .lines()
+                .find(|line| line.split(':').next() == Some(*key))?;
+            let kib: u64 = line.split_whitespace().nth(1)?.parse().ok()?;
+            Some((*label, kib * 1024))
+        })
+        .collect()
+}
 
 pub struct MemoryState {
     system: System,
@@ -11,10 +39,12 @@ pub struct MemoryState {
     used_swap: u64,
     memory_history: VecDeque<f64>,
     swap_history: VecDeque<f64>,
+    history_size: usize,
+    details: Vec<(&'static str, u64)>,
 }
 
 impl MemoryState {
-    pub fn new() -> Self {
+    pub fn new(history_size: usize) -> Self {
         let mut system = System::new_all();
         system.refresh_memory();
 
@@ -24,8 +54,10 @@ impl MemoryState {
             total_swap: system.total_swap(),
             used_swap: system.used_swap(),
             system,
-            memory_history: VecDeque::with_capacity(HISTORY_SIZE),
-            swap_history: VecDeque::with_capacity(HISTORY_SIZE),
+            memory_history: VecDeque::with_capacity(history_size),
+            swap_history: VecDeque::with_capacity(history_size),
+            history_size,
+            details: Vec::new(),
         }
     }
 
@@ -35,6 +67,9 @@ impl MemoryState {
         self.used_memory = self.system.used_memory();
         self.total_swap = self.system.total_swap();
         self.used_swap = self.system.used_swap();
+        self.details = fs::read_to_string(PROC_MEMINFO)
+            .map(|content| parse_meminfo(&content))
+            .unwrap_or_default();
 
         let mem_percent = self.get_memory_usage_percent();
         let swap_percent = self.get_swap_usage_percent();
@@ -42,11 +77,11 @@ impl MemoryState {
         self.memory_history.push_back(mem_percent);
         self.swap_history.push_back(swap_percent);
 
-        if self.memory_history.len() > HISTORY_SIZE {
+        if self.memory_history.len() > self.history_size {
             self.memory_history.pop_front();
         }
 
-        if self.swap_history.len() > HISTORY_SIZE {
+        if self.swap_history.len() > self.history_size {
             self.swap_history.pop_front();
         }
     }
@@ -88,4 +123,8 @@ impl MemoryState {
     pub fn get_swap_history(&self) -> &VecDeque<f64> {
         &self.swap_history
     }
+
+    pub fn get_details(&self) -> &[(&'static str, u64)] {
+        &self.details
+    }
 }
@@ -1,36 +1,102 @@
 #[allow(dead_code)]
+pub mod blocktree;
+#[allow(dead_code)]
+pub mod cgroup;
+#[allow(dead_code)]
+pub mod connections;
+#[allow(dead_code)]
+pub mod container;
+#[allow(dead_code)]
 pub mod cpu;
 #[allow(dead_code)]
 pub mod memory;
 #[allow(dead_code)]
 pub mod process;
 #[allow(dead_code)]
+pub mod dbus;
+#[allow(dead_code)]
 pub mod disk;
 #[allow(dead_code)]
+pub mod diskio;
+#[allow(dead_code)]
 pub mod network;
+#[allow(dead_code)]
+pub mod nettraffic;
+#[allow(dead_code)]
+pub mod pressure;
+#[allow(dead_code)]
+pub mod sensors;
+#[allow(dead_code)]
+pub mod smaps;
+#[allow(dead_code)]
+pub mod systemd;
+#[allow(dead_code)]
+pub mod vmstat;
 
+pub use blocktree::BlockTree;
+pub use cgroup::CgroupState;
+pub use connections::ConnectionState;
+pub use container::ContainerState;
 pub use cpu::CpuState;
 pub use memory::MemoryState;
 pub use process::ProcessList;
 pub use disk::DiskState;
+pub use diskio::DiskIoState;
+pub use nettraffic::NetTrafficState;
 pub use network::NetworkState;
+pub use pressure::PressureState;
+pub use sensors::SensorState;
+pub use systemd::SystemdState;
+pub use vmstat::VmStatState;
+
+use crate::alerts::AlertState;
+use crate::config::Config;
+use std::time::Duration;
 
 pub struct SystemState {
     pub cpu: CpuState,
     pub memory: MemoryState,
     pub processes: ProcessList,
     pub disk: DiskState,
+    pub disk_io: DiskIoState,
+    pub block_tree: BlockTree,
+    pub cgroups: CgroupState,
     pub network: NetworkState,
+    pub connections: ConnectionState,
+    pub traffic: NetTrafficState,
+    pub containers: ContainerState,
+    pub systemd: SystemdState,
+    pub pressure: PressureState,
+    pub vmstat: VmStatState,
+    pub sensors: SensorState,
+    pub alerts: AlertState,
 }
 
 impl SystemState {
-    pub fn new() -> Self {
+    pub fn new(config: &Config, history_size: usize) -> Self {
+        let connections = ConnectionState::new(config.connections.show_unix);
+        let traffic = NetTrafficState::new(&connections);
+
         Self {
-            cpu: CpuState::new(),
-            memory: MemoryState::new(),
-            processes: ProcessList::new(),
-            disk: DiskState::new(),
-            network: NetworkState::new(),
+            cpu: CpuState::new(history_size),
+            memory: MemoryState::new(history_size),
+            processes: ProcessList::new(
+                config.process.smaps_accounting,
+                Duration::from_millis(config.process.smaps_interval),
+            ),
+            disk: DiskState::new(&config.disk),
+            disk_io: DiskIoState::new(history_size),
+            block_tree: BlockTree::new(),
+            cgroups: CgroupState::new(),
+            network: NetworkState::new(&config.network, history_size),
+            connections,
+            traffic,
+            containers: ContainerState::new(&config.containers),
+            systemd: SystemdState::new(&config.systemd),
+            pressure: PressureState::new(&config.pressure_cgroups, history_size),
+            vmstat: VmStatState::new(history_size),
+            sensors: SensorState::new(),
+            alerts: AlertState::new(&config.alerts, &config.units),
         }
     }
 
@@ -39,6 +105,19 @@ impl SystemState {
         self.memory.update();
         self.processes.update();
         self.disk.update();
+        self.disk_io.update();
+        self.block_tree.update();
+        self.cgroups.update();
         self.network.update();
+        self.connections.update();
+        self.traffic.update(&self.connections);
+        self.containers.update(&self.processes, &self.traffic);
+        self.systemd.update(&self.processes, &self.cgroups);
+        self.pressure.update();
+        self.vmstat.update();
+        self.sensors.update();
+
+        let evaluations = self.alerts.evaluate(self);
+        self.alerts.record(evaluations);
     }
 }
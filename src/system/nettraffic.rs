@@ -0,0 +1,320 @@
+use std::collections::HashMap;
+use std::io;
+use std::mem;
+use std::time::Instant;
+
+use crate::system::connections::ConnectionState;
+
+const NETLINK_SOCK_DIAG: libc::c_int = 4;
+const SOCK_DIAG_BY_FAMILY: u16 = 20;
+const NLMSG_ERROR: u16 = 2;
+const NLMSG_DONE: u16 = 3;
+const INET_DIAG_INFO: u16 = 2;
+
+const NLMSG_HEADER_LEN: usize = 16;
+const INET_DIAG_MSG_LEN: usize = 72;
+const INET_DIAG_INODE_OFFSET: usize = 68;
+const TCP_INFO_BYTES_ACKED: usize = 120;
+const TCP_INFO_BYTES_RECEIVED: usize = 128;
+
+#[derive(Debug, Clone, Copy, Default)]
+pub struct SocketCounters {
+    pub bytes_sent: u64,
+    pub bytes_received: u64,
+}
+
+#[repr(C)]
+struct InetDiagRequest {
+    nlmsg_len: u32,
+    nlmsg_type: u16,
+    nlmsg_flags: u16,
+    nlmsg_seq: u32,
+    nlmsg_pid: u32,
+    family: u8,
+    protocol: u8,
+    ext: u8,
+    pad: u8,
+    states: u32,
+    sockid: [u8; 48],
+}
+
+fn read_u16(buf: &[u8], offset: usize) -> u16 {
+    u16::from_ne_bytes([buf[offset], buf[offset + 1]])
+}
+
+fn read_u32(buf: &[u8], offset: usize) -> u32 {
+    u32::from_ne_bytes(buf[offset..offset + 4].try_into().unwrap())
+}
+
+fn read_u64(buf: &[u8], offset: usize) -> u64 {
+    u64::from_ne_bytes(buf[offset..offset + 8].try_into().unwrap())
+}
+
+fn align(len: usize) -> usize {
+    (len + 3) & !3
+}
+
+struct NetlinkSocket(libc::c_int);
+
+impl Drop for NetlinkSocket {
+    fn drop(&mut self) {
+        unsafe {
+            libc::close(self.0);
+        }
+    }
+}
+
+fn dump_family(
+    socket: &NetlinkSocket,
+    family: u8,
+    counters: &mut HashMap<u64, SocketCounters>,
+) -> io::Result<()> {
+    let request = InetDiagRequest {
+        nlmsg_len: mem::size_of::<InetDiagRequest>() as u32,
+        nlmsg_type: SOCK_DIAG_BY_FAMILY,
+        nlmsg_flags: (libc::NLM_F_REQUEST | libc::NLM_F_DUMP) as u16,
+        nlmsg_seq: family as u32,
+        nlmsg_pid: 0,
+        family,
+        protocol: libc::IPPROTO_TCP as u8,
+        ext: 1 << (INET_DIAG_INFO - 1),
+        pad: 0,
+        states: u32::MAX,
+        sockid: [0; 48],
+    };
+
+    let sent = unsafe {
+        libc::send(
+            socket.0,
+            &request as *const InetDiagRequest as *const libc::c_void,
+            mem::size_of::<InetDiagRequest>(),
+            0,
+        )
+    };
+    if sent < 0 {
+        return Err(io::Error::last_os_error());
+    }
+
+    let mut buf = vec![0u8; 32 * 1024];
+    loop {
+        let received = unsafe {
+            libc::recv(
+                socket.0,
+                buf.as_mut_ptr() as *mut libc::c_void,
+                buf.len(),
+                0,
+            )
+        };
+        if received < 0 {
+            return Err(io::Error::last_os_error());
+        }
+        let data = &buf[..received as usize];
+
+        let mut offset = 0;
+        while offset + NLMSG_HEADER_LEN <= data.len() {
+            let len = read_u32(data, offset) as usize;
+            let kind = read_u16(data, offset + 4);
+            if len < NLMSG_HEADER_LEN || offset + len > data.len() {
+                return Ok(());
+            }
+
+            match kind {
+                NLMSG_DONE => return Ok(()),
+                NLMSG_ERROR => {
+                    let errno = data
+                        .get(offset + NLMSG_HEADER_LEN..offset + NLMSG_HEADER_LEN + 4)
+                        .map(|bytes| i32::from_ne_bytes(bytes.try_into().unwrap()))
+                        .unwrap_or(0);
+                    return Err(io::Error::from_raw_os_error(-errno));
+                }
+                _ => parse_diag_message(&data[offset + NLMSG_HEADER_LEN..offset + len], counters),
+            }
+
+            offset += align(len);
+        }
+    }
+}
+
+fn parse_diag_message(message: &[u8], counters: &mut HashMap<u64, SocketCounters>) {
+    if message.len() < INET_DIAG_MSG_LEN {
+        return;
+    }
+    let inode = read_u32(message, INET_DIAG_INODE_OFFSET) as u64;
+    if inode == 0 {
+        return;
+    }
+
+    let mut offset = INET_DIAG_MSG_LEN;
+    while offset + 4 <= message.len() {
+        let len = read_u16(message, offset) as usize;
+        let kind = read_u16(message, offset + 2);
+        if len < 4 || offset + len > message.len() {
+            break;
+        }
+
+        if kind == INET_DIAG_INFO && len - 4 >= TCP_INFO_BYTES_RECEIVED + 8 {
+            let info = &message[offset + 4..offset + len];
+            counters.insert(
+                inode,
+                SocketCounters {
+                    bytes_sent: read_u64(info, TCP_INFO_BYTES_ACKED),
+                    bytes_received: read_u64(info, TCP_INFO_BYTES_RECEIVED),
+                },
+            );
+        }
+
+        offset += align(len);
+    }
+}
+
+pub fn read_socket_counters() -> io::Result<HashMap<u64, SocketCounters>> {
+    let fd = unsafe { libc::socket(libc::AF_NETLINK, libc::SOCK_DGRAM, NETLINK_SOCK_DIAG) };
+    if fd < 0 {
+        return Err(io::Error::last_os_error());
+    }
+    let socket = NetlinkSocket(fd);
+
+    let mut counters = HashMap::new();
+    dump_family(&socket, libc::AF_INET as u8, &mut counters)?;
+    dump_family(&socket, libc::AF_INET6 as u8, &mut counters)?;
+    Ok(counters)
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct ProcessTraffic {
+    pub pid: u32,
+    pub name: String,
+    pub rx_rate: f64,
+    pub tx_rate: f64,
+    pub connections: usize,
+}
+
+impl ProcessTraffic {
+    pub fn total_rate(&self) -> f64 {
+        self.rx_rate + self.tx_rate
+    }
+}
+
+pub struct NetTrafficState {
+    sockets: HashMap<u64, SocketCounters>,
+    processes: HashMap<u32, ProcessTraffic>,
+    attributed: bool,
+    last_error: Option<String>,
+    last_update: Option<Instant>,
+}
+
+impl NetTrafficState {
+    pub fn new(connections: &ConnectionState) -> Self {
+        let mut state = Self {
+            sockets: HashMap::new(),
+            processes: HashMap::new(),
+            attributed: false,
+            last_error: None,
+            last_update: None,
+        };
+        state.update(connections);
+        state
+    }
+
+    pub fn update(&mut self, connections: &ConnectionState) {
+        if connections.is_resolving_owners() && !connections.has_scanned_owners() {
+            return;
+        }
+        let now = Instant::now();
+        let mut processes: HashMap<u32, ProcessTraffic> = HashMap::new();
+
+        for (pid, count) in connections.count_by_pid() {
+            let entry = processes.entry(pid).or_default();
+            entry.pid = pid;
+            entry.connections = count;
+        }
+        for (pid, name) in connections.get_owners().values() {
+            if let Some(entry) = processes.get_mut(pid) {
+                if entry.name.is_empty() {
+                    entry.name = name.clone();
+                }
+            }
+        }
+
+        let counters = if connections.is_resolving_owners() {
+            Some(read_socket_counters())
+        } else {
+            None
+        };
+
+        match counters {
+            None => {
+                self.sockets.clear();
+                self.attributed = false;
+                self.last_error = None;
+            }
+            Some(Ok(sockets)) => {
+                if let (Some(last_update), true) = (self.last_update, self.attributed) {
+                    let time_delta = now.duration_since(last_update).as_secs_f64();
+                    if time_delta > 0.001 {
+                        for (inode, current) in &sockets {
+                            let previous = self.sockets.get(inode).copied().unwrap_or_default();
+                            let (pid, name) = match connections.get_owners().get(inode) {
+                                Some(owner) => owner,
+                                None => continue,
+                            };
+
+                            let entry = processes.entry(*pid).or_default();
+                            entry.pid = *pid;
+                            if entry.name.is_empty() {
+                                entry.name = name.clone();
+                            }
+                            entry.rx_rate += current
+                                .bytes_received
+                                .saturating_sub(previous.bytes_received)
+                                as f64
+                                / time_delta;
+                            entry.tx_rate += current.bytes_sent.saturating_sub(previous.bytes_sent)
+                                as f64
+                                / time_delta;
+                        }
+                    }
+                }
+
+                self.sockets = sockets;
+                self.attributed = true;
+                self.last_error = None;
+            }
+            Some(Err(err)) => {
+                self.sockets.clear();
+                self.attributed = false;
+                self.last_error = Some(err.to_string());
+            }
+        }
+
+        self.processes = processes;
+        self.last_update = Some(now);
+    }
+
+    pub fn is_attributed(&self) -> bool {
+        self.attributed
+    }
+
+    pub fn get_last_error(&self) -> Option<&str> {
+        self.last_error.as_deref()
+    }
+
+    pub fn get_process(&self, pid: u32) -> Option<&ProcessTraffic> {
+        self.processes.get(&pid)
+    }
+
+    pub fn get_top_talkers(&self, limit: usize) -> Vec<&ProcessTraffic> {
+        let mut talkers: Vec<&ProcessTraffic> = self.processes.values().collect();
+        if self.attributed {
+            talkers.sort_by(|a, b| {
+                b.total_rate()
+                    .total_cmp(&a.total_rate())
+                    .then(b.connections.cmp(&a.connections))
+            });
+        } else {
+            talkers.sort_by_key(|t| std::cmp::Reverse(t.connections));
+        }
+        talkers.truncate(limit);
+        talkers
+    }
+}
@@ -1,11 +1,223 @@
 use std::collections::{HashMap, VecDeque};
+use std::ffi::CStr;
+use std::fs;
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr};
+use std::path::Path;
 use std::time::Instant;
 use sysinfo::{NetworkExt, System, SystemExt};
 
-const HISTORY_SIZE: usize = 100;
+use crate::config::NetworkConfig;
+use crate::util::matches_any;
+
+const SYS_CLASS_NET: &str = "/sys/class/net";
+const ERROR_WINDOW: usize = 5;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum InterfaceSort {
+    Name,
+    RxRate,
+    TxRate,
+}
+
+impl InterfaceSort {
+    pub fn from_name(name: &str) -> Self {
+        match name.to_lowercase().as_str() {
+            "rx" => InterfaceSort::RxRate,
+            "tx" => InterfaceSort::TxRate,
+            _ => InterfaceSort::Name,
+        }
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            InterfaceSort::Name => "name",
+            InterfaceSort::RxRate => "rx",
+            InterfaceSort::TxRate => "tx",
+        }
+    }
+
+    pub fn next(&self) -> Self {
+        match self {
+            InterfaceSort::Name => InterfaceSort::RxRate,
+            InterfaceSort::RxRate => InterfaceSort::TxRate,
+            InterfaceSort::TxRate => InterfaceSort::Name,
+        }
+    }
+}
+
+pub struct InterfaceFilter {
+    include: Vec<String>,
+    exclude: Vec<String>,
+}
+
+impl InterfaceFilter {
+    pub fn from_config(config: &NetworkConfig) -> Self {
+        Self {
+            include: config.include.clone(),
+            exclude: config.exclude.clone(),
+        }
+    }
+
+    pub fn matches(&self, name: &str) -> bool {
+        (self.include.is_empty() || matches_any(&self.include, name))
+            && !matches_any(&self.exclude, name)
+    }
+}
+
+pub fn is_virtual_interface(name: &str) -> bool {
+    fs::read_link(Path::new(SYS_CLASS_NET).join(name))
+        .map(|target| target.to_string_lossy().contains("/virtual/"))
+        .unwrap_or(false)
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct LinkInfo {
+    pub mac: Option<String>,
+    pub operstate: String,
+    pub mtu: Option<u32>,
+    pub speed: Option<u32>,
+    pub duplex: Option<String>,
+}
+
+#[derive(Debug, Clone, Copy, Default)]
+pub struct ErrorCounters {
+    pub rx_errors: u64,
+    pub tx_errors: u64,
+    pub rx_dropped: u64,
+    pub tx_dropped: u64,
+    pub collisions: u64,
+}
+
+#[derive(Debug, Clone, Copy, Default)]
+pub struct ErrorRates {
+    pub rx_errors: f64,
+    pub tx_errors: f64,
+    pub rx_dropped: f64,
+    pub tx_dropped: f64,
+    pub collisions: f64,
+}
+
+impl ErrorRates {
+    pub fn errors(&self) -> f64 {
+        self.rx_errors + self.tx_errors + self.collisions
+    }
+
+    pub fn drops(&self) -> f64 {
+        self.rx_dropped + self.tx_dropped
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct InterfaceAddress {
+    pub address: IpAddr,
+    pub prefix: u32,
+}
+
+impl std::fmt::Display for InterfaceAddress {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}/{}", self.address, self.prefix)
+    }
+}
+
+fn read_sys_value(path: &Path) -> Option<String> {
+    fs::read_to_string(path)
+        .ok()
+        .map(|content| content.trim().to_string())
+        .filter(|content| !content.is_empty())
+}
+
+pub fn read_link_info(name: &str) -> LinkInfo {
+    let path = Path::new(SYS_CLASS_NET).join(name);
+
+    LinkInfo {
+        mac: read_sys_value(&path.join("address")).filter(|mac| mac != "00:00:00:00:00:00"),
+        operstate: read_sys_value(&path.join("operstate")).unwrap_or_else(|| "unknown".into()),
+        mtu: read_sys_value(&path.join("mtu")).and_then(|mtu| mtu.parse().ok()),
+        speed: read_sys_value(&path.join("speed"))
+            .and_then(|speed| speed.parse::<i64>().ok())
+            .filter(|speed| *speed > 0)
+            .map(|speed| speed as u32),
+        duplex: read_sys_value(&path.join("duplex")).filter(|duplex| duplex != "unknown"),
+    }
+}
+
+pub fn read_error_counters(name: &str) -> ErrorCounters {
+    let path = Path::new(SYS_CLASS_NET).join(name).join("statistics");
+    let counter = |file: &str| {
+        read_sys_value(&path.join(file))
+            .and_then(|value| value.parse().ok())
+            .unwrap_or(0)
+    };
+
+    ErrorCounters {
+        rx_errors: counter("rx_errors"),
+        tx_errors: counter("tx_errors"),
+        rx_dropped: counter("rx_dropped"),
+        tx_dropped: counter("tx_dropped"),
+        collisions: counter("collisions"),
+    }
+}
+
+fn sockaddr_to_ip(addr: *const libc::sockaddr) -> Option<IpAddr> {
+    if addr.is_null() {
+        return None;
+    }
+
+    unsafe {
+        match (*addr).sa_family as libc::c_int {
+            libc::AF_INET => {
+                let addr = &*(addr as *const libc::sockaddr_in);
+                Some(IpAddr::V4(Ipv4Addr::from(u32::from_be(
+                    addr.sin_addr.s_addr,
+                ))))
+            }
+            libc::AF_INET6 => {
+                let addr = &*(addr as *const libc::sockaddr_in6);
+                Some(IpAddr::V6(Ipv6Addr::from(addr.sin6_addr.s6_addr)))
+            }
+            _ => None,
+        }
+    }
+}
+
+fn prefix_length(netmask: Option<IpAddr>) -> u32 {
+    match netmask {
+        Some(IpAddr::V4(mask)) => u32::from(mask).count_ones(),
+        Some(IpAddr::V6(mask)) => u128::from(mask).count_ones(),
+        None => 0,
+    }
+}
+
+pub fn read_addresses() -> HashMap<String, Vec<InterfaceAddress>> {
+    let mut addresses: HashMap<String, Vec<InterfaceAddress>> = HashMap::new();
+
+    let mut ifap: *mut libc::ifaddrs = std::ptr::null_mut();
+    if unsafe { libc::getifaddrs(&mut ifap) } != 0 {
+        return addresses;
+    }
+
+    let mut current = ifap;
+    while !current.is_null() {
+        let entry = unsafe { &*current };
+        if let Some(address) = sockaddr_to_ip(entry.ifa_addr) {
+            let name = unsafe { CStr::from_ptr(entry.ifa_name) }
+                .to_string_lossy()
+                .to_string();
+            addresses.entry(name).or_default().push(InterfaceAddress {
+                address,
+                prefix: prefix_length(sockaddr_to_ip(entry.ifa_netmask)),
+            });
+        }
+        current = entry.ifa_next;
+    }
+
+    unsafe { libc::freeifaddrs(ifap) };
+    addresses
+}
 
 pub struct NetworkInterface {
     name: String,
+    is_virtual: bool,
     received_bytes: u64,
     transmitted_bytes: u64,
     received_packets: u64,
@@ -18,12 +230,20 @@ pub struct NetworkInterface {
     last_update: Instant,
     receive_rate_history: VecDeque<f64>,
     transmit_rate_history: VecDeque<f64>,
+
+    link: LinkInfo,
+    addresses: Vec<InterfaceAddress>,
+    error_counters: ErrorCounters,
+    error_rates: ErrorRates,
+    error_rate_history: VecDeque<f64>,
+    history_size: usize,
 }
 
 impl NetworkInterface {
-    fn new(name: &str, network: &sysinfo::NetworkData) -> Self {
+    fn new(name: &str, network: &sysinfo::NetworkData, history_size: usize) -> Self {
         Self {
             name: name.to_string(),
+            is_virtual: is_virtual_interface(name),
             received_bytes: network.received(),
             transmitted_bytes: network.transmitted(),
             received_packets: network.packets_received(),
@@ -33,8 +253,14 @@ impl NetworkInterface {
             receive_rate: 0.0,
             transmit_rate: 0.0,
             last_update: Instant::now(),
-            receive_rate_history: VecDeque::with_capacity(HISTORY_SIZE),
-            transmit_rate_history: VecDeque::with_capacity(HISTORY_SIZE),
+            receive_rate_history: VecDeque::with_capacity(history_size),
+            transmit_rate_history: VecDeque::with_capacity(history_size),
+            link: read_link_info(name),
+            addresses: Vec::new(),
+            error_counters: read_error_counters(name),
+            error_rates: ErrorRates::default(),
+            error_rate_history: VecDeque::with_capacity(history_size),
+            history_size,
         }
     }
 
@@ -60,14 +286,36 @@ impl NetworkInterface {
             self.transmit_rate = (tx_diff as f64 / time_delta).min(f64::MAX / 2.0);
         }
 
+        self.link = read_link_info(&self.name);
+        let counters = read_error_counters(&self.name);
+        if time_delta > 0.001 {
+            let prev = self.error_counters;
+            let rate =
+                |current: u64, previous: u64| current.saturating_sub(previous) as f64 / time_delta;
+            self.error_rates = ErrorRates {
+                rx_errors: rate(counters.rx_errors, prev.rx_errors),
+                tx_errors: rate(counters.tx_errors, prev.tx_errors),
+                rx_dropped: rate(counters.rx_dropped, prev.rx_dropped),
+                tx_dropped: rate(counters.tx_dropped, prev.tx_dropped),
+                collisions: rate(counters.collisions, prev.collisions),
+            };
+        }
+        self.error_counters = counters;
+
+        self.error_rate_history
+            .push_back(self.error_rates.errors() + self.error_rates.drops());
+        if self.error_rate_history.len() > self.history_size {
+            self.error_rate_history.pop_front();
+        }
+
         self.receive_rate_history.push_back(self.receive_rate);
         self.transmit_rate_history.push_back(self.transmit_rate);
 
-        if self.receive_rate_history.len() > HISTORY_SIZE {
+        if self.receive_rate_history.len() > self.history_size {
             self.receive_rate_history.pop_front();
         }
 
-        if self.transmit_rate_history.len() > HISTORY_SIZE {
+        if self.transmit_rate_history.len() > self.history_size {
             self.transmit_rate_history.pop_front();
         }
 
@@ -78,6 +326,10 @@ impl NetworkInterface {
         &self.name
     }
 
+    pub fn is_virtual(&self) -> bool {
+        self.is_virtual
+    }
+
     pub fn get_received_bytes(&self) -> u64 {
         self.received_bytes
     }
@@ -109,25 +361,71 @@ impl NetworkInterface {
     pub fn get_transmit_rate_history(&self) -> &VecDeque<f64> {
         &self.transmit_rate_history
     }
+
+    pub fn get_link(&self) -> &LinkInfo {
+        &self.link
+    }
+
+    pub fn get_addresses(&self) -> &[InterfaceAddress] {
+        &self.addresses
+    }
+
+    pub fn get_error_counters(&self) -> &ErrorCounters {
+        &self.error_counters
+    }
+
+    pub fn get_error_rates(&self) -> &ErrorRates {
+        &self.error_rates
+    }
+
+    pub fn get_error_rate_history(&self) -> &VecDeque<f64> {
+        &self.error_rate_history
+    }
+
+    pub fn is_up(&self) -> bool {
+        self.link.operstate == "up" || self.link.operstate == "unknown"
+    }
+
+    pub fn has_climbing_errors(&self) -> bool {
+        self.error_rate_history
+            .iter()
+            .rev()
+            .take(ERROR_WINDOW)
+            .any(|rate| *rate > 0.0)
+    }
 }
 
 pub struct NetworkState {
     system: System,
     interfaces: HashMap<String, NetworkInterface>,
+    filter: InterfaceFilter,
+    history_size: usize,
 }
 
 impl NetworkState {
-    pub fn new() -> Self {
+    pub fn new(config: &NetworkConfig, history_size: usize) -> Self {
         let mut system = System::new_all();
         system.refresh_networks_list();
 
         let interfaces = system
             .networks()
             .into_iter()
-            .map(|(name, data)| (name.clone(), NetworkInterface::new(name, data)))
+            .map(|(name, data)| {
+                (
+                    name.clone(),
+                    NetworkInterface::new(name, data, history_size),
+                )
+            })
             .collect();
 
-        Self { system, interfaces }
+        let mut state = Self {
+            system,
+            interfaces,
+            filter: InterfaceFilter::from_config(config),
+            history_size,
+        };
+        state.update_addresses();
+        state
     }
 
     pub fn update(&mut self) {
@@ -137,14 +435,45 @@ impl NetworkState {
             if let Some(interface) = self.interfaces.get_mut(name) {
                 interface.update(data);
             } else {
-                self.interfaces
-                    .insert(name.clone(), NetworkInterface::new(name, data));
+                self.interfaces.insert(
+                    name.clone(),
+                    NetworkInterface::new(name, data, self.history_size),
+                );
             }
         }
+
+        self.update_addresses();
+    }
+
+    fn update_addresses(&mut self) {
+        let mut addresses = read_addresses();
+        for (name, interface) in self.interfaces.iter_mut() {
+            interface.addresses = addresses.remove(name).unwrap_or_default();
+        }
     }
 
     pub fn get_interfaces(&self) -> Vec<&NetworkInterface> {
-        self.interfaces.values().collect()
+        let mut interfaces: Vec<&NetworkInterface> = self
+            .interfaces
+            .values()
+            .filter(|i| self.filter.matches(&i.name))
+            .collect();
+        interfaces.sort_by(|a, b| a.name.cmp(&b.name));
+        interfaces
+    }
+
+    pub fn get_sorted(&self, sort: InterfaceSort) -> Vec<&NetworkInterface> {
+        let mut interfaces = self.get_interfaces();
+        match sort {
+            InterfaceSort::Name => {}
+            InterfaceSort::RxRate => {
+                interfaces.sort_by(|a, b| b.receive_rate.total_cmp(&a.receive_rate))
+            }
+            InterfaceSort::TxRate => {
+                interfaces.sort_by(|a, b| b.transmit_rate.total_cmp(&a.transmit_rate))
+            }
+        }
+        interfaces
     }
 
     pub fn get_interface(&self, name: &str) -> Option<&NetworkInterface> {
@@ -152,18 +481,27 @@ impl NetworkState {
     }
 
     pub fn get_total_received(&self) -> u64 {
-        self.interfaces.values().map(|i| i.received_bytes).sum()
+        self.get_interfaces().iter().map(|i| i.received_bytes).sum()
     }
 
     pub fn get_total_transmitted(&self) -> u64 {
-        self.interfaces.values().map(|i| i.transmitted_bytes).sum()
+        self.get_interfaces()
+            .iter()
+            .map(|i| i.transmitted_bytes)
+            .sum()
     }
 
     pub fn get_total_receive_rate(&self) -> f64 {
-        self.interfaces.values().map(|i| i.receive_rate).sum()
+        self.get_interfaces().iter().map(|i| i.receive_rate).sum()
     }
 
     pub fn get_total_transmit_rate(&self) -> f64 {
-        self.interfaces.values().map(|i| i.transmit_rate).sum()
+        self.get_interfaces().iter().map(|i| i.transmit_rate).sum()
+    }
+
+    pub fn has_climbing_errors(&self) -> bool {
+        self.get_interfaces()
+            .iter()
+            .any(|i| i.has_climbing_errors())
     }
 }
@@ -0,0 +1,240 @@
+use std::collections::VecDeque;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::Instant;
+
+const PROC_PRESSURE: &str = "/proc/pressure";
+const CGROUP_ROOT: &str = "/sys/fs/cgroup";
+
+#[derive(Debug, Clone, Copy, Default)]
+pub struct PressureLine {
+    pub avg10: f64,
+    pub avg60: f64,
+    pub avg300: f64,
+    pub total: u64,
+}
+
+#[derive(Debug, Clone, Copy, Default)]
+pub struct PressureStats {
+    pub some: PressureLine,
+    pub full: Option<PressureLine>,
+}
+
+pub fn parse_pressure(content: &str) -> Option<PressureStats> {
+    let mut some = None;
+    let mut full = None;
+
+    for line in content.lines() {
+        let mut fields = line.split_whitespace();
+        let kind = fields.next()?;
+
+        let mut parsed = PressureLine::default();
+        for field in fields {
+            let (key, value) = match field.split_once('=') {
+                Some(pair) => pair,
+                None => continue,
+            };
+            match key {
+                "avg10" => parsed.avg10 = value.parse().unwrap_or(0.0),
+                "avg60" => parsed.avg60 = value.parse().unwrap_or(0.0),
+                "avg300" => parsed.avg300 = value.parse().unwrap_or(0.0),
+                "total" => parsed.total = value.parse().unwrap_or(0),
+                _ => {}
+            }
+        }
+
+        match kind {
+            "some" => some = Some(parsed),
+            "full" => full = Some(parsed),
+            _ => {}
+        }
+    }
+
+    some.map(|some| PressureStats { some, full })
+}
+
+pub fn read_pressure(path: &Path) -> Option<PressureStats> {
+    fs::read_to_string(path)
+        .ok()
+        .and_then(|content| parse_pressure(&content))
+}
+
+pub struct PressureResource {
+    name: String,
+    path: PathBuf,
+    stats: Option<PressureStats>,
+    some_stall_rate: f64,
+    full_stall_rate: f64,
+    last_update: Instant,
+    history: VecDeque<f64>,
+    history_size: usize,
+}
+
+impl PressureResource {
+    fn new(name: &str, path: PathBuf, history_size: usize) -> Self {
+        Self {
+            name: name.to_string(),
+            stats: read_pressure(&path),
+            path,
+            some_stall_rate: 0.0,
+            full_stall_rate: 0.0,
+            last_update: Instant::now(),
+            history: VecDeque::with_capacity(history_size),
+            history_size,
+        }
+    }
+
+    fn update(&mut self) {
+        let now = Instant::now();
+        let elapsed_us = now.duration_since(self.last_update).as_secs_f64() * 1_000_000.0;
+        let current = read_pressure(&self.path);
+
+        if let (Some(prev), Some(cur)) = (self.stats, current) {
+            if elapsed_us > 1000.0 {
+                let some_delta = cur.some.total.saturating_sub(prev.some.total);
+                self.some_stall_rate = (some_delta as f64 / elapsed_us * 100.0).min(100.0);
+
+                self.full_stall_rate = match (prev.full, cur.full) {
+                    (Some(prev_full), Some(cur_full)) => {
+                        let full_delta = cur_full.total.saturating_sub(prev_full.total);
+                        (full_delta as f64 / elapsed_us * 100.0).min(100.0)
+                    }
+                    _ => 0.0,
+                };
+            }
+        }
+
+        self.stats = current;
+        self.last_update = now;
+
+        self.history.push_back(self.get_some_avg10());
+        if self.history.len() > self.history_size {
+            self.history.pop_front();
+        }
+    }
+
+    pub fn get_name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn is_available(&self) -> bool {
+        self.stats.is_some()
+    }
+
+    pub fn get_some(&self) -> Option<PressureLine> {
+        self.stats.map(|stats| stats.some)
+    }
+
+    pub fn get_full(&self) -> Option<PressureLine> {
+        self.stats.and_then(|stats| stats.full)
+    }
+
+    pub fn get_some_avg10(&self) -> f64 {
+        self.get_some().map(|line| line.avg10).unwrap_or(0.0)
+    }
+
+    pub fn get_some_stall_rate(&self) -> f64 {
+        self.some_stall_rate
+    }
+
+    pub fn get_full_stall_rate(&self) -> f64 {
+        self.full_stall_rate
+    }
+
+    pub fn get_history(&self) -> &VecDeque<f64> {
+        &self.history
+    }
+}
+
+pub struct PressureGroup {
+    name: String,
+    cpu: PressureResource,
+    memory: PressureResource,
+    io: PressureResource,
+}
+
+impl PressureGroup {
+    fn new(name: &str, dir: &Path, history_size: usize) -> Self {
+        Self {
+            name: name.to_string(),
+            cpu: PressureResource::new("cpu", dir.join("cpu.pressure"), history_size),
+            memory: PressureResource::new("memory", dir.join("memory.pressure"), history_size),
+            io: PressureResource::new("io", dir.join("io.pressure"), history_size),
+        }
+    }
+
+    fn update(&mut self) {
+        self.cpu.update();
+        self.memory.update();
+        self.io.update();
+    }
+
+    pub fn get_name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn get_resources(&self) -> [&PressureResource; 3] {
+        [&self.cpu, &self.memory, &self.io]
+    }
+}
+
+pub struct PressureState {
+    cpu: PressureResource,
+    memory: PressureResource,
+    io: PressureResource,
+    cgroups: Vec<PressureGroup>,
+}
+
+impl PressureState {
+    pub fn new(cgroups: &[String], history_size: usize) -> Self {
+        let root = Path::new(PROC_PRESSURE);
+        let cgroup_root = Path::new(CGROUP_ROOT);
+
+        Self {
+            cpu: PressureResource::new("cpu", root.join("cpu"), history_size),
+            memory: PressureResource::new("memory", root.join("memory"), history_size),
+            io: PressureResource::new("io", root.join("io"), history_size),
+            cgroups: cgroups
+                .iter()
+                .map(|name| {
+                    let relative = name.trim_start_matches('/');
+                    PressureGroup::new(name, &cgroup_root.join(relative), history_size)
+                })
+                .collect(),
+        }
+    }
+
+    pub fn update(&mut self) {
+        self.cpu.update();
+        self.memory.update();
+        self.io.update();
+
+        for group in &mut self.cgroups {
+            group.update();
+        }
+    }
+
+    pub fn is_available(&self) -> bool {
+        self.cpu.is_available() || self.memory.is_available() || self.io.is_available()
+    }
+
+    pub fn get_cpu(&self) -> &PressureResource {
+        &self.cpu
+    }
+
+    pub fn get_memory(&self) -> &PressureResource {
+        &self.memory
+    }
+
+    pub fn get_io(&self) -> &PressureResource {
+        &self.io
+    }
+
+    pub fn get_resources(&self) -> [&PressureResource; 3] {
+        [&self.cpu, &self.memory, &self.io]
+    }
+
+    pub fn get_cgroups(&self) -> &[PressureGroup] {
+        &self.cgroups
+    }
+}
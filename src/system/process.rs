@@ -1,5 +1,114 @@
 use std::collections::HashMap;
-use sysinfo::{Pid, ProcessExt, System, SystemExt};
+use std::time::{Duration, Instant};
+use sysinfo::{Pid, PidExt, ProcessExt, System, SystemExt};
+
+use crate::system::cgroup::{is_in_cgroup, read_process_cgroup};
+use crate::system::smaps::{read_smaps_rollup, SmapsRollup};
+use crate::util::glob::glob_match;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MemoryColumn {
+    Rss,
+    Uss,
+    Pss,
+    Shared,
+    Swap,
+}
+
+impl MemoryColumn {
+    pub const ALL: [MemoryColumn; 5] = [
+        MemoryColumn::Rss,
+        MemoryColumn::Uss,
+        MemoryColumn::Pss,
+        MemoryColumn::Shared,
+        MemoryColumn::Swap,
+    ];
+
+    pub fn from_name(name: &str) -> Self {
+        match name.to_lowercase().as_str() {
+            "uss" => MemoryColumn::Uss,
+            "pss" => MemoryColumn::Pss,
+            "shared" => MemoryColumn::Shared,
+            "swap" => MemoryColumn::Swap,
+            _ => MemoryColumn::Rss,
+        }
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            MemoryColumn::Rss => "rss",
+            MemoryColumn::Uss => "uss",
+            MemoryColumn::Pss => "pss",
+            MemoryColumn::Shared => "shared",
+            MemoryColumn::Swap => "swap",
+        }
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            MemoryColumn::Rss => "RSS",
+            MemoryColumn::Uss => "USS",
+            MemoryColumn::Pss => "PSS",
+            MemoryColumn::Shared => "SHR",
+            MemoryColumn::Swap => "SWAP",
+        }
+    }
+
+    pub fn needs_smaps(&self) -> bool {
+        *self != MemoryColumn::Rss
+    }
+
+    pub fn next(&self, smaps_enabled: bool) -> Self {
+        if !smaps_enabled {
+            return MemoryColumn::Rss;
+        }
+        match self {
+            MemoryColumn::Rss => MemoryColumn::Uss,
+            MemoryColumn::Uss => MemoryColumn::Pss,
+            MemoryColumn::Pss => MemoryColumn::Shared,
+            MemoryColumn::Shared => MemoryColumn::Swap,
+            MemoryColumn::Swap => MemoryColumn::Rss,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SortKey {
+    Cpu,
+    Memory(MemoryColumn),
+    DiskRead,
+    DiskWrite,
+}
+
+impl SortKey {
+    pub fn from_name(name: &str, column: MemoryColumn) -> Self {
+        match name.to_lowercase().as_str() {
+            "cpu" => SortKey::Cpu,
+            "memory" | "mem" => SortKey::Memory(column),
+            "read" => SortKey::DiskRead,
+            "write" => SortKey::DiskWrite,
+            other => SortKey::Memory(MemoryColumn::from_name(other)),
+        }
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            SortKey::Cpu => "cpu",
+            SortKey::Memory(_) => "memory",
+            SortKey::DiskRead => "read",
+            SortKey::DiskWrite => "write",
+        }
+    }
+
+    pub fn next(&self, column: MemoryColumn) -> Self {
+        match self {
+            SortKey::Cpu => SortKey::Memory(column),
+            SortKey::Memory(_) => SortKey::DiskRead,
+            SortKey::DiskRead => SortKey::DiskWrite,
+            SortKey::DiskWrite => SortKey::Cpu,
+        }
+    }
+}
 
 #[derive(Debug, Clone)]
 pub struct Process {
@@ -10,10 +119,17 @@ pub struct Process {
     memory_usage: u64,
     status: String,
     user_id: Option<String>,
+    smaps: Option<SmapsRollup>,
+    total_read_bytes: u64,
+    total_written_bytes: u64,
+    read_rate: f64,
+    write_rate: f64,
+    cgroup: Option<String>,
 }
 
 impl Process {
-    fn new(pid: Pid, process: &sysinfo::Process) -> Self {
+    fn new(pid: Pid, process: &sysinfo::Process, smaps: Option<SmapsRollup>) -> Self {
+        let disk_usage = process.disk_usage();
         Self {
             pid,
             name: process.name().to_string(),
@@ -22,6 +138,26 @@ impl Process {
             memory_usage: process.memory(),
             status: format!("{:?}", process.status()),
             user_id: process.user_id().map(|id| id.to_string()),
+            smaps,
+            total_read_bytes: disk_usage.total_read_bytes,
+            total_written_bytes: disk_usage.total_written_bytes,
+            read_rate: 0.0,
+            write_rate: 0.0,
+            cgroup: read_process_cgroup(pid.as_u32()),
+        }
+    }
+
+    fn update_io_rates(&mut self, previous: &Process, time_delta: f64) {
+        if time_delta > 0.001 {
+            let read_diff = self
+                .total_read_bytes
+                .saturating_sub(previous.total_read_bytes);
+            let write_diff = self
+                .total_written_bytes
+                .saturating_sub(previous.total_written_bytes);
+
+            self.read_rate = read_diff as f64 / time_delta;
+            self.write_rate = write_diff as f64 / time_delta;
         }
     }
 
@@ -41,6 +177,36 @@ impl Process {
         self.memory_usage
     }
 
+    pub fn get_smaps(&self) -> Option<&SmapsRollup> {
+        self.smaps.as_ref()
+    }
+
+    pub fn get_memory(&self, column: MemoryColumn) -> Option<u64> {
+        match column {
+            MemoryColumn::Rss => Some(self.memory_usage),
+            MemoryColumn::Uss => self.smaps.map(|s| s.uss),
+            MemoryColumn::Pss => self.smaps.map(|s| s.pss),
+            MemoryColumn::Shared => self.smaps.map(|s| s.shared),
+            MemoryColumn::Swap => self.smaps.map(|s| s.swap),
+        }
+    }
+
+    pub fn get_total_read_bytes(&self) -> u64 {
+        self.total_read_bytes
+    }
+
+    pub fn get_total_written_bytes(&self) -> u64 {
+        self.total_written_bytes
+    }
+
+    pub fn get_read_rate(&self) -> f64 {
+        self.read_rate
+    }
+
+    pub fn get_write_rate(&self) -> f64 {
+        self.write_rate
+    }
+
     pub fn get_status(&self) -> &str {
         &self.status
     }
@@ -52,37 +218,197 @@ impl Process {
     pub fn get_command(&self) -> &[String] {
         &self.cmd
     }
+
+    pub fn get_cgroup(&self) -> Option<&str> {
+        self.cgroup.as_deref()
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct ProcessFilter {
+    cgroup: Option<String>,
+    user: Option<(String, String)>,
+    name: Option<String>,
+}
+
+fn resolve_uid(user: &str) -> String {
+    if user.parse::<u32>().is_ok() {
+        return user.to_string();
+    }
+    let name = match std::ffi::CString::new(user) {
+        Ok(name) => name,
+        Err(_) => return user.to_string(),
+    };
+    let mut passwd: libc::passwd = unsafe { std::mem::zeroed() };
+    let mut buf = vec![0 as libc::c_char; 4096];
+    let mut result: *mut libc::passwd = std::ptr::null_mut();
+    let rc = unsafe {
+        libc::getpwnam_r(
+            name.as_ptr(),
+            &mut passwd,
+            buf.as_mut_ptr(),
+            buf.len(),
+            &mut result,
+        )
+    };
+    if rc == 0 && !result.is_null() {
+        passwd.pw_uid.to_string()
+    } else {
+        user.to_string()
+    }
+}
+
+impl ProcessFilter {
+    pub fn parse(filters: &[String]) -> Self {
+        let mut filter = Self::default();
+        for token in filters {
+            if let Some(path) = token.strip_prefix("cgroup:") {
+                filter.cgroup = Some(path.to_string());
+            } else if let Some(user) = token.strip_prefix("user:") {
+                filter.user = Some((user.to_string(), resolve_uid(user)));
+            } else if let Some(name) = token.strip_prefix("name:") {
+                filter.name = Some(name.to_string());
+            } else if !token.is_empty() {
+                filter.name = Some(token.to_string());
+            }
+        }
+        filter
+    }
+
+    pub fn get_cgroup(&self) -> Option<&str> {
+        self.cgroup.as_deref()
+    }
+
+    pub fn describe(&self) -> Vec<String> {
+        let mut parts = Vec::new();
+        if let Some(cgroup) = &self.cgroup {
+            parts.push(format!("cgroup: {}", cgroup));
+        }
+        if let Some((user, _)) = &self.user {
+            parts.push(format!("user: {}", user));
+        }
+        if let Some(name) = &self.name {
+            parts.push(format!("name: {}", name));
+        }
+        parts
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.cgroup.is_none() && self.user.is_none() && self.name.is_none()
+    }
+
+    pub fn matches(&self, process: &Process) -> bool {
+        if let Some(cgroup) = &self.cgroup {
+            let inside = process
+                .get_cgroup()
+                .map(|path| is_in_cgroup(path, cgroup))
+                .unwrap_or(false);
+            if !inside {
+                return false;
+            }
+        }
+        if let Some((_, uid)) = &self.user {
+            if process.get_user_id() != Some(uid.as_str()) {
+                return false;
+            }
+        }
+        if let Some(pattern) = &self.name {
+            let name = process.get_name().to_lowercase();
+            let pattern = pattern.to_lowercase();
+            let matched = if pattern.contains(['*', '?']) {
+                glob_match(&pattern, &name)
+            } else {
+                name.contains(&pattern)
+            };
+            if !matched {
+                return false;
+            }
+        }
+        true
+    }
 }
 
 #[derive(Debug)]
 pub struct ProcessList {
     system: System,
     processes: HashMap<Pid, Process>,
+    smaps_enabled: bool,
+    smaps_interval: Duration,
+    smaps_last_refresh: Option<Instant>,
+    smaps: HashMap<Pid, SmapsRollup>,
+    last_update: Instant,
 }
 
 impl ProcessList {
-    pub fn new() -> Self {
+    pub fn new(smaps_enabled: bool, smaps_interval: Duration) -> Self {
         let mut system = System::new_all();
         system.refresh_processes();
 
-        let processes = system
+        let mut list = Self {
+            system,
+            processes: HashMap::new(),
+            smaps_enabled,
+            smaps_interval,
+            smaps_last_refresh: None,
+            smaps: HashMap::new(),
+            last_update: Instant::now(),
+        };
+        list.collect();
+        list
+    }
+
+    pub fn update(&mut self) {
+        self.system.refresh_processes();
+        self.collect();
+    }
+
+    fn collect(&mut self) {
+        if self.smaps_enabled {
+            self.refresh_smaps();
+        }
+
+        let now = Instant::now();
+        let time_delta = now.duration_since(self.last_update).as_secs_f64();
+
+        let smaps = &self.smaps;
+        let previous = &self.processes;
+        let processes = self
+            .system
             .processes()
             .iter()
-            .map(|(pid, process)| (*pid, Process::new(*pid, process)))
+            .map(|(pid, process)| {
+                let mut current = Process::new(*pid, process, smaps.get(pid).copied());
+                if let Some(prev) = previous.get(pid) {
+                    current.update_io_rates(prev, time_delta);
+                }
+                (*pid, current)
+            })
             .collect();
 
-        Self { system, processes }
+        self.processes = processes;
+        self.last_update = now;
     }
 
-    pub fn update(&mut self) {
-        self.system.refresh_processes();
+    fn refresh_smaps(&mut self) {
+        let due = self
+            .smaps_last_refresh
+            .map(|at| at.elapsed() >= self.smaps_interval)
+            .unwrap_or(true);
+        if !due {
+            return;
+        }
 
-        self.processes = self
+        self.smaps = self
             .system
             .processes()
-            .iter()
-            .map(|(pid, process)| (*pid, Process::new(*pid, process)))
+            .keys()
+            .filter_map(|pid| read_smaps_rollup(pid.as_u32()).map(|rollup| (*pid, rollup)))
             .collect();
+        self.smaps_last_refresh = Some(Instant::now());
+    }
+
+    pub fn is_smaps_enabled(&self) -> bool {
+        self.smaps_enabled
     }
 
     pub fn get_process(&self, pid: &Pid) -> Option<&Process> {
@@ -93,6 +419,39 @@ impl ProcessList {
         self.processes.values().collect()
     }
 
+    pub fn get_sorted(
+        &self,
+        key: SortKey,
+        filter: &ProcessFilter,
+        limit: Option<usize>,
+    ) -> Vec<&Process> {
+        let sorted = match key {
+            SortKey::Cpu => self.get_sorted_by_cpu(None),
+            SortKey::Memory(column) => self.get_sorted_by_memory_column(column, None),
+            SortKey::DiskRead => self.get_sorted_by_rate(None, Process::get_read_rate),
+            SortKey::DiskWrite => self.get_sorted_by_rate(None, Process::get_write_rate),
+        };
+
+        let mut processes: Vec<&Process> =
+            sorted.into_iter().filter(|p| filter.matches(p)).collect();
+        if let Some(n) = limit {
+            processes.truncate(n);
+        }
+
+        processes
+    }
+
+    fn get_sorted_by_rate(&self, limit: Option<usize>, rate: fn(&Process) -> f64) -> Vec<&Process> {
+        let mut processes = self.get_processes();
+        processes.sort_by(|a, b| rate(b).total_cmp(&rate(a)));
+
+        if let Some(n) = limit {
+            processes.truncate(n);
+        }
+
+        processes
+    }
+
     pub fn get_sorted_by_cpu(&self, limit: Option<usize>) -> Vec<&Process> {
         let mut processes = self.get_processes();
         processes.sort_by(|a, b| b.cpu_usage.partial_cmp(&a.cpu_usage).unwrap());
@@ -105,8 +464,16 @@ impl ProcessList {
     }
 
     pub fn get_sorted_by_memory(&self, limit: Option<usize>) -> Vec<&Process> {
+        self.get_sorted_by_memory_column(MemoryColumn::Rss, limit)
+    }
+
+    pub fn get_sorted_by_memory_column(
+        &self,
+        column: MemoryColumn,
+        limit: Option<usize>,
+    ) -> Vec<&Process> {
         let mut processes = self.get_processes();
-        processes.sort_by(|a, b| b.memory_usage.cmp(&a.memory_usage));
+        processes.sort_by_key(|p| std::cmp::Reverse(p.get_memory(column).unwrap_or(0)));
 
         if let Some(n) = limit {
             processes.truncate(n);
@@ -0,0 +1,45 @@
+use sysinfo::{ComponentExt, System, SystemExt};
+
+pub struct Sensor {
+    pub label: String,
+    pub temperature: f32,
+    pub max: f32,
+    pub critical: Option<f32>,
+}
+
+pub struct SensorState {
+    system: System,
+    sensors: Vec<Sensor>,
+}
+
+impl SensorState {
+    pub fn new() -> Self {
+        let mut system = System::new();
+        system.refresh_components_list();
+
+        Self {
+            system,
+            sensors: Vec::new(),
+        }
+    }
+
+    pub fn update(&mut self) {
+        self.system.refresh_components();
+        self.sensors = self
+            .system
+            .components()
+            .iter()
+            .map(|component| Sensor {
+                label: component.label().to_string(),
+                temperature: component.temperature(),
+                max: component.max(),
+                critical: component.critical(),
+            })
+            .collect();
+        self.sensors.sort_by(|a, b| a.label.cmp(&b.label));
+    }
+
+    pub fn get_sensors(&self) -> &[Sensor] {
+        &self.sensors
+    }
+}
@@ -0,0 +1,43 @@
+use std::fs;
+
+#[derive(Debug, Clone, Copy, Default)]
+pub struct SmapsRollup {
+    pub rss: u64,
+    pub pss: u64,
+    pub uss: u64,
+    pub shared: u64,
+    pub swap: u64,
+}
+
+pub fn parse_smaps_rollup(content: &str) -> SmapsRollup {
+    let mut rollup = SmapsRollup::default();
+
+    for line in content.lines() {
+        let (key, rest) = match line.split_once(':') {
+            Some(pair) => pair,
+            None => continue,
+        };
+        let kib: u64 = match rest.split_whitespace().next().and_then(|v| v.parse().ok()) {
+            Some(value) => value,
+            None => continue,
+        };
+        let bytes = kib * 1024;
+
+        match key {
+            "Rss" => rollup.rss = bytes,
+            "Pss" => rollup.pss = bytes,
+            "Shared_Clean" | "Shared_Dirty" => rollup.shared += bytes,
+            "Private_Clean" | "Private_Dirty" => rollup.uss += bytes,
+            "Swap" => rollup.swap = bytes,
+            _ => {}
+        }
+    }
+
+    rollup
+}
+
+pub fn read_smaps_rollup(pid: u32) -> Option<SmapsRollup> {
+    fs::read_to_string(format!("/proc/{}/smaps_rollup", pid))
+        .ok()
+        .map(|content| parse_smaps_rollup(&content))
+}
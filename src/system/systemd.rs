@@ -0,0 +1,348 @@
+use std::collections::HashMap;
+use std::io;
+use std::sync::mpsc::{self, Receiver, Sender};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use sysinfo::PidExt;
+
+use crate::config::SystemdConfig;
+use crate::system::cgroup::read_cgroup_paths;
+use crate::system::dbus::{Connection, Value};
+use crate::system::{CgroupState, ProcessList};
+
+const SYSTEMD_DESTINATION: &str = "org.freedesktop.systemd1";
+const SYSTEMD_PATH: &str = "/org/freedesktop/systemd1";
+const MANAGER_INTERFACE: &str = "org.freedesktop.systemd1.Manager";
+const SERVICE_INTERFACE: &str = "org.freedesktop.systemd1.Service";
+const PROPERTIES_INTERFACE: &str = "org.freedesktop.DBus.Properties";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum UnitAction {
+    Restart,
+    Stop,
+}
+
+impl UnitAction {
+    pub fn label(&self) -> &'static str {
+        match self {
+            UnitAction::Restart => "Restart",
+            UnitAction::Stop => "Stop",
+        }
+    }
+
+    fn method(&self) -> &'static str {
+        match self {
+            UnitAction::Restart => "RestartUnit",
+            UnitAction::Stop => "StopUnit",
+        }
+    }
+}
+
+pub fn unit_from_cgroup(path: &str) -> Option<(String, String)> {
+    let mut unit = None;
+    let mut prefix = String::new();
+    for segment in path.split('/').filter(|s| !s.is_empty()) {
+        prefix.push('/');
+        prefix.push_str(segment);
+        if segment.ends_with(".service") || segment.ends_with(".scope") {
+            unit = Some((segment.to_string(), prefix.clone()));
+        }
+    }
+    unit
+}
+
+#[derive(Debug, Clone, Default)]
+struct BusUnit {
+    description: String,
+    load_state: String,
+    active_state: String,
+    sub_state: String,
+    object_path: String,
+    main_pid: Option<u32>,
+}
+
+#[derive(Debug, Clone)]
+pub struct UnitSummary {
+    pub name: String,
+    pub description: String,
+    pub load_state: String,
+    pub active_state: String,
+    pub sub_state: String,
+    pub main_pid: Option<u32>,
+    pub cgroup: Option<String>,
+    pub tasks: u64,
+    pub cpu_usage: f32,
+    pub memory: u64,
+}
+
+impl UnitSummary {
+    pub fn is_failed(&self) -> bool {
+        self.active_state == "failed"
+    }
+}
+
+fn list_units(connection: &mut Connection) -> io::Result<HashMap<String, BusUnit>> {
+    let reply = connection.call(
+        SYSTEMD_DESTINATION,
+        SYSTEMD_PATH,
+        MANAGER_INTERFACE,
+        "ListUnits",
+        "",
+        &[],
+    )?;
+
+    let mut units = HashMap::new();
+    for unit in reply.first().and_then(Value::as_slice).unwrap_or(&[]) {
+        let fields: Vec<&str> = unit
+            .as_slice()
+            .unwrap_or(&[])
+            .iter()
+            .map(|field| field.as_str().unwrap_or(""))
+            .collect();
+        if fields.len() < 7 {
+            continue;
+        }
+        units.insert(
+            fields[0].to_string(),
+            BusUnit {
+                description: fields[1].to_string(),
+                load_state: fields[2].to_string(),
+                active_state: fields[3].to_string(),
+                sub_state: fields[4].to_string(),
+                object_path: fields[6].to_string(),
+                main_pid: None,
+            },
+        );
+    }
+    Ok(units)
+}
+
+fn read_main_pid(connection: &mut Connection, object_path: &str) -> Option<u32> {
+    let reply = connection
+        .call(
+            SYSTEMD_DESTINATION,
+            object_path,
+            PROPERTIES_INTERFACE,
+            "Get",
+            "ss",
+            &[
+                Value::Str(SERVICE_INTERFACE.to_string()),
+                Value::Str("MainPID".to_string()),
+            ],
+        )
+        .ok()?;
+    reply
+        .first()
+        .and_then(Value::as_u64)
+        .map(|pid| pid as u32)
+        .filter(|pid| *pid > 0)
+}
+
+fn query_units(bus: &str) -> io::Result<HashMap<String, BusUnit>> {
+    let mut connection = Connection::open(bus)?;
+    let mut units = list_units(&mut connection)?;
+    for (name, unit) in units.iter_mut() {
+        if name.ends_with(".service") && unit.active_state != "inactive" {
+            unit.main_pid = read_main_pid(&mut connection, &unit.object_path);
+        }
+    }
+    Ok(units)
+}
+
+fn perform_action(bus: &str, action: UnitAction, unit: &str) -> String {
+    let result = Connection::open(bus).and_then(|mut connection| {
+        connection.call(
+            SYSTEMD_DESTINATION,
+            SYSTEMD_PATH,
+            MANAGER_INTERFACE,
+            action.method(),
+            "ss",
+            &[
+                Value::Str(unit.to_string()),
+                Value::Str("replace".to_string()),
+            ],
+        )
+    });
+
+    match result {
+        Ok(_) => format!("{} {}: queued", action.label(), unit),
+        Err(err) if err.kind() == io::ErrorKind::PermissionDenied => {
+            format!("{} {}: permission denied ({})", action.label(), unit, err)
+        }
+        Err(err) => format!("{} {}: {}", action.label(), unit, err),
+    }
+}
+
+enum BusReply {
+    Units(io::Result<HashMap<String, BusUnit>>),
+    Action(String),
+}
+
+pub struct SystemdState {
+    bus: String,
+    refresh_interval: Duration,
+    last_query: Option<Instant>,
+    bus_units: HashMap<String, BusUnit>,
+    bus_error: Option<String>,
+    known: HashMap<u32, Option<(String, String)>>,
+    units: Vec<UnitSummary>,
+    action_status: Option<String>,
+    querying: bool,
+    sender: Sender<BusReply>,
+    receiver: Receiver<BusReply>,
+}
+
+impl SystemdState {
+    pub fn new(config: &SystemdConfig) -> Self {
+        let (sender, receiver) = mpsc::channel();
+        Self {
+            bus: config.bus.clone(),
+            refresh_interval: Duration::from_millis(config.refresh_interval),
+            last_query: None,
+            bus_units: HashMap::new(),
+            bus_error: None,
+            known: HashMap::new(),
+            units: Vec::new(),
+            action_status: None,
+            querying: false,
+            sender,
+            receiver,
+        }
+    }
+
+    pub fn update(&mut self, processes: &ProcessList, cgroups: &CgroupState) {
+        let due = self
+            .last_query
+            .map(|at| at.elapsed() >= self.refresh_interval)
+            .unwrap_or(true);
+        self.collect_bus();
+        if due && !self.querying {
+            self.refresh_bus();
+        }
+
+        let mut known = HashMap::new();
+        let mut units: HashMap<String, UnitSummary> = HashMap::new();
+        for process in processes.get_processes() {
+            let pid = process.get_pid().as_u32();
+            let unit = match self.known.remove(&pid) {
+                Some(unit) => unit,
+                None => process.get_cgroup().and_then(unit_from_cgroup).or_else(|| {
+                    read_cgroup_paths(pid)
+                        .iter()
+                        .find_map(|path| unit_from_cgroup(path))
+                }),
+            };
+
+            if let Some((name, cgroup)) = &unit {
+                let summary = units
+                    .entry(name.clone())
+                    .or_insert_with(|| self.summary(name, Some(cgroup.clone())));
+                summary.tasks += 1;
+                summary.cpu_usage += process.get_cpu_usage();
+                summary.memory += process.get_memory_usage();
+            }
+            known.insert(pid, unit);
+        }
+        self.known = known;
+
+        for summary in units.values_mut() {
+            let pids = summary
+                .cgroup
+                .as_deref()
+                .and_then(|path| cgroups.find(path))
+                .and_then(|node| node.stats.pids_current);
+            if let Some(pids) = pids {
+                summary.tasks = pids;
+            }
+        }
+
+        for (name, unit) in &self.bus_units {
+            let listed = name.ends_with(".service")
+                && unit.load_state != "not-found"
+                && unit.active_state != "inactive";
+            if listed && !units.contains_key(name) {
+                units.insert(name.clone(), self.summary(name, None));
+            }
+        }
+
+        let mut units: Vec<UnitSummary> = units.into_values().collect();
+        units.sort_by(|a, b| a.name.cmp(&b.name));
+        self.units = units;
+    }
+
+    fn summary(&self, name: &str, cgroup: Option<String>) -> UnitSummary {
+        let unit = self.bus_units.get(name).cloned().unwrap_or_default();
+        UnitSummary {
+            name: name.to_string(),
+            description: unit.description,
+            load_state: unit.load_state,
+            active_state: unit.active_state,
+            sub_state: unit.sub_state,
+            main_pid: unit.main_pid,
+            cgroup,
+            tasks: 0,
+            cpu_usage: 0.0,
+            memory: 0,
+        }
+    }
+
+    fn refresh_bus(&mut self) {
+        self.last_query = Some(Instant::now());
+        self.querying = true;
+
+        let bus = self.bus.clone();
+        let sender = self.sender.clone();
+        thread::spawn(move || {
+            let _ = sender.send(BusReply::Units(query_units(&bus)));
+        });
+    }
+
+    fn collect_bus(&mut self) {
+        while let Ok(reply) = self.receiver.try_recv() {
+            match reply {
+                BusReply::Units(Ok(units)) => {
+                    self.querying = false;
+                    self.bus_units = units;
+                    self.bus_error = None;
+                }
+                BusReply::Units(Err(err)) => {
+                    self.querying = false;
+                    self.bus_units.clear();
+                    self.bus_error = Some(err.to_string());
+                }
+                BusReply::Action(status) => {
+                    self.action_status = Some(status);
+                    self.last_query = None;
+                }
+            }
+        }
+    }
+
+    pub fn perform(&mut self, action: UnitAction, unit: &str) {
+        self.action_status = Some(format!("{} {}: pending", action.label(), unit));
+
+        let bus = self.bus.clone();
+        let unit = unit.to_string();
+        let sender = self.sender.clone();
+        thread::spawn(move || {
+            let _ = sender.send(BusReply::Action(perform_action(&bus, action, &unit)));
+        });
+    }
+
+    pub fn get_units(&self) -> &[UnitSummary] {
+        &self.units
+    }
+
+    pub fn get_bus_error(&self) -> Option<&str> {
+        self.bus_error.as_deref()
+    }
+
+    pub fn get_action_status(&self) -> Option<&str> {
+        self.action_status.as_deref()
+    }
+
+    pub fn clear_action_status(&mut self) {
+        self.action_status = None;
+    }
+}
@@ -0,0 +1,179 @@
+use std::collections::{HashMap, VecDeque};
+use std::fs;
+use std::time::{Duration, Instant};
+
+const PROC_VMSTAT: &str = "/proc/vmstat";
+const OOM_HIGHLIGHT: Duration = Duration::from_secs(60);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum VmCounter {
+    SwapIn,
+    SwapOut,
+    PageFault,
+    MajorFault,
+    PageScan,
+    PageSteal,
+    OomKill,
+}
+
+impl VmCounter {
+    pub const ALL: [VmCounter; 7] = [
+        VmCounter::SwapIn,
+        VmCounter::SwapOut,
+        VmCounter::PageFault,
+        VmCounter::MajorFault,
+        VmCounter::PageScan,
+        VmCounter::PageSteal,
+        VmCounter::OomKill,
+    ];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            VmCounter::SwapIn => "Swap in",
+            VmCounter::SwapOut => "Swap out",
+            VmCounter::PageFault => "Page faults",
+            VmCounter::MajorFault => "Major faults",
+            VmCounter::PageScan => "Pages scanned",
+            VmCounter::PageSteal => "Pages stolen",
+            VmCounter::OomKill => "OOM kills",
+        }
+    }
+
+    fn value(&self, fields: &HashMap<String, u64>) -> u64 {
+        let get = |key: &str| fields.get(key).copied().unwrap_or(0);
+        let sum_reclaim = |prefix: &str| {
+            ["kswapd", "direct", "khugepaged"]
+                .iter()
+                .map(|source| get(&format!("{}_{}", prefix, source)))
+                .sum()
+        };
+
+        match self {
+            VmCounter::SwapIn => get("pswpin"),
+            VmCounter::SwapOut => get("pswpout"),
+            VmCounter::PageFault => get("pgfault"),
+            VmCounter::MajorFault => get("pgmajfault"),
+            VmCounter::PageScan => sum_reclaim("pgscan"),
+            VmCounter::PageSteal => sum_reclaim("pgsteal"),
+            VmCounter::OomKill => get("oom_kill"),
+        }
+    }
+}
+
+pub fn parse_vmstat(content: &str) -> HashMap<String, u64> {
+    content
+        .lines()
+        .filter_map(|line| {
+            let (key, value) = line.split_once(' ')?;
+            Some((key.to_string(), value.trim().parse().ok()?))
+        })
+        .collect()
+}
+
+pub struct VmStatState {
+    available: bool,
+    counters: HashMap<VmCounter, u64>,
+    rates: HashMap<VmCounter, f64>,
+    history: HashMap<VmCounter, VecDeque<f64>>,
+    history_size: usize,
+    last_update: Instant,
+    last_oom_kill: Option<Instant>,
+}
+
+impl VmStatState {
+    pub fn new(history_size: usize) -> Self {
+        let fields = fs::read_to_string(PROC_VMSTAT)
+            .map(|content| parse_vmstat(&content))
+            .ok();
+
+        let counters = fields
+            .as_ref()
+            .map(|fields| {
+                VmCounter::ALL
+                    .iter()
+                    .map(|counter| (*counter, counter.value(fields)))
+                    .collect()
+            })
+            .unwrap_or_default();
+
+        Self {
+            available: fields.is_some(),
+            counters,
+            rates: HashMap::new(),
+            history: VmCounter::ALL
+                .iter()
+                .map(|counter| (*counter, VecDeque::with_capacity(history_size)))
+                .collect(),
+            history_size,
+            last_update: Instant::now(),
+            last_oom_kill: None,
+        }
+    }
+
+    pub fn update(&mut self) {
+        let fields = match fs::read_to_string(PROC_VMSTAT) {
+            Ok(content) => parse_vmstat(&content),
+            Err(_) => {
+                self.available = false;
+                return;
+            }
+        };
+        self.available = true;
+
+        let now = Instant::now();
+        let time_delta = now.duration_since(self.last_update).as_secs_f64();
+
+        for counter in VmCounter::ALL {
+            let value = counter.value(&fields);
+            let prev = self.counters.insert(counter, value).unwrap_or(value);
+            let diff = value.saturating_sub(prev);
+
+            if counter == VmCounter::OomKill && diff > 0 {
+                self.last_oom_kill = Some(now);
+            }
+
+            if time_delta > 0.001 {
+                self.rates.insert(counter, diff as f64 / time_delta);
+            }
+
+            let rate = self.get_rate(counter);
+            let history = self.history.entry(counter).or_default();
+            history.push_back(rate);
+            if history.len() > self.history_size {
+                history.pop_front();
+            }
+        }
+
+        self.last_update = now;
+    }
+
+    pub fn is_available(&self) -> bool {
+        self.available
+    }
+
+    pub fn get_total(&self, counter: VmCounter) -> u64 {
+        self.counters.get(&counter).copied().unwrap_or(0)
+    }
+
+    pub fn get_rate(&self, counter: VmCounter) -> f64 {
+        self.rates.get(&counter).copied().unwrap_or(0.0)
+    }
+
+    pub fn get_history(&self, counter: VmCounter) -> Option<&VecDeque<f64>> {
+        self.history.get(&counter)
+    }
+
+    pub fn is_swapping(&self) -> bool {
+        self.get_rate(VmCounter::SwapIn) > 0.0 || self.get_rate(VmCounter::SwapOut) > 0.0
+    }
+
+    pub fn get_last_oom_kill(&self) -> Option<Instant> {
+        self.last_oom_kill
+    }
+
+    pub fn recent_oom_kill(&self) -> bool {
+        self.last_oom_kill
+            .map(|at| at.elapsed() < OOM_HIGHLIGHT)
+            .unwrap_or(false)
+    }
+}
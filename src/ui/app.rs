@@ -1,45 +1,117 @@
-use crate::config::Config;
+use crate::config::{Config, State};
+use crate::system::network::InterfaceSort;
+use crate::system::process::{MemoryColumn, SortKey};
+use crate::system::systemd::UnitAction;
 use crate::system::SystemState;
+use crate::ui::graph::{self, MarkerStyle};
+use crate::ui::keymap::{Action, Keymap};
+use crate::ui::layout::{self, LayoutSet, Pane, View};
+use crate::ui::mouse::{ClickTracker, Regions, StatusTarget};
+use crate::ui::palette::{self, Command, Palette};
 use crate::ui::theme::Theme;
-use anyhow::Result;
+use crate::ui::tree::TreeState;
+use crate::ui::widgets::{self, NetworkRow};
+use crate::util::units::ByteBase;
+use anyhow::{bail, Result};
 use crossterm::{
-    event::{self, DisableMouseCapture, EnableMouseCapture, Event, KeyCode},
+    event::{
+        self, DisableMouseCapture, EnableMouseCapture, Event, KeyCode, KeyEvent, KeyModifiers,
+        MouseButton, MouseEvent, MouseEventKind,
+    },
     execute,
     terminal::{disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen},
 };
 use ratatui::{
     backend::{Backend, CrosstermBackend},
-    Terminal,
+    Frame, Terminal,
 };
 use std::{
+    collections::BTreeSet,
     io,
     time::{Duration, Instant},
 };
+use sysinfo::PidExt;
+
+const PROCESS_OWNER_INTERVAL: Duration = Duration::from_secs(5);
 
 pub struct App {
     system: SystemState,
     config: Config,
     theme: Theme,
+    keymap: Keymap,
     should_quit: bool,
-    current_layout: LayoutView,
+    layouts: LayoutSet,
+    current_layout: usize,
+    block_tree_state: TreeState,
+    cgroup_tree_state: TreeState,
+    connection_filter: String,
+    editing_filter: bool,
+    connection_selected: usize,
+    show_interface_details: bool,
+    unit_selected: usize,
+    pending_unit_action: Option<(UnitAction, String)>,
+    pending_kill: Option<(libc::pid_t, i32, String)>,
+    show_help: bool,
+    palette: Option<Palette>,
+    message: Option<String>,
+    process_selected: Option<u32>,
+    focused: Option<Pane>,
+    zoomed: Option<Pane>,
+    resized_layouts: BTreeSet<String>,
+    regions: Regions,
+    clicks: ClickTracker,
 }
 
 impl App {
-    pub fn new(config: Config) -> Self {
+    pub fn new(mut config: Config) -> Result<Self> {
+        let state = State::load();
+        if !state.tab.is_empty() {
+            config.tabs.selected = state.tab;
+        }
         let theme = Theme::from_name(&config.theme);
-        Self {
-            system: SystemState::new(),
+        graph::validate(&config.graphs)?;
+        let layouts = LayoutSet::from_config(&config)?;
+        let keymap = Keymap::from_config(&config.keys, &layouts.key_bindings())?;
+        let current_layout = layouts
+            .find(&config.tabs.selected)
+            .or_else(|| layouts.find("default"))
+            .unwrap_or(0);
+        Ok(Self {
+            system: SystemState::new(&config, graph::history_size(&config)),
+            connection_filter: config.connections.filter.clone(),
+            editing_filter: false,
+            connection_selected: 0,
+            show_interface_details: false,
+            unit_selected: 0,
+            pending_unit_action: None,
+            pending_kill: None,
+            show_help: false,
+            palette: None,
+            message: None,
+            process_selected: None,
+            focused: None,
+            zoomed: None,
+            resized_layouts: BTreeSet::new(),
+            regions: Regions::new(),
+            clicks: ClickTracker::new(),
             config,
             theme,
+            keymap,
             should_quit: false,
-            current_layout: LayoutView::Default,
-        }
+            current_layout,
+            layouts,
+            block_tree_state: TreeState::new(),
+            cgroup_tree_state: TreeState::new(),
+        })
     }
 
     pub fn run(&mut self) -> Result<()> {
         enable_raw_mode()?;
         let mut stdout = io::stdout();
-        execute!(stdout, EnterAlternateScreen, EnableMouseCapture)?;
+        execute!(stdout, EnterAlternateScreen)?;
+        if self.config.mouse.enabled {
+            execute!(stdout, EnableMouseCapture)?;
+        }
         let backend = CrosstermBackend::new(stdout);
         let mut terminal = Terminal::new(backend)?;
 
@@ -47,16 +119,21 @@ impl App {
         let res = self.run_app(&mut terminal, tick_rate);
 
         disable_raw_mode()?;
-        execute!(
-            terminal.backend_mut(),
-            LeaveAlternateScreen,
-            DisableMouseCapture
-        )?;
+        if self.config.mouse.enabled {
+            execute!(terminal.backend_mut(), DisableMouseCapture)?;
+        }
+        execute!(terminal.backend_mut(), LeaveAlternateScreen)?;
         terminal.show_cursor()?;
 
         if let Err(err) = res {
             println!("{:?}", err);
         }
+        if let Err(err) = self.remember_tab() {
+            eprintln!("Error saving state: {}", err);
+        }
+        if let Err(err) = self.save_layouts() {
+            eprintln!("Error saving layouts: {}", err);
+        }
 
         Ok(())
     }
@@ -76,8 +153,10 @@ impl App {
                 .unwrap_or_else(|| Duration::from_secs(0));
 
             if event::poll(timeout)? {
-                if let Event::Key(key) = event::read()? {
-                    self.handle_key(key.code);
+                match event::read()? {
+                    Event::Key(key) => self.handle_key(key),
+                    Event::Mouse(mouse) => self.handle_mouse(mouse),
+                    _ => {}
                 }
             }
 
@@ -91,6 +170,28 @@ impl App {
     }
 
     fn update(&mut self) {
+        let panes = match self.zoomed {
+            Some(pane) => vec![pane],
+            None => self.panes(),
+        };
+        let owner_interval = if [Pane::Connections, Pane::TopTalkers, Pane::Containers]
+            .iter()
+            .any(|pane| panes.contains(pane))
+        {
+            Some(Duration::ZERO)
+        } else if panes.contains(&Pane::Processes) {
+            Some(PROCESS_OWNER_INTERVAL)
+        } else {
+            None
+        };
+        self.system.connections.set_owner_interval(owner_interval);
+        self.system
+            .cgroups
+            .set_enabled(panes.contains(&Pane::Cgroups) || panes.contains(&Pane::Units));
+        self.system
+            .block_tree
+            .set_enabled(panes.contains(&Pane::BlockTree));
+
         match std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
             self.system.update();
         })) {
@@ -101,75 +202,742 @@ impl App {
         }
     }
 
-    fn handle_key(&mut self, key: KeyCode) {
+    fn handle_key(&mut self, key: KeyEvent) {
+        self.message = None;
+        if self.show_help {
+            self.show_help = false;
+            return;
+        }
+        if self.palette.is_some() {
+            self.handle_palette_key(key);
+            return;
+        }
+        if self.editing_filter {
+            self.handle_filter_key(key.code);
+            return;
+        }
+        if let Some((action, unit)) = self.pending_unit_action.take() {
+            if key.code == KeyCode::Char('y') {
+                self.system.systemd.perform(action, &unit);
+            }
+            return;
+        }
+        if let Some((pid, signal, target)) = self.pending_kill.take() {
+            if key.code == KeyCode::Char('y') {
+                self.kill(pid, signal, &target);
+            }
+            return;
+        }
+
+        if let Some(action) = self.keymap.handle(key) {
+            self.perform(action);
+        }
+    }
+
+    fn perform(&mut self, action: Action) {
+        match action {
+            Action::Quit => self.should_quit = true,
+            Action::CycleTheme => self.theme.cycle_next(),
+            Action::ToggleGraphs => self.toggle_graph_view(),
+            Action::CycleSort => self.cycle_sort_key(),
+            Action::CycleMemoryColumn => self.cycle_memory_column(),
+            Action::TogglePseudoFilesystems => self.toggle_pseudo_filesystems(),
+            Action::LayoutDefault
+            | Action::LayoutGraphs
+            | Action::LayoutCpu
+            | Action::LayoutMemory
+            | Action::LayoutCompact
+            | Action::LayoutStorage
+            | Action::LayoutConnections
+            | Action::LayoutCgroups
+            | Action::LayoutContainers
+            | Action::LayoutUnits
+            | Action::LayoutAlerts
+            | Action::LayoutProcesses
+            | Action::LayoutDisks
+            | Action::LayoutNetwork
+            | Action::LayoutSensors
+            | Action::CustomLayout(_) => {
+                if let Some(index) = self.layouts.find_action(action) {
+                    self.set_layout(index);
+                }
+            }
+            Action::RestartUnit => self.request_unit_action(UnitAction::Restart),
+            Action::StopUnit => self.request_unit_action(UnitAction::Stop),
+            Action::ShowCgroupProcesses => self.show_cgroup_processes(),
+            Action::FilterConnections => self.start_filter(),
+            Action::NextInterface => self.cycle_interface(),
+            Action::CycleInterfaceSort => self.cycle_interface_sort(),
+            Action::ToggleVirtualInterfaces => self.toggle_virtual_interfaces(),
+            Action::ToggleByteBase => self.toggle_byte_base(),
+            Action::ToggleBits => self.config.units.network_bits = !self.config.units.network_bits,
+            Action::InterfaceDetails => self.show_interface_details = !self.show_interface_details,
+            Action::Dismiss => self.dismiss(),
+            Action::SelectNext => self.select_next(),
+            Action::SelectPrevious => self.select_previous(),
+            Action::SelectFirst => self.select_first(),
+            Action::SelectLast => self.select_last(),
+            Action::ToggleSelected => self.toggle_selected(),
+            Action::Help => self.show_help = true,
+            Action::CommandPalette => self.palette = Some(Palette::new()),
+            Action::NextTab => self.cycle_tab(1),
+            Action::PreviousTab => self.cycle_tab(-1),
+            Action::FocusNext => self.cycle_focus(1),
+            Action::FocusPrevious => self.cycle_focus(-1),
+            Action::ToggleZoom => self.toggle_zoom(),
+            Action::GrowPane => self.resize_focused(true),
+            Action::ShrinkPane => self.resize_focused(false),
+            Action::CycleGraphWindow => self.cycle_graph_window(),
+            Action::CycleGraphMarker => self.cycle_graph_marker(),
+        }
+    }
+
+    fn handle_mouse(&mut self, mouse: MouseEvent) {
+        let (column, row) = (mouse.column, mouse.row);
+        let clicked = matches!(mouse.kind, MouseEventKind::Down(_));
+        if self.show_help {
+            self.show_help = !clicked;
+            return;
+        }
+        if self.palette.is_some()
+            || self.editing_filter
+            || self.pending_unit_action.is_some()
+            || self.pending_kill.is_some()
+        {
+            return;
+        }
+
+        match mouse.kind {
+            MouseEventKind::Down(MouseButton::Left) => {
+                self.message = None;
+                self.click(column, row);
+            }
+            MouseEventKind::ScrollDown => self.scroll(column, row, 1),
+            MouseEventKind::ScrollUp => self.scroll(column, row, -1),
+            _ => {}
+        }
+    }
+
+    fn click(&mut self, column: u16, row: u16) {
+        if let Some(tab) = self.regions.tab_at(column, row) {
+            if let Some(index) = self.layouts.get_tabs().get(tab).copied() {
+                self.set_layout(index);
+            }
+            return;
+        }
+        if let Some(target) = self.regions.status_at(column, row).cloned() {
+            match target {
+                StatusTarget::Action(action) => self.perform(action),
+                StatusTarget::Palette(input) => self.palette = Some(Palette::with_input(input)),
+            }
+            return;
+        }
+        if let Some(key) = self.regions.sort_header_at(column, row) {
+            self.config.sort_by = key.to_string();
+            return;
+        }
+
+        let pane = match self.regions.pane_at(column, row) {
+            Some(pane) => pane,
+            None => return,
+        };
+        self.focused = Some(pane);
+        if self.clicks.is_double_click(pane) {
+            self.toggle_zoom();
+            return;
+        }
+        if let Some((pane, index)) = self.regions.row_at(column, row) {
+            if index < self.selection_count(pane) {
+                self.select_index(pane, index);
+            }
+        }
+    }
+
+    fn scroll(&mut self, column: u16, row: u16, direction: isize) {
+        if let Some(pane) = self.regions.pane_at(column, row) {
+            let lines = self.config.mouse.scroll_lines.max(1) as isize;
+            self.move_selection(pane, direction * lines);
+        }
+    }
+
+    fn set_layout(&mut self, layout: usize) {
+        self.current_layout = layout;
+        self.focused = None;
+        self.zoomed = None;
+        if self.layouts.get_tabs().contains(&layout) {
+            self.config.tabs.selected = self.layouts.get(layout).name.clone();
+        }
+    }
+
+    fn cycle_tab(&mut self, delta: isize) {
+        let tabs = self.layouts.get_tabs();
+        if tabs.is_empty() {
+            return;
+        }
+        let current = tabs
+            .iter()
+            .position(|index| *index == self.current_layout)
+            .or_else(|| {
+                let selected = self.layouts.find(&self.config.tabs.selected)?;
+                tabs.iter().position(|index| *index == selected)
+            });
+        let count = tabs.len() as isize;
+        let next = match current {
+            Some(position) => (position as isize + delta).rem_euclid(count),
+            None => 0,
+        };
+        self.set_layout(tabs[next as usize]);
+    }
+
+    fn cycle_focus(&mut self, delta: isize) {
+        let panes = self.panes();
+        if panes.is_empty() {
+            return;
+        }
+        let count = panes.len() as isize;
+        let index = match self
+            .focused
+            .and_then(|focused| panes.iter().position(|pane| *pane == focused))
+        {
+            Some(index) => (index as isize + delta).rem_euclid(count),
+            None if delta > 0 => 0,
+            None => count - 1,
+        };
+        self.focused = Some(panes[index as usize]);
+        if self.zoomed.is_some() {
+            self.zoomed = self.focused;
+        }
+    }
+
+    fn toggle_zoom(&mut self) {
+        self.zoomed = match self.zoomed {
+            Some(_) => None,
+            None => self
+                .focused
+                .or_else(|| self.selection_pane())
+                .or_else(|| self.panes().first().copied()),
+        };
+        if self.zoomed.is_some() {
+            self.focused = self.zoomed;
+        }
+    }
+
+    fn resize_focused(&mut self, grow: bool) {
+        let pane = match self.focused {
+            Some(pane) => pane,
+            None => {
+                self.message = Some("no pane is focused".to_string());
+                return;
+            }
+        };
+        let layout = self.layouts.get(self.current_layout);
+        let name = layout.name.clone();
+        let mut definition = layout.definition.clone();
+        if !layout::resize(&mut definition.root, pane, grow, &self.config) {
+            return;
+        }
+
+        self.config.layouts.insert(name.clone(), definition);
+        match LayoutSet::from_config(&self.config) {
+            Ok(layouts) => self.layouts = layouts,
+            Err(err) => {
+                self.message = Some(err.to_string());
+                return;
+            }
+        }
+        self.message = Some(format!("Layout `{}` will be saved on exit", name));
+        self.resized_layouts.insert(name);
+    }
+
+    fn save_layouts(&self) -> Result<()> {
+        if self.resized_layouts.is_empty() {
+            return Ok(());
+        }
+        let path = match &self.config.path {
+            Some(path) => path,
+            None => bail!("no config file location"),
+        };
+        let layouts = self
+            .resized_layouts
+            .iter()
+            .filter_map(|name| Some((name.clone(), self.config.layouts.get(name)?.clone())))
+            .collect();
+        Config::save_layouts(path, &layouts)
+    }
+
+    fn remember_tab(&self) -> Result<()> {
+        let tab = self.config.tabs.selected.clone();
+        if tab.is_empty() || State::load().tab == tab {
+            return Ok(());
+        }
+        State { tab }.save()
+    }
+
+    fn handle_palette_key(&mut self, key: KeyEvent) {
+        let candidates = palette::candidates(&self.system.processes, &self.layouts);
+        let palette = match self.palette.as_mut() {
+            Some(palette) => palette,
+            None => return,
+        };
+        let count = palette.matches(&candidates).len();
+        let ctrl = key.modifiers.contains(KeyModifiers::CONTROL);
+
+        match key.code {
+            KeyCode::Esc => self.palette = None,
+            KeyCode::Enter => self.submit_palette(&candidates),
+            KeyCode::Tab => {
+                let selected = palette
+                    .matches(&candidates)
+                    .get(palette.get_selected())
+                    .copied();
+                if let Some(candidate) = selected {
+                    palette.complete(candidate);
+                }
+            }
+            KeyCode::Down => palette.select_next(count),
+            KeyCode::Up | KeyCode::BackTab => palette.select_previous(count),
+            KeyCode::Char('n') if ctrl => palette.select_next(count),
+            KeyCode::Char('p') if ctrl => palette.select_previous(count),
+            KeyCode::Char('c') if ctrl => self.palette = None,
+            KeyCode::Backspace => palette.pop(),
+            KeyCode::Char(c) => palette.push(c),
+            _ => {}
+        }
+    }
+
+    fn submit_palette(&mut self, candidates: &[palette::Candidate]) {
+        let palette = match self.palette.as_mut() {
+            Some(palette) => palette,
+            None => return,
+        };
+        if palette.get_input().trim().is_empty() {
+            self.palette = None;
+            return;
+        }
+
+        let command = match Command::parse(palette.get_input()) {
+            Ok(command) => command,
+            Err(err) => {
+                let selected = palette
+                    .matches(candidates)
+                    .get(palette.get_selected())
+                    .copied();
+                match selected {
+                    Some(candidate) if palette.get_input().trim_end() != candidate.text => {
+                        palette.complete(candidate)
+                    }
+                    _ => palette.set_error(err),
+                }
+                return;
+            }
+        };
+
+        self.palette = None;
+        self.execute(command);
+    }
+
+    fn execute(&mut self, command: Command) {
+        match command {
+            Command::Action(action) => self.perform(action),
+            Command::Sort(key) => self.config.sort_by = key,
+            Command::Filter(None) => self.config.filters.clear(),
+            Command::Filter(Some(filter)) => {
+                for token in filter.split_whitespace() {
+                    let (kind, token) = match token.split_once(':') {
+                        Some((kind @ ("cgroup" | "user" | "name"), _)) => (kind, token.to_string()),
+                        _ => ("name", format!("name:{}", token)),
+                    };
+                    let prefix = format!("{}:", kind);
+                    self.config
+                        .filters
+                        .retain(|existing| !existing.starts_with(&prefix));
+                    self.config.filters.push(token);
+                }
+            }
+            Command::Layout(name) => match self.layouts.find(&name) {
+                Some(index) => self.set_layout(index),
+                None => self.message = Some(format!("unknown layout `{}`", name)),
+            },
+            Command::Theme(name) => {
+                self.theme = Theme::from_name(&name);
+                self.config.theme = name;
+            }
+            Command::Kill(pid, signal) => self.request_kill(pid, signal),
+        }
+    }
+
+    fn request_kill(&mut self, pid: libc::pid_t, signal: i32) {
+        let name = self
+            .system
+            .processes
+            .get_processes()
+            .into_iter()
+            .find(|process| process.get_pid().as_u32() == pid as u32)
+            .map(|process| process.get_name().to_string());
+        let target = match name {
+            Some(name) => format!("{} ({})", pid, name),
+            None => pid.to_string(),
+        };
+        self.pending_kill = Some((pid, signal, target));
+    }
+
+    fn kill(&mut self, pid: libc::pid_t, signal: i32, target: &str) {
+        let result = unsafe { libc::kill(pid, signal) };
+        self.message = Some(if result == 0 {
+            format!("Sent {} to {}", palette::signal_name(signal), target)
+        } else {
+            format!("kill {}: {}", target, io::Error::last_os_error())
+        });
+    }
+
+    fn handle_filter_key(&mut self, key: KeyCode) {
         match key {
-            KeyCode::Char('q') => self.should_quit = true,
-            KeyCode::Char('c') => self.theme.cycle_next(),
-            KeyCode::Char('g') => self.toggle_graph_view(),
-            KeyCode::Char('1') => self.current_layout = LayoutView::Default,
-            KeyCode::Char('2') => self.current_layout = LayoutView::GraphView,
-            KeyCode::Char('3') => self.current_layout = LayoutView::CpuFocused,
-            KeyCode::Char('4') => self.current_layout = LayoutView::MemoryFocused,
-            KeyCode::Char('5') => self.current_layout = LayoutView::Compact,
+            KeyCode::Enter => self.editing_filter = false,
+            KeyCode::Esc => {
+                self.connection_filter.clear();
+                self.editing_filter = false;
+            }
+            KeyCode::Backspace => {
+                self.connection_filter.pop();
+            }
+            KeyCode::Char(c) => self.connection_filter.push(c),
+            _ => {}
+        }
+        self.connection_selected = 0;
+    }
+
+    fn start_filter(&mut self) {
+        if self.has_pane(Pane::Connections) {
+            self.editing_filter = true;
+        }
+    }
+
+    fn request_unit_action(&mut self, action: UnitAction) {
+        if self.has_pane(Pane::Units) {
+            if let Some(unit) = self.system.systemd.get_units().get(self.unit_selected) {
+                self.pending_unit_action = Some((action, unit.name.clone()));
+            }
+        }
+    }
+
+    fn dismiss(&mut self) {
+        if self.zoomed.is_some() {
+            self.zoomed = None;
+        } else if self.show_interface_details {
+            self.show_interface_details = false;
+        } else if self.system.systemd.get_action_status().is_some() {
+            self.system.systemd.clear_action_status();
+        } else {
+            self.config
+                .filters
+                .retain(|filter| !filter.starts_with("cgroup:"));
+        }
+    }
+
+    fn show_cgroup_processes(&mut self) {
+        let path = match self.selection_pane() {
+            Some(Pane::Cgroups) => {
+                let rows = widgets::cgroup_rows(&self.system.cgroups, &self.cgroup_tree_state);
+                rows.get(self.cgroup_tree_state.get_selected())
+                    .map(|row| row.item.path.clone())
+            }
+            Some(Pane::Units) => self
+                .system
+                .systemd
+                .get_units()
+                .get(self.unit_selected)
+                .and_then(|unit| unit.cgroup.clone()),
+            _ => None,
+        };
+
+        if let Some(path) = path {
+            self.config
+                .filters
+                .retain(|filter| !filter.starts_with("cgroup:"));
+            self.config.filters.push(format!("cgroup:{}", path));
+            self.perform(Action::LayoutDefault);
+        }
+    }
+
+    fn cycle_interface(&mut self) {
+        let names: Vec<String> = widgets::network_rows(&self.system.network, &self.config.network)
+            .into_iter()
+            .filter_map(|row| match row {
+                NetworkRow::Interface(i) => Some(i.get_name().to_string()),
+                NetworkRow::VirtualGroup { .. } => None,
+            })
+            .collect();
+
+        let next = names
+            .iter()
+            .position(|name| *name == self.config.network.interface)
+            .map(|idx| (idx + 1) % names.len())
+            .unwrap_or(0);
+        if let Some(name) = names.get(next) {
+            self.config.network.interface = name.clone();
+        }
+    }
+
+    fn cycle_interface_sort(&mut self) {
+        let sort = InterfaceSort::from_name(&self.config.network.sort_by);
+        self.config.network.sort_by = sort.next().name().to_string();
+    }
+
+    fn toggle_virtual_interfaces(&mut self) {
+        self.config.network.collapse_virtual = !self.config.network.collapse_virtual;
+    }
+
+    fn toggle_byte_base(&mut self) {
+        self.config.units.byte_base = match ByteBase::from_value(self.config.units.byte_base) {
+            ByteBase::Binary => ByteBase::Decimal.value(),
+            ByteBase::Decimal => ByteBase::Binary.value(),
+        };
+    }
+
+    fn cycle_sort_key(&mut self) {
+        let column = MemoryColumn::from_name(&self.config.process.memory_column);
+        let sort_key = SortKey::from_name(&self.config.sort_by, column);
+        self.config.sort_by = sort_key.next(column).name().to_string();
+    }
+
+    fn cycle_memory_column(&mut self) {
+        let column = MemoryColumn::from_name(&self.config.process.memory_column);
+        let next = column.next(self.system.processes.is_smaps_enabled());
+        self.config.process.memory_column = next.name().to_string();
+    }
+
+    fn cycle_graph_window(&mut self) {
+        self.config.graphs.window = graph::next_window(&self.config.graphs);
+    }
+
+    fn cycle_graph_marker(&mut self) {
+        let marker =
+            MarkerStyle::from_name(&self.config.graphs.marker).unwrap_or(MarkerStyle::Braille);
+        self.config.graphs.marker = marker.next().name().to_string();
+    }
+
+    fn toggle_pseudo_filesystems(&mut self) {
+        self.system.disk.toggle_pseudo();
+        self.config.disk.hide_pseudo = self.system.disk.is_hiding_pseudo();
+    }
+
+    fn panes(&self) -> Vec<Pane> {
+        self.layouts
+            .get(self.current_layout)
+            .root
+            .as_ref()
+            .map(|root| root.panes())
+            .unwrap_or_default()
+    }
+
+    fn has_pane(&self, pane: Pane) -> bool {
+        self.panes().contains(&pane)
+    }
+
+    fn selection_pane(&self) -> Option<Pane> {
+        let selectable = [
+            Pane::BlockTree,
+            Pane::Cgroups,
+            Pane::Connections,
+            Pane::Units,
+            Pane::Processes,
+        ];
+        match self.focused {
+            Some(pane) if selectable.contains(&pane) => Some(pane),
+            _ => {
+                let panes = self.panes();
+                selectable.into_iter().find(|pane| panes.contains(pane))
+            }
+        }
+    }
+
+    fn selection_count(&self, pane: Pane) -> usize {
+        match pane {
+            Pane::BlockTree => {
+                widgets::block_tree_rows(&self.system.block_tree, &self.block_tree_state).len()
+            }
+            Pane::Cgroups => {
+                widgets::cgroup_rows(&self.system.cgroups, &self.cgroup_tree_state).len()
+            }
+            Pane::Connections => {
+                widgets::connection_rows(&self.system.connections, &self.connection_filter).len()
+            }
+            Pane::Units => self.system.systemd.get_units().len(),
+            Pane::Processes => self.regions.get_process_pids().len(),
+            _ => 0,
+        }
+    }
+
+    fn selected_index(&self, pane: Pane) -> Option<usize> {
+        match pane {
+            Pane::BlockTree => Some(self.block_tree_state.get_selected()),
+            Pane::Cgroups => Some(self.cgroup_tree_state.get_selected()),
+            Pane::Connections => Some(self.connection_selected),
+            Pane::Units => Some(self.unit_selected),
+            Pane::Processes => self.process_selected.and_then(|pid| {
+                self.regions
+                    .get_process_pids()
+                    .iter()
+                    .position(|p| *p == pid)
+            }),
+            _ => None,
+        }
+    }
 
+    fn select_index(&mut self, pane: Pane, index: usize) {
+        let count = self.selection_count(pane);
+        if count == 0 {
+            return;
+        }
+        let index = index.min(count - 1);
+        match pane {
+            Pane::BlockTree => self.block_tree_state.select(index),
+            Pane::Cgroups => self.cgroup_tree_state.select(index),
+            Pane::Connections => self.connection_selected = index,
+            Pane::Units => self.unit_selected = index,
+            Pane::Processes => {
+                self.process_selected = self.regions.get_process_pids().get(index).copied()
+            }
             _ => {}
         }
     }
 
-    fn toggle_graph_view(&mut self) {
-        self.current_layout = match self.current_layout {
-            LayoutView::Default => LayoutView::GraphView,
-            LayoutView::GraphView => LayoutView::Default,
-            _ => LayoutView::Default,
-        };
-    }
-
-    fn render<B: Backend>(&self, frame: &mut ratatui::Frame<B>) {
-        match self.current_layout {
-            LayoutView::Default => crate::ui::layout::render(
-                frame,
-                &self.system,
-                &self.config,
-                &self.theme,
-                "Default View",
-            ),
-            LayoutView::GraphView => crate::ui::layout::render_with_graphs(
-                frame,
-                &self.system,
-                &self.config,
-                &self.theme,
-                "Graph View",
-            ),
-            LayoutView::CpuFocused => crate::ui::layout::render_cpu_focused(
-                frame,
-                &self.system,
-                &self.config,
-                &self.theme,
-                "CPU Focus",
-            ),
-            LayoutView::MemoryFocused => crate::ui::layout::render_memory_focused(
-                frame,
-                &self.system,
-                &self.config,
-                &self.theme,
-                "Memory Focus",
-            ),
-            LayoutView::Compact => crate::ui::layout::render_compact(
-                frame,
-                &self.system,
-                &self.config,
-                &self.theme,
-                "Compact View",
-            ),
+    fn move_selection(&mut self, pane: Pane, delta: isize) {
+        let index = match self.selected_index(pane) {
+            Some(index) => index.saturating_add_signed(delta),
+            None => 0,
+        };
+        self.select_index(pane, index);
+    }
+
+    fn select_next(&mut self) {
+        if let Some(pane) = self.selection_pane() {
+            self.move_selection(pane, 1);
+        }
+    }
+
+    fn select_previous(&mut self) {
+        if let Some(pane) = self.selection_pane() {
+            self.move_selection(pane, -1);
         }
     }
-}
 
-#[derive(Clone, Copy)]
-enum LayoutView {
-    Default,
-    GraphView,
-    CpuFocused,
-    MemoryFocused,
-    Compact,
+    fn select_first(&mut self) {
+        if let Some(pane) = self.selection_pane() {
+            self.select_index(pane, 0);
+        }
+    }
+
+    fn select_last(&mut self) {
+        if let Some(pane) = self.selection_pane() {
+            self.select_index(pane, usize::MAX);
+        }
+    }
+
+    fn toggle_selected(&mut self) {
+        match self.selection_pane() {
+            Some(Pane::BlockTree) => {
+                let rows =
+                    widgets::block_tree_rows(&self.system.block_tree, &self.block_tree_state);
+                if let Some(row) = rows.get(self.block_tree_state.get_selected()) {
+                    if row.has_children {
+                        let key = row.key.clone();
+                        self.block_tree_state.toggle(&key);
+                    }
+                }
+            }
+            Some(Pane::Cgroups) => {
+                let rows = widgets::cgroup_rows(&self.system.cgroups, &self.cgroup_tree_state);
+                if let Some(row) = rows.get(self.cgroup_tree_state.get_selected()) {
+                    if row.has_children {
+                        let key = row.key.clone();
+                        self.cgroup_tree_state.toggle(&key);
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+
+    fn toggle_graph_view(&mut self) {
+        let action = match self.layouts.get(self.current_layout).action {
+            Action::LayoutDefault => Action::LayoutGraphs,
+            _ => Action::LayoutDefault,
+        };
+        self.perform(action);
+    }
+
+    fn render<B: Backend>(&mut self, frame: &mut Frame<B>) {
+        let layout = self.layouts.get(self.current_layout);
+        let view = View {
+            system: &self.system,
+            config: &self.config,
+            theme: &self.theme,
+            keymap: &self.keymap,
+            layout_name: &layout.title,
+            layout_actions: self
+                .layouts
+                .get_layouts()
+                .iter()
+                .map(|layout| layout.action)
+                .collect(),
+            block_tree_state: &self.block_tree_state,
+            cgroup_tree_state: &self.cgroup_tree_state,
+            connection_filter: &self.connection_filter,
+            editing_filter: self.editing_filter,
+            connection_selected: self.connection_selected,
+            unit_selected: self.unit_selected,
+            pending_unit_action: self
+                .pending_unit_action
+                .as_ref()
+                .map(|(action, unit)| (*action, unit.as_str())),
+            process_selected: self.process_selected,
+            focused: self.focused,
+            zoomed: self.zoomed,
+            tabs: self
+                .layouts
+                .get_tabs()
+                .iter()
+                .map(|index| {
+                    (
+                        self.layouts.get(*index).title.as_str(),
+                        *index == self.current_layout,
+                    )
+                })
+                .collect(),
+        };
+        layout::render_frame(frame, &view, &mut self.regions, layout.root.as_ref());
+
+        if self.show_interface_details {
+            let interface = widgets::selected_interface(&self.system.network, &self.config.network);
+            if let Some(interface) = interface {
+                widgets::render_interface_details(
+                    frame,
+                    frame.size(),
+                    interface,
+                    &self.config,
+                    &self.theme,
+                );
+            }
+        }
+
+        if self.show_help {
+            widgets::render_help(frame, frame.size(), &self.keymap, &self.theme);
+        }
+        if let Some(palette) = &self.palette {
+            let candidates = palette::candidates(&self.system.processes, &self.layouts);
+            let matches = palette.matches(&candidates);
+            widgets::render_palette(frame, frame.size(), palette, &matches, &self.theme);
+        }
+        if let Some((_, signal, target)) = &self.pending_kill {
+            let prompt = format!(
+                "Send {} to {}? [y/N]",
+                palette::signal_name(*signal),
+                target
+            );
+            widgets::render_message(frame, frame.size(), &prompt, &self.theme);
+        } else if let Some(message) = &self.message {
+            widgets::render_message(frame, frame.size(), message, &self.theme);
+        }
+    }
 }
@@ -0,0 +1,377 @@
+use std::time::Duration;
+
+use anyhow::{bail, Result};
+use ratatui::{
+    buffer::Buffer,
+    layout::Rect,
+    style::{Color, Style},
+    symbols,
+    text::Span,
+    widgets::{Axis, Block, Chart, Dataset, GraphType, Widget},
+};
+
+use crate::alerts::rule::parse_duration;
+use crate::config::{Config, GraphsConfig};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MarkerStyle {
+    Braille,
+    Block,
+    Line,
+}
+
+impl MarkerStyle {
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name.to_lowercase().as_str() {
+            "braille" => Some(MarkerStyle::Braille),
+            "block" => Some(MarkerStyle::Block),
+            "line" => Some(MarkerStyle::Line),
+            _ => None,
+        }
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            MarkerStyle::Braille => "braille",
+            MarkerStyle::Block => "block",
+            MarkerStyle::Line => "line",
+        }
+    }
+
+    pub fn next(&self) -> Self {
+        match self {
+            MarkerStyle::Braille => MarkerStyle::Block,
+            MarkerStyle::Block => MarkerStyle::Line,
+            MarkerStyle::Line => MarkerStyle::Braille,
+        }
+    }
+}
+
+pub fn parse_window(text: &str) -> std::result::Result<Duration, String> {
+    match parse_duration(text.trim()) {
+        Ok(duration) if duration.is_zero() => Err(format!("empty time window `{}`", text)),
+        result => result,
+    }
+}
+
+pub fn validate(config: &GraphsConfig) -> Result<()> {
+    let mut errors = Vec::new();
+    for (field, value) in [("history", &config.history), ("window", &config.window)]
+        .into_iter()
+        .chain(config.windows.iter().map(|window| ("windows", window)))
+    {
+        match (parse_window(value), parse_window(&config.history)) {
+            (Err(err), _) => errors.push(format!("{}: {}", field, err)),
+            (Ok(window), Ok(history)) if field == "window" && window > history => {
+                errors.push(format!(
+                    "{}: `{}` is longer than the history `{}`",
+                    field, value, config.history
+                ))
+            }
+            _ => {}
+        }
+    }
+    if MarkerStyle::from_name(&config.marker).is_none() {
+        errors.push(format!("unknown marker `{}`", config.marker));
+    }
+
+    if !errors.is_empty() {
+        bail!("invalid graphs:\n  {}", errors.join("\n  "));
+    }
+    Ok(())
+}
+
+pub fn history_size(config: &Config) -> usize {
+    let history = parse_window(&config.graphs.history).unwrap_or(Duration::from_secs(1800));
+    let samples = (history.as_millis() as u64).div_ceil(config.update_interval.max(1));
+    samples.max(2) as usize
+}
+
+pub fn next_window(config: &GraphsConfig) -> String {
+    let current = parse_window(&config.window).ok();
+    let position = config
+        .windows
+        .iter()
+        .position(|window| parse_window(window).ok() == current);
+    let next = match position {
+        Some(position) => (position + 1) % config.windows.len(),
+        None => 0,
+    };
+    config
+        .windows
+        .get(next)
+        .cloned()
+        .unwrap_or_else(|| config.window.clone())
+}
+
+pub fn format_offset(seconds: u64) -> String {
+    if seconds == 0 {
+        return "now".to_string();
+    }
+    let (hours, minutes, secs) = (seconds / 3600, seconds % 3600 / 60, seconds % 60);
+    let mut text = "-".to_string();
+    for (value, unit) in [(hours, "h"), (minutes, "m"), (secs, "s")] {
+        if value > 0 {
+            text.push_str(&format!("{}{}", value, unit));
+        }
+    }
+    text
+}
+
+pub struct Series {
+    pub name: String,
+    pub style: Style,
+    pub values: Vec<f64>,
+}
+
+pub struct TimeChart<'a> {
+    block: Block<'a>,
+    series: Vec<Series>,
+    window: Duration,
+    interval: Duration,
+    marker: MarkerStyle,
+    y_title: &'a str,
+    y_max: f64,
+    y_labels: Vec<String>,
+}
+
+impl<'a> TimeChart<'a> {
+    pub fn new(title: &str, config: &Config) -> Self {
+        let window = parse_window(&config.graphs.window).unwrap_or(Duration::from_secs(300));
+        let block = Block::default()
+            .title(format!("{} ({})", title, config.graphs.window))
+            .borders(ratatui::widgets::Borders::ALL);
+        Self {
+            block,
+            series: Vec::new(),
+            window,
+            interval: Duration::from_millis(config.update_interval.max(1)),
+            marker: MarkerStyle::from_name(&config.graphs.marker).unwrap_or(MarkerStyle::Braille),
+            y_title: "",
+            y_max: 100.0,
+            y_labels: vec!["0".to_string(), "50".to_string(), "100".to_string()],
+        }
+    }
+
+    pub fn series(mut self, name: String, style: Style, values: Vec<f64>) -> Self {
+        self.series.push(Series {
+            name,
+            style,
+            values,
+        });
+        self
+    }
+
+    pub fn y_axis(mut self, title: &'a str, max: f64, labels: Vec<String>) -> Self {
+        self.y_title = title;
+        self.y_max = max;
+        self.y_labels = labels;
+        self
+    }
+
+    pub fn visible_count(&self, values: &[f64]) -> usize {
+        let samples = self.window.as_millis() / self.interval.as_millis().max(1);
+        values.len().min(samples as usize + 1)
+    }
+
+    fn points(&self, values: &[f64]) -> Vec<(f64, f64)> {
+        let interval = self.interval.as_secs_f64();
+        let visible = self.visible_count(values);
+        values[values.len() - visible..]
+            .iter()
+            .enumerate()
+            .map(|(i, value)| (-((visible - 1 - i) as f64 * interval), *value))
+            .collect()
+    }
+
+    fn x_labels(&self, width: u16) -> Vec<String> {
+        let count = if width >= 60 { 5 } else { 3 };
+        let window = self.window.as_secs();
+        (0..count)
+            .map(|i| format_offset(window * (count - 1 - i) / (count - 1)))
+            .collect()
+    }
+
+    fn graph_area(&self, inner: Rect, x_labels: &[String]) -> Rect {
+        if inner.width == 0 || inner.height == 0 {
+            return Rect::default();
+        }
+        let mut x = inner.left();
+        let mut y = inner.bottom() - 1;
+        if y > inner.top() {
+            y -= 1;
+        }
+        let y_label_width = self.y_labels.iter().map(|l| l.len()).max().unwrap_or(0) as u16;
+        let x_label_width = x_labels.first().map(|l| l.len()).unwrap_or(0) as u16;
+        x += y_label_width
+            .max(x_label_width.saturating_sub(1))
+            .min(inner.width / 3);
+        if y > inner.top() {
+            y -= 1;
+        }
+        if x + 1 < inner.right() {
+            x += 1;
+        }
+        if x < inner.right() && y > 1 {
+            Rect::new(x, inner.top(), inner.right() - x, y - inner.top() + 1)
+        } else {
+            Rect::default()
+        }
+    }
+
+    fn legend_area(&self, graph: Rect) -> Option<Rect> {
+        let inner_width = self.series.iter().map(|s| s.name.len()).max()? as u16;
+        let width = inner_width + 2;
+        let height = self.series.len() as u16 + 2;
+        if inner_width > 0 && width < graph.width / 4 && height < graph.height / 4 {
+            Some(Rect::new(graph.right() - width, graph.top(), width, height))
+        } else {
+            None
+        }
+    }
+
+    fn draw_lines(&self, buf: &mut Buffer, graph: Rect, legend: Option<Rect>) {
+        if graph.width < 2 || graph.height == 0 {
+            return;
+        }
+        let x_min = -self.window.as_secs_f64();
+        let height = graph.height - 1;
+        let row = |value: f64| {
+            let ratio = (value / self.y_max).clamp(0.0, 1.0);
+            graph.top() + height - (ratio * height as f64).round() as u16
+        };
+
+        for series in &self.series {
+            let points = self.points(&series.values);
+            let mut previous: Option<u16> = None;
+            for column in 0..graph.width {
+                let x = x_min * (1.0 - column as f64 / (graph.width - 1) as f64);
+                let value = match interpolate(&points, x) {
+                    Some(value) => value,
+                    None => continue,
+                };
+                let y = row(value);
+                let cell_x = graph.left() + column;
+                let mut set = |cell_y: u16, symbol: &str| {
+                    let hidden = legend.is_some_and(|legend| {
+                        cell_x >= legend.left()
+                            && cell_x < legend.right()
+                            && cell_y >= legend.top()
+                            && cell_y < legend.bottom()
+                    });
+                    if !hidden {
+                        buf.get_mut(cell_x, cell_y)
+                            .set_symbol(symbol)
+                            .set_style(series.style);
+                    }
+                };
+                match previous {
+                    Some(prev) if y < prev => {
+                        set(prev, "╯");
+                        set(y, "╭");
+                        for between in y + 1..prev {
+                            set(between, "│");
+                        }
+                    }
+                    Some(prev) if y > prev => {
+                        set(prev, "╮");
+                        set(y, "╰");
+                        for between in prev + 1..y {
+                            set(between, "│");
+                        }
+                    }
+                    _ => set(y, "─"),
+                }
+                previous = Some(y);
+            }
+        }
+    }
+}
+
+fn interpolate(points: &[(f64, f64)], x: f64) -> Option<f64> {
+    let first = points.first()?;
+    if x < first.0 {
+        return None;
+    }
+    let index = points.iter().position(|(px, _)| *px >= x)?;
+    if index == 0 {
+        return Some(first.1);
+    }
+    let (x0, y0) = points[index - 1];
+    let (x1, y1) = points[index];
+    if x1 - x0 <= f64::EPSILON {
+        return Some(y1);
+    }
+    Some(y0 + (y1 - y0) * (x - x0) / (x1 - x0))
+}
+
+impl<'a> Widget for TimeChart<'a> {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        let inner = self.block.inner(area);
+        let x_labels = self.x_labels(inner.width);
+        let graph = self.graph_area(inner, &x_labels);
+        let legend = self.legend_area(graph);
+
+        let (marker, line) = match self.marker {
+            MarkerStyle::Braille => (symbols::Marker::Braille, false),
+            MarkerStyle::Block => (symbols::Marker::Block, false),
+            MarkerStyle::Line => (symbols::Marker::Dot, true),
+        };
+        let data: Vec<Vec<(f64, f64)>> = self
+            .series
+            .iter()
+            .map(|series| {
+                if line {
+                    Vec::new()
+                } else {
+                    self.points(&series.values)
+                }
+            })
+            .collect();
+        let datasets = self
+            .series
+            .iter()
+            .zip(&data)
+            .map(|(series, data)| {
+                Dataset::default()
+                    .name(series.name.clone())
+                    .marker(marker)
+                    .graph_type(GraphType::Line)
+                    .style(series.style)
+                    .data(data)
+            })
+            .collect();
+
+        let label_style = Style::default().fg(Color::Gray);
+        let chart = Chart::new(datasets)
+            .block(self.block.clone())
+            .x_axis(
+                Axis::default()
+                    .style(label_style)
+                    .bounds([-self.window.as_secs_f64(), 0.0])
+                    .labels(
+                        x_labels
+                            .iter()
+                            .map(|label| Span::styled(label.clone(), label_style))
+                            .collect(),
+                    ),
+            )
+            .y_axis(
+                Axis::default()
+                    .title(Span::styled(self.y_title, label_style))
+                    .style(label_style)
+                    .bounds([0.0, self.y_max])
+                    .labels(
+                        self.y_labels
+                            .iter()
+                            .map(|label| Span::styled(label.clone(), label_style))
+                            .collect(),
+                    ),
+            );
+        chart.render(area, buf);
+
+        if line {
+            self.draw_lines(buf, graph, legend);
+        }
+    }
+}
@@ -0,0 +1,731 @@
+use std::collections::BTreeMap;
+use std::fmt;
+
+use anyhow::{bail, Result};
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+
+use crate::config::KeysConfig;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Action {
+    Quit,
+    CycleTheme,
+    ToggleGraphs,
+    LayoutDefault,
+    LayoutGraphs,
+    LayoutCpu,
+    LayoutMemory,
+    LayoutCompact,
+    LayoutStorage,
+    LayoutConnections,
+    LayoutCgroups,
+    LayoutContainers,
+    LayoutUnits,
+    LayoutAlerts,
+    LayoutProcesses,
+    LayoutDisks,
+    LayoutNetwork,
+    LayoutSensors,
+    NextTab,
+    PreviousTab,
+    CycleSort,
+    CycleMemoryColumn,
+    ShowCgroupProcesses,
+    RestartUnit,
+    StopUnit,
+    TogglePseudoFilesystems,
+    NextInterface,
+    CycleInterfaceSort,
+    ToggleVirtualInterfaces,
+    InterfaceDetails,
+    FilterConnections,
+    ToggleByteBase,
+    ToggleBits,
+    SelectNext,
+    SelectPrevious,
+    SelectFirst,
+    SelectLast,
+    ToggleSelected,
+    Dismiss,
+    Help,
+    CommandPalette,
+    FocusNext,
+    FocusPrevious,
+    ToggleZoom,
+    GrowPane,
+    ShrinkPane,
+    CycleGraphWindow,
+    CycleGraphMarker,
+    CustomLayout(usize),
+}
+
+impl Action {
+    pub const ALL: [Action; 48] = [
+        Action::Quit,
+        Action::CycleTheme,
+        Action::ToggleGraphs,
+        Action::LayoutDefault,
+        Action::LayoutGraphs,
+        Action::LayoutCpu,
+        Action::LayoutMemory,
+        Action::LayoutCompact,
+        Action::LayoutStorage,
+        Action::LayoutConnections,
+        Action::LayoutCgroups,
+        Action::LayoutContainers,
+        Action::LayoutUnits,
+        Action::LayoutAlerts,
+        Action::LayoutProcesses,
+        Action::LayoutDisks,
+        Action::LayoutNetwork,
+        Action::LayoutSensors,
+        Action::NextTab,
+        Action::PreviousTab,
+        Action::CycleSort,
+        Action::CycleMemoryColumn,
+        Action::ShowCgroupProcesses,
+        Action::RestartUnit,
+        Action::StopUnit,
+        Action::TogglePseudoFilesystems,
+        Action::NextInterface,
+        Action::CycleInterfaceSort,
+        Action::ToggleVirtualInterfaces,
+        Action::InterfaceDetails,
+        Action::FilterConnections,
+        Action::ToggleByteBase,
+        Action::ToggleBits,
+        Action::SelectNext,
+        Action::SelectPrevious,
+        Action::SelectFirst,
+        Action::SelectLast,
+        Action::ToggleSelected,
+        Action::Dismiss,
+        Action::Help,
+        Action::CommandPalette,
+        Action::FocusNext,
+        Action::FocusPrevious,
+        Action::ToggleZoom,
+        Action::GrowPane,
+        Action::ShrinkPane,
+        Action::CycleGraphWindow,
+        Action::CycleGraphMarker,
+    ];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Action::Quit => "quit",
+            Action::CycleTheme => "cycle_theme",
+            Action::ToggleGraphs => "toggle_graphs",
+            Action::LayoutDefault => "layout_default",
+            Action::LayoutGraphs => "layout_graphs",
+            Action::LayoutCpu => "layout_cpu",
+            Action::LayoutMemory => "layout_memory",
+            Action::LayoutCompact => "layout_compact",
+            Action::LayoutStorage => "layout_storage",
+            Action::LayoutConnections => "layout_connections",
+            Action::LayoutCgroups => "layout_cgroups",
+            Action::LayoutContainers => "layout_containers",
+            Action::LayoutUnits => "layout_units",
+            Action::LayoutAlerts => "layout_alerts",
+            Action::LayoutProcesses => "layout_processes",
+            Action::LayoutDisks => "layout_disks",
+            Action::LayoutNetwork => "layout_network",
+            Action::LayoutSensors => "layout_sensors",
+            Action::NextTab => "next_tab",
+            Action::PreviousTab => "previous_tab",
+            Action::CycleSort => "cycle_sort",
+            Action::CycleMemoryColumn => "cycle_memory_column",
+            Action::ShowCgroupProcesses => "show_cgroup_processes",
+            Action::RestartUnit => "restart_unit",
+            Action::StopUnit => "stop_unit",
+            Action::TogglePseudoFilesystems => "toggle_pseudo_filesystems",
+            Action::NextInterface => "next_interface",
+            Action::CycleInterfaceSort => "cycle_interface_sort",
+            Action::ToggleVirtualInterfaces => "toggle_virtual_interfaces",
+            Action::InterfaceDetails => "interface_details",
+            Action::FilterConnections => "filter_connections",
+            Action::ToggleByteBase => "toggle_byte_base",
+            Action::ToggleBits => "toggle_bits",
+            Action::SelectNext => "select_next",
+            Action::SelectPrevious => "select_previous",
+            Action::SelectFirst => "select_first",
+            Action::SelectLast => "select_last",
+            Action::ToggleSelected => "toggle_selected",
+            Action::Dismiss => "dismiss",
+            Action::Help => "help",
+            Action::CommandPalette => "command_palette",
+            Action::FocusNext => "focus_next",
+            Action::FocusPrevious => "focus_previous",
+            Action::ToggleZoom => "toggle_zoom",
+            Action::GrowPane => "grow_pane",
+            Action::ShrinkPane => "shrink_pane",
+            Action::CycleGraphWindow => "cycle_graph_window",
+            Action::CycleGraphMarker => "cycle_graph_marker",
+            Action::CustomLayout(_) => "custom_layout",
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<Self> {
+        Action::ALL
+            .iter()
+            .copied()
+            .find(|action| action.name() == name)
+    }
+
+    pub fn description(&self) -> &'static str {
+        match self {
+            Action::Quit => "Quit",
+            Action::CycleTheme => "Cycle Theme",
+            Action::ToggleGraphs => "Graph View",
+            Action::LayoutDefault => "Default layout",
+            Action::LayoutGraphs => "Graph layout",
+            Action::LayoutCpu => "CPU-focused layout",
+            Action::LayoutMemory => "Memory-focused layout",
+            Action::LayoutCompact => "Compact layout",
+            Action::LayoutStorage => "Storage layout",
+            Action::LayoutConnections => "Network connections layout",
+            Action::LayoutCgroups => "Control group layout",
+            Action::LayoutContainers => "Container layout",
+            Action::LayoutUnits => "systemd unit layout",
+            Action::LayoutAlerts => "Alerts pane",
+            Action::LayoutProcesses => "Processes page",
+            Action::LayoutDisks => "Disks page",
+            Action::LayoutNetwork => "Network page",
+            Action::LayoutSensors => "Sensors page",
+            Action::NextTab => "Next tab",
+            Action::PreviousTab => "Previous tab",
+            Action::CycleSort => "Cycle process sorting",
+            Action::CycleMemoryColumn => "Cycle process memory column",
+            Action::ShowCgroupProcesses => "Show processes of the selected cgroup or unit",
+            Action::RestartUnit => "Restart the selected unit",
+            Action::StopUnit => "Stop the selected unit",
+            Action::TogglePseudoFilesystems => "Show/hide pseudo filesystems",
+            Action::NextInterface => "Select the next network interface",
+            Action::CycleInterfaceSort => "Cycle network interface sorting",
+            Action::ToggleVirtualInterfaces => "Expand/collapse virtual interfaces",
+            Action::InterfaceDetails => "Network interface details",
+            Action::FilterConnections => "Filter the connections table",
+            Action::ToggleByteBase => "Toggle IEC/SI byte units",
+            Action::ToggleBits => "Toggle bytes/bits per second",
+            Action::SelectNext => "Select next row",
+            Action::SelectPrevious => "Select previous row",
+            Action::SelectFirst => "Select first row",
+            Action::SelectLast => "Select last row",
+            Action::ToggleSelected => "Expand/collapse the selected node",
+            Action::Dismiss => "Close popup or clear cgroup filter",
+            Action::Help => "Show this help",
+            Action::CommandPalette => "Open the command palette",
+            Action::FocusNext => "Focus the next pane",
+            Action::FocusPrevious => "Focus the previous pane",
+            Action::ToggleZoom => "Zoom/unzoom the focused pane",
+            Action::GrowPane => "Grow the focused pane",
+            Action::ShrinkPane => "Shrink the focused pane",
+            Action::CycleGraphWindow => "Cycle the graph time window",
+            Action::CycleGraphMarker => "Cycle the graph marker style",
+            Action::CustomLayout(_) => "Custom layout",
+        }
+    }
+
+    pub fn context(&self) -> &'static str {
+        match self {
+            Action::Quit
+            | Action::CycleTheme
+            | Action::ToggleGraphs
+            | Action::Dismiss
+            | Action::Help
+            | Action::CommandPalette => "General",
+            Action::LayoutDefault
+            | Action::LayoutGraphs
+            | Action::LayoutCpu
+            | Action::LayoutMemory
+            | Action::LayoutCompact
+            | Action::LayoutStorage
+            | Action::LayoutConnections
+            | Action::LayoutCgroups
+            | Action::LayoutContainers
+            | Action::LayoutUnits
+            | Action::LayoutAlerts
+            | Action::LayoutProcesses
+            | Action::LayoutDisks
+            | Action::LayoutNetwork
+            | Action::LayoutSensors
+            | Action::NextTab
+            | Action::PreviousTab
+            | Action::CustomLayout(_) => "Layouts",
+            Action::CycleSort | Action::CycleMemoryColumn | Action::ShowCgroupProcesses => {
+                "Processes"
+            }
+            Action::RestartUnit | Action::StopUnit => "Units",
+            Action::TogglePseudoFilesystems => "Disks",
+            Action::NextInterface
+            | Action::CycleInterfaceSort
+            | Action::ToggleVirtualInterfaces
+            | Action::InterfaceDetails
+            | Action::FilterConnections => "Network",
+            Action::ToggleByteBase | Action::ToggleBits => "Units of measure",
+            Action::SelectNext
+            | Action::SelectPrevious
+            | Action::SelectFirst
+            | Action::SelectLast
+            | Action::ToggleSelected => "Navigation",
+            Action::FocusNext
+            | Action::FocusPrevious
+            | Action::ToggleZoom
+            | Action::GrowPane
+            | Action::ShrinkPane => "Panes",
+            Action::CycleGraphWindow | Action::CycleGraphMarker => "Graphs",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct KeyPress {
+    pub code: KeyCode,
+    pub modifiers: KeyModifiers,
+}
+
+impl KeyPress {
+    pub fn from_event(event: KeyEvent) -> Self {
+        let mut modifiers = event.modifiers & (KeyModifiers::CONTROL | KeyModifiers::ALT);
+        if !matches!(event.code, KeyCode::Char(_) | KeyCode::BackTab) {
+            modifiers |= event.modifiers & KeyModifiers::SHIFT;
+        }
+        Self {
+            code: event.code,
+            modifiers,
+        }
+    }
+
+    pub fn parse(text: &str) -> std::result::Result<Self, String> {
+        let mut modifiers = KeyModifiers::NONE;
+        let mut rest = text;
+        loop {
+            let lower = rest.to_lowercase();
+            let prefix = ["ctrl+", "alt+", "shift+", "c-", "m-", "s-"]
+                .iter()
+                .find(|prefix| lower.starts_with(**prefix) && rest.len() > prefix.len());
+            match prefix {
+                Some(prefix) => {
+                    modifiers |= match *prefix {
+                        "ctrl+" | "c-" => KeyModifiers::CONTROL,
+                        "alt+" | "m-" => KeyModifiers::ALT,
+                        _ => KeyModifiers::SHIFT,
+                    };
+                    rest = &rest[prefix.len()..];
+                }
+                None => break,
+            }
+        }
+
+        let mut chars = rest.chars();
+        let code = match (chars.next(), chars.next()) {
+            (Some(c), None) => KeyCode::Char(c),
+            _ => match rest.to_lowercase().as_str() {
+                "enter" | "return" => KeyCode::Enter,
+                "esc" | "escape" => KeyCode::Esc,
+                "space" => KeyCode::Char(' '),
+                "tab" => KeyCode::Tab,
+                "backtab" => KeyCode::BackTab,
+                "backspace" => KeyCode::Backspace,
+                "delete" | "del" => KeyCode::Delete,
+                "insert" | "ins" => KeyCode::Insert,
+                "up" => KeyCode::Up,
+                "down" => KeyCode::Down,
+                "left" => KeyCode::Left,
+                "right" => KeyCode::Right,
+                "home" => KeyCode::Home,
+                "end" => KeyCode::End,
+                "pageup" | "pgup" => KeyCode::PageUp,
+                "pagedown" | "pgdn" => KeyCode::PageDown,
+                name => match name.strip_prefix('f').and_then(|n| n.parse::<u8>().ok()) {
+                    Some(n) if (1..=12).contains(&n) => KeyCode::F(n),
+                    _ => return Err(format!("unknown key `{}`", text)),
+                },
+            },
+        };
+
+        let code = match code {
+            KeyCode::Char(c) if modifiers.contains(KeyModifiers::SHIFT) => {
+                modifiers.remove(KeyModifiers::SHIFT);
+                KeyCode::Char(c.to_ascii_uppercase())
+            }
+            KeyCode::Tab if modifiers.contains(KeyModifiers::SHIFT) => {
+                modifiers.remove(KeyModifiers::SHIFT);
+                KeyCode::BackTab
+            }
+            KeyCode::Char(c) if modifiers.contains(KeyModifiers::CONTROL) => {
+                KeyCode::Char(c.to_ascii_lowercase())
+            }
+            code => code,
+        };
+
+        Ok(Self { code, modifiers })
+    }
+}
+
+impl fmt::Display for KeyPress {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.modifiers.contains(KeyModifiers::CONTROL) {
+            write!(f, "Ctrl-")?;
+        }
+        if self.modifiers.contains(KeyModifiers::ALT) {
+            write!(f, "Alt-")?;
+        }
+        if self.modifiers.contains(KeyModifiers::SHIFT) {
+            write!(f, "Shift-")?;
+        }
+        match self.code {
+            KeyCode::Char(' ') => write!(f, "Space"),
+            KeyCode::Char(c) => write!(f, "{}", c),
+            KeyCode::Enter => write!(f, "Enter"),
+            KeyCode::Esc => write!(f, "Esc"),
+            KeyCode::Tab => write!(f, "Tab"),
+            KeyCode::BackTab => write!(f, "Shift-Tab"),
+            KeyCode::Backspace => write!(f, "Backspace"),
+            KeyCode::Delete => write!(f, "Del"),
+            KeyCode::Insert => write!(f, "Ins"),
+            KeyCode::Up => write!(f, "Up"),
+            KeyCode::Down => write!(f, "Down"),
+            KeyCode::Left => write!(f, "Left"),
+            KeyCode::Right => write!(f, "Right"),
+            KeyCode::Home => write!(f, "Home"),
+            KeyCode::End => write!(f, "End"),
+            KeyCode::PageUp => write!(f, "PgUp"),
+            KeyCode::PageDown => write!(f, "PgDn"),
+            KeyCode::F(n) => write!(f, "F{}", n),
+            code => write!(f, "{:?}", code),
+        }
+    }
+}
+
+pub fn parse_sequence(text: &str) -> std::result::Result<Vec<KeyPress>, String> {
+    let keys = text
+        .split_whitespace()
+        .map(KeyPress::parse)
+        .collect::<std::result::Result<Vec<_>, _>>()?;
+    if keys.is_empty() {
+        return Err("empty key binding".to_string());
+    }
+    Ok(keys)
+}
+
+pub fn format_sequence(keys: &[KeyPress]) -> String {
+    keys.iter()
+        .map(|key| key.to_string())
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+fn preset(name: &str) -> Option<Vec<(Action, &'static [&'static str])>> {
+    let mut bindings: Vec<(Action, &'static [&'static str])> = vec![
+        (Action::Quit, &["q", "ctrl+c"]),
+        (Action::CycleTheme, &["c"]),
+        (Action::ToggleGraphs, &["g"]),
+        (Action::LayoutDefault, &["1"]),
+        (Action::LayoutGraphs, &["2"]),
+        (Action::LayoutCpu, &["3"]),
+        (Action::LayoutMemory, &["4"]),
+        (Action::LayoutCompact, &["5"]),
+        (Action::LayoutStorage, &["6"]),
+        (Action::LayoutConnections, &["7"]),
+        (Action::LayoutCgroups, &["8"]),
+        (Action::LayoutContainers, &["9"]),
+        (Action::LayoutUnits, &["0"]),
+        (Action::LayoutAlerts, &["a"]),
+        (Action::NextTab, &["l", "right"]),
+        (Action::PreviousTab, &["h", "left"]),
+        (Action::CycleSort, &["s"]),
+        (Action::CycleMemoryColumn, &["m"]),
+        (Action::ShowCgroupProcesses, &["f"]),
+        (Action::RestartUnit, &["r"]),
+        (Action::StopUnit, &["x"]),
+        (Action::TogglePseudoFilesystems, &["p"]),
+        (Action::NextInterface, &["n"]),
+        (Action::CycleInterfaceSort, &["o"]),
+        (Action::ToggleVirtualInterfaces, &["v"]),
+        (Action::InterfaceDetails, &["i"]),
+        (Action::FilterConnections, &["/"]),
+        (Action::ToggleByteBase, &["u"]),
+        (Action::ToggleBits, &["b"]),
+        (Action::SelectNext, &["j", "down"]),
+        (Action::SelectPrevious, &["k", "up"]),
+        (Action::SelectFirst, &["home"]),
+        (Action::SelectLast, &["end"]),
+        (Action::ToggleSelected, &["enter", "space"]),
+        (Action::Dismiss, &["esc"]),
+        (Action::Help, &["?", "f1"]),
+        (Action::CommandPalette, &[":"]),
+        (Action::FocusNext, &["tab"]),
+        (Action::FocusPrevious, &["backtab"]),
+        (Action::ToggleZoom, &["z"]),
+        (Action::GrowPane, &["+", "="]),
+        (Action::ShrinkPane, &["-"]),
+        (Action::CycleGraphWindow, &["w"]),
+        (Action::CycleGraphMarker, &["M"]),
+    ];
+
+    match name {
+        "default" => {}
+        "vi" => {
+            for (action, keys) in bindings.iter_mut() {
+                *keys = match action {
+                    Action::Quit => &["q", "Z Z", "ctrl+c"],
+                    Action::ToggleGraphs => &["ctrl+g"],
+                    Action::SelectNext => &["j", "down", "ctrl+n"],
+                    Action::SelectPrevious => &["k", "up", "ctrl+p"],
+                    Action::SelectFirst => &["g g", "home"],
+                    Action::SelectLast => &["G", "end"],
+                    Action::ToggleSelected => &["enter", "space", "o"],
+                    Action::CycleInterfaceSort => &["O"],
+                    _ => keys,
+                };
+            }
+        }
+        _ => return None,
+    }
+    Some(bindings)
+}
+
+pub fn digit_label(label: &str) -> Option<u32> {
+    let mut chars = label.chars();
+    match (chars.next(), chars.next()) {
+        (Some(c), None) => c.to_digit(10),
+        _ => None,
+    }
+}
+
+#[derive(Debug)]
+pub struct Keymap {
+    preset: String,
+    bindings: Vec<(Vec<KeyPress>, Action)>,
+    pending: Vec<KeyPress>,
+    layouts: BTreeMap<Action, String>,
+}
+
+impl Keymap {
+    pub fn from_config(
+        config: &KeysConfig,
+        layouts: &BTreeMap<Action, (String, String)>,
+    ) -> Result<Self> {
+        let mut errors = Vec::new();
+        let mut actions: BTreeMap<Action, Vec<String>> = BTreeMap::new();
+
+        match preset(&config.preset) {
+            Some(bindings) => {
+                for (action, keys) in bindings {
+                    actions.insert(action, keys.iter().map(|key| key.to_string()).collect());
+                }
+            }
+            None => errors.push(format!("unknown key preset `{}`", config.preset)),
+        }
+
+        for (name, keys) in &config.bindings {
+            match Action::from_name(name) {
+                Some(action) => {
+                    actions.insert(action, keys.clone());
+                }
+                None => errors.push(format!("unknown action `{}`", name)),
+            }
+        }
+
+        for (action, (key, _)) in layouts {
+            actions.entry(*action).or_default().push(key.clone());
+        }
+        let describe = |action: &Action| match layouts.get(action) {
+            Some((_, title)) if matches!(action, Action::CustomLayout(_)) => {
+                format!("layout `{}`", title)
+            }
+            _ => action.name().to_string(),
+        };
+
+        let mut bindings: Vec<(Vec<KeyPress>, Action)> = Vec::new();
+        for (action, keys) in &actions {
+            for key in keys {
+                match parse_sequence(key) {
+                    Ok(sequence) => {
+                        if !bindings.iter().any(|(s, a)| *s == sequence && a == action) {
+                            bindings.push((sequence, *action));
+                        }
+                    }
+                    Err(err) => errors.push(format!("{}: {}", action.name(), err)),
+                }
+            }
+        }
+
+        for (idx, (sequence, action)) in bindings.iter().enumerate() {
+            for (other, other_action) in &bindings[idx + 1..] {
+                if sequence == other {
+                    errors.push(format!(
+                        "`{}` is bound to both {} and {}",
+                        format_sequence(sequence),
+                        describe(action),
+                        describe(other_action)
+                    ));
+                } else if other.starts_with(sequence) || sequence.starts_with(other) {
+                    let (short, short_action, long, long_action) = if sequence.len() < other.len() {
+                        (sequence, action, other, other_action)
+                    } else {
+                        (other, other_action, sequence, action)
+                    };
+                    errors.push(format!(
+                        "`{}` ({}) shadows `{}` ({})",
+                        format_sequence(short),
+                        describe(short_action),
+                        format_sequence(long),
+                        describe(long_action)
+                    ));
+                }
+            }
+        }
+
+        if !errors.is_empty() {
+            bail!("invalid key bindings:\n  {}", errors.join("\n  "));
+        }
+
+        Ok(Self {
+            preset: config.preset.clone(),
+            bindings,
+            pending: Vec::new(),
+            layouts: layouts
+                .iter()
+                .map(|(action, (_, title))| (*action, title.clone()))
+                .collect(),
+        })
+    }
+
+    pub fn handle(&mut self, event: KeyEvent) -> Option<Action> {
+        self.pending.push(KeyPress::from_event(event));
+
+        if let Some(action) = self.lookup(&self.pending) {
+            self.pending.clear();
+            return Some(action);
+        }
+        if self.is_prefix(&self.pending) {
+            return None;
+        }
+
+        let last = self.pending.pop();
+        let retry = !self.pending.is_empty();
+        self.pending.clear();
+        match last {
+            Some(key) if retry => {
+                if let Some(action) = self.lookup(&[key]) {
+                    Some(action)
+                } else {
+                    if self.is_prefix(&[key]) {
+                        self.pending.push(key);
+                    }
+                    None
+                }
+            }
+            _ => None,
+        }
+    }
+
+    fn lookup(&self, keys: &[KeyPress]) -> Option<Action> {
+        self.bindings
+            .iter()
+            .find(|(sequence, _)| sequence == keys)
+            .map(|(_, action)| *action)
+    }
+
+    fn is_prefix(&self, keys: &[KeyPress]) -> bool {
+        self.bindings
+            .iter()
+            .any(|(sequence, _)| sequence.len() > keys.len() && sequence.starts_with(keys))
+    }
+
+    pub fn get_pending(&self) -> Option<String> {
+        if self.pending.is_empty() {
+            None
+        } else {
+            Some(format_sequence(&self.pending))
+        }
+    }
+
+    pub fn get_keys(&self, action: Action) -> Vec<String> {
+        self.bindings
+            .iter()
+            .filter(|(_, a)| *a == action)
+            .map(|(sequence, _)| format_sequence(sequence))
+            .collect()
+    }
+
+    pub fn label(&self, action: Action) -> Option<String> {
+        self.get_keys(action).into_iter().next()
+    }
+
+    pub fn range_label(&self, actions: &[Action]) -> Option<String> {
+        let labels: Vec<String> = actions
+            .iter()
+            .filter_map(|action| self.label(*action))
+            .collect();
+        if labels.is_empty() {
+            return None;
+        }
+
+        let mut runs: Vec<Vec<&str>> = Vec::new();
+        for label in &labels {
+            let follows = runs
+                .last()
+                .and_then(|run| run.last())
+                .and_then(|last| digit_label(last))
+                .zip(digit_label(label))
+                .is_some_and(|(last, digit)| digit == last + 1);
+            match runs.last_mut() {
+                Some(run) if follows => run.push(label),
+                _ => runs.push(vec![label]),
+            }
+        }
+        let parts: Vec<String> = runs
+            .iter()
+            .map(|run| match run.as_slice() {
+                [first, .., last] if run.len() > 2 => format!("{}-{}", first, last),
+                _ => run.join("/"),
+            })
+            .collect();
+        Some(parts.join("/"))
+    }
+
+    pub fn describe(&self, action: Action) -> String {
+        match (action, self.layouts.get(&action)) {
+            (Action::CustomLayout(_), Some(title)) => format!("{} layout", title),
+            _ => action.description().to_string(),
+        }
+    }
+
+    pub fn help(&self) -> Vec<(&'static str, Vec<(String, Action)>)> {
+        let mut groups: Vec<(&'static str, Vec<(String, Action)>)> = Vec::new();
+        let custom = self
+            .layouts
+            .keys()
+            .copied()
+            .filter(|action| matches!(action, Action::CustomLayout(_)));
+        for action in Action::ALL.into_iter().chain(custom) {
+            let keys = self.get_keys(action);
+            if keys.is_empty() {
+                continue;
+            }
+            let entry = (keys.join(", "), action);
+            match groups
+                .iter_mut()
+                .find(|(context, _)| *context == action.context())
+            {
+                Some((_, entries)) => entries.push(entry),
+                None => groups.push((action.context(), vec![entry])),
+            }
+        }
+        groups
+    }
+
+    pub fn help_text(&self) -> String {
+        let mut text = format!("Key bindings (preset: {})\n", self.preset);
+        for (context, entries) in self.help() {
+            text.push_str(&format!("\n{}\n", context));
+            for (keys, action) in entries {
+                text.push_str(&format!("  {:<20} {}\n", keys, self.describe(action)));
+            }
+        }
+        text
+    }
+}
@@ -1,187 +1,718 @@
+use std::collections::BTreeMap;
+use std::fmt;
+
+use anyhow::{bail, Result};
 use ratatui::{
     backend::Backend,
-    layout::{Constraint, Direction, Layout},
+    layout::{Constraint, Direction, Layout, Margin, Rect},
     Frame,
 };
 
-use crate::config::Config;
+use crate::config::{self, Config, LayoutDefinition, LayoutNodeConfig};
+use crate::system::systemd::UnitAction;
 use crate::system::SystemState;
+use crate::ui::keymap::{Action, Keymap};
+use crate::ui::mouse::Regions;
 use crate::ui::theme::Theme;
+use crate::ui::tree::TreeState;
 use crate::ui::widgets;
 
-pub fn render<B: Backend>(
-    frame: &mut Frame<B>,
-    system: &SystemState,
-    config: &Config,
-    theme: &Theme,
-    layout_name: &str,
-) {
-    let main_chunks = Layout::default()
-        .direction(Direction::Vertical)
-        .constraints([Constraint::Min(5), Constraint::Length(2)].as_ref())
-        .split(frame.size());
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Pane {
+    Cpu,
+    Memory,
+    Disk,
+    DiskIo,
+    DiskIoGraph,
+    Network,
+    Processes,
+    Pressure,
+    Paging,
+    PagingGraph,
+    CpuGraph,
+    MemoryGraph,
+    PressureGraph,
+    NetworkGraph,
+    BlockTree,
+    Connections,
+    TopTalkers,
+    Cgroups,
+    Containers,
+    Units,
+    ActiveAlerts,
+    ResolvedAlerts,
+    AlertActions,
+    CpuCores,
+    MemoryDetails,
+    ProcessDetails,
+    Sensors,
+}
 
-    let content_chunks = Layout::default()
-        .direction(Direction::Vertical)
-        .margin(1)
-        .constraints(
-            [
-                Constraint::Length(3),
-                Constraint::Length(5),
-                Constraint::Length(8),
-                Constraint::Length(8),
-                Constraint::Min(10),
-            ]
-            .as_ref(),
-        )
-        .split(main_chunks[0]);
+impl Pane {
+    pub const ALL: [Pane; 27] = [
+        Pane::Cpu,
+        Pane::Memory,
+        Pane::Disk,
+        Pane::DiskIo,
+        Pane::DiskIoGraph,
+        Pane::Network,
+        Pane::Processes,
+        Pane::Pressure,
+        Pane::Paging,
+        Pane::PagingGraph,
+        Pane::CpuGraph,
+        Pane::MemoryGraph,
+        Pane::PressureGraph,
+        Pane::NetworkGraph,
+        Pane::BlockTree,
+        Pane::Connections,
+        Pane::TopTalkers,
+        Pane::Cgroups,
+        Pane::Containers,
+        Pane::Units,
+        Pane::ActiveAlerts,
+        Pane::ResolvedAlerts,
+        Pane::AlertActions,
+        Pane::CpuCores,
+        Pane::MemoryDetails,
+        Pane::ProcessDetails,
+        Pane::Sensors,
+    ];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Pane::Cpu => "cpu",
+            Pane::Memory => "memory",
+            Pane::Disk => "disk",
+            Pane::DiskIo => "disk_io",
+            Pane::DiskIoGraph => "disk_io_graph",
+            Pane::Network => "network",
+            Pane::Processes => "processes",
+            Pane::Pressure => "pressure",
+            Pane::Paging => "paging",
+            Pane::PagingGraph => "paging_graph",
+            Pane::CpuGraph => "cpu_graph",
+            Pane::MemoryGraph => "memory_graph",
+            Pane::PressureGraph => "pressure_graph",
+            Pane::NetworkGraph => "network_graph",
+            Pane::BlockTree => "block_tree",
+            Pane::Connections => "connections",
+            Pane::TopTalkers => "top_talkers",
+            Pane::Cgroups => "cgroups",
+            Pane::Containers => "containers",
+            Pane::Units => "units",
+            Pane::ActiveAlerts => "active_alerts",
+            Pane::ResolvedAlerts => "resolved_alerts",
+            Pane::AlertActions => "alert_actions",
+            Pane::CpuCores => "cpu_cores",
+            Pane::MemoryDetails => "memory_details",
+            Pane::ProcessDetails => "process_details",
+            Pane::Sensors => "sensors",
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<Self> {
+        Pane::ALL.iter().copied().find(|pane| pane.name() == name)
+    }
+
+    fn is_shown(&self, config: &Config) -> bool {
+        let layout = &config.layout;
+        match self {
+            Pane::Cpu | Pane::CpuGraph | Pane::CpuCores => layout.show_cpu,
+            Pane::Memory | Pane::MemoryGraph | Pane::MemoryDetails => layout.show_memory,
+            Pane::Disk | Pane::DiskIo | Pane::DiskIoGraph => layout.show_disk,
+            Pane::Network | Pane::NetworkGraph => layout.show_network,
+            Pane::ProcessDetails => layout.show_process_details,
+            _ => true,
+        }
+    }
+}
+
+const PERCENT_STEP: u16 = 5;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Size {
+    Fixed(u16),
+    Percent(u16),
+    Min(u16),
+    Ratio(u32, u32),
+}
 
-    if config.layout.show_cpu {
-        widgets::render_cpu_widget(frame, content_chunks[0], &system.cpu, theme);
+impl Size {
+    pub fn parse(text: &str) -> std::result::Result<Self, String> {
+        let text = text.trim();
+        let invalid = || format!("invalid size `{}`", text);
+        if let Some(percent) = text.strip_suffix('%') {
+            match percent.trim().parse::<u16>() {
+                Ok(percent) if percent <= 100 => Ok(Size::Percent(percent)),
+                _ => Err(invalid()),
+            }
+        } else if let Some(min) = text.strip_prefix("min:") {
+            min.trim().parse().map(Size::Min).map_err(|_| invalid())
+        } else if let Some((numerator, denominator)) = text.split_once('/') {
+            match (
+                numerator.trim().parse::<u32>(),
+                denominator.trim().parse::<u32>(),
+            ) {
+                (Ok(numerator), Ok(denominator)) if denominator > 0 && numerator <= denominator => {
+                    Ok(Size::Ratio(numerator, denominator))
+                }
+                _ => Err(invalid()),
+            }
+        } else {
+            text.parse().map(Size::Fixed).map_err(|_| invalid())
+        }
     }
 
-    if config.layout.show_memory {
-        widgets::render_memory_widget(frame, content_chunks[1], &system.memory, theme);
+    fn length(&self, total: u16) -> u16 {
+        match *self {
+            Size::Fixed(length) | Size::Min(length) => length,
+            Size::Percent(percent) => (total as u32 * percent as u32 / 100) as u16,
+            Size::Ratio(numerator, denominator) => {
+                (total as u64 * numerator as u64 / denominator as u64) as u16
+            }
+        }
     }
 
-    if config.layout.show_disk {
-        widgets::render_disk_widget(frame, content_chunks[2], &system.disk, theme);
+    fn is_flexible(&self) -> bool {
+        matches!(self, Size::Min(_))
     }
 
-    if config.layout.show_network {
-        widgets::render_network_widget(frame, content_chunks[3], &system.network, theme);
+    fn resized(&self, grow: bool) -> Option<Size> {
+        match *self {
+            Size::Fixed(length) if grow => Some(Size::Fixed(length.saturating_add(1))),
+            Size::Fixed(length) if length > 1 => Some(Size::Fixed(length - 1)),
+            Size::Min(length) if grow => Some(Size::Min(length.saturating_add(1))),
+            Size::Min(length) if length > 1 => Some(Size::Min(length - 1)),
+            Size::Percent(percent) if grow && percent + PERCENT_STEP < 100 => {
+                Some(Size::Percent(percent + PERCENT_STEP))
+            }
+            Size::Percent(percent) if !grow && percent > PERCENT_STEP => {
+                Some(Size::Percent(percent - PERCENT_STEP))
+            }
+            Size::Ratio(numerator, denominator) => {
+                Size::Percent((numerator * 100 / denominator) as u16).resized(grow)
+            }
+            _ => None,
+        }
     }
 
-    widgets::render_process_widget(frame, content_chunks[4], &system.processes, config, theme);
+    fn constraint(&self) -> Constraint {
+        match *self {
+            Size::Fixed(length) => Constraint::Length(length),
+            Size::Percent(percent) => Constraint::Percentage(percent),
+            Size::Min(length) => Constraint::Min(length),
+            Size::Ratio(numerator, denominator) => Constraint::Ratio(numerator, denominator),
+        }
+    }
+}
 
-    widgets::render_status_bar(frame, main_chunks[1], layout_name);
+impl fmt::Display for Size {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Size::Fixed(length) => write!(f, "{}", length),
+            Size::Percent(percent) => write!(f, "{}%", percent),
+            Size::Min(length) => write!(f, "min:{}", length),
+            Size::Ratio(numerator, denominator) => write!(f, "{}/{}", numerator, denominator),
+        }
+    }
 }
 
-pub fn render_cpu_focused<B: Backend>(
-    frame: &mut Frame<B>,
-    system: &SystemState,
-    config: &Config,
-    theme: &Theme,
-    layout_name: &str,
-) {
-    let main_chunks = Layout::default()
-        .direction(Direction::Vertical)
-        .constraints([Constraint::Min(5), Constraint::Length(2)].as_ref())
-        .split(frame.size());
+#[derive(Debug, Clone)]
+pub enum LayoutNode {
+    Pane(Pane),
+    Split {
+        direction: Direction,
+        children: Vec<(Option<Size>, LayoutNode)>,
+    },
+}
 
-    let content_chunks = Layout::default()
-        .direction(Direction::Vertical)
-        .margin(1)
-        .constraints([Constraint::Percentage(70), Constraint::Percentage(30)].as_ref())
-        .split(main_chunks[0]);
+impl LayoutNode {
+    fn from_config(
+        node: &LayoutNodeConfig,
+        config: &Config,
+        errors: &mut Vec<String>,
+    ) -> Option<Self> {
+        let (direction, children) = match (
+            node.widget.is_empty(),
+            node.rows.is_empty(),
+            node.cols.is_empty(),
+        ) {
+            (false, true, true) => {
+                return match Pane::from_name(&node.widget) {
+                    Some(pane) if pane.is_shown(config) => Some(LayoutNode::Pane(pane)),
+                    Some(_) => None,
+                    None => {
+                        errors.push(format!("unknown widget `{}`", node.widget));
+                        None
+                    }
+                };
+            }
+            (true, false, true) => (Direction::Vertical, &node.rows),
+            (true, true, false) => (Direction::Horizontal, &node.cols),
+            _ => {
+                errors.push("a node needs exactly one of `widget`, `rows` or `cols`".to_string());
+                return None;
+            }
+        };
+
+        let children: Vec<(Option<Size>, LayoutNode)> = children
+            .iter()
+            .filter_map(|child| {
+                let size = if child.size.is_empty() {
+                    None
+                } else {
+                    match Size::parse(&child.size) {
+                        Ok(size) => Some(size),
+                        Err(err) => {
+                            errors.push(err);
+                            None
+                        }
+                    }
+                };
+                LayoutNode::from_config(child, config, errors).map(|node| (size, node))
+            })
+            .collect();
 
-    widgets::render_cpu_widget(frame, content_chunks[0], &system.cpu, theme);
-    widgets::render_process_widget(frame, content_chunks[1], &system.processes, config, theme);
+        if children.is_empty() {
+            None
+        } else {
+            Some(LayoutNode::Split {
+                direction,
+                children,
+            })
+        }
+    }
 
-    widgets::render_status_bar(frame, main_chunks[1], layout_name);
+    pub fn panes(&self) -> Vec<Pane> {
+        match self {
+            LayoutNode::Pane(pane) => vec![*pane],
+            LayoutNode::Split { children, .. } => children
+                .iter()
+                .flat_map(|(_, child)| child.panes())
+                .collect(),
+        }
+    }
 }
 
-pub fn render_memory_focused<B: Backend>(
-    frame: &mut Frame<B>,
-    system: &SystemState,
+fn node_panes(node: &LayoutNodeConfig) -> Vec<Pane> {
+    Pane::from_name(&node.widget)
+        .into_iter()
+        .chain(node.rows.iter().chain(&node.cols).flat_map(node_panes))
+        .collect()
+}
+
+pub fn resize(node: &mut LayoutNodeConfig, pane: Pane, grow: bool, config: &Config) -> bool {
+    let children = if node.rows.is_empty() {
+        &mut node.cols
+    } else {
+        &mut node.rows
+    };
+    let index = match children
+        .iter()
+        .position(|child| node_panes(child).contains(&pane))
+    {
+        Some(index) => index,
+        None => return false,
+    };
+    resize(&mut children[index], pane, grow, config)
+        || resize_child(children, index, grow, config).is_some()
+}
+
+fn resize_child(
+    children: &mut [LayoutNodeConfig],
+    index: usize,
+    grow: bool,
     config: &Config,
-    theme: &Theme,
-    layout_name: &str,
-) {
-    let main_chunks = Layout::default()
-        .direction(Direction::Vertical)
-        .constraints([Constraint::Min(5), Constraint::Length(2)].as_ref())
-        .split(frame.size());
+) -> Option<()> {
+    let sizes: Vec<Option<Size>> = children
+        .iter()
+        .map(|child| Size::parse(&child.size).ok())
+        .collect();
+    let mut siblings: Vec<usize> = (0..children.len())
+        .filter(|other| {
+            *other != index
+                && node_panes(&children[*other])
+                    .iter()
+                    .any(|pane| pane.is_shown(config))
+        })
+        .collect();
+    siblings.sort_by_key(|other| (other.abs_diff(index), *other < index));
+    let rigid = |other: &usize| matches!(sizes[*other], Some(size) if !size.is_flexible());
 
-    let content_chunks = Layout::default()
-        .direction(Direction::Vertical)
-        .margin(1)
-        .constraints([Constraint::Percentage(70), Constraint::Percentage(30)].as_ref())
-        .split(main_chunks[0]);
+    match sizes[index] {
+        Some(size) if !size.is_flexible() => {
+            let resized = size.resized(grow)?;
+            if matches!(size, Size::Percent(_) | Size::Ratio(..)) {
+                let other = siblings.iter().copied().find(|other| {
+                    matches!(sizes[*other], Some(Size::Percent(_) | Size::Ratio(..)))
+                });
+                if let Some(other) = other {
+                    children[other].size = sizes[other]?.resized(!grow)?.to_string();
+                }
+            }
+            children[index].size = resized.to_string();
+        }
+        size => match siblings.iter().copied().find(rigid) {
+            Some(other) => children[other].size = sizes[other]?.resized(!grow)?.to_string(),
+            None => children[index].size = size?.resized(grow)?.to_string(),
+        },
+    }
+    Some(())
+}
+
+const BUILTIN: [(&str, Action); 15] = [
+    ("default", Action::LayoutDefault),
+    ("graphs", Action::LayoutGraphs),
+    ("cpu", Action::LayoutCpu),
+    ("memory", Action::LayoutMemory),
+    ("compact", Action::LayoutCompact),
+    ("storage", Action::LayoutStorage),
+    ("connections", Action::LayoutConnections),
+    ("cgroups", Action::LayoutCgroups),
+    ("containers", Action::LayoutContainers),
+    ("units", Action::LayoutUnits),
+    ("alerts", Action::LayoutAlerts),
+    ("processes", Action::LayoutProcesses),
+    ("disks", Action::LayoutDisks),
+    ("network", Action::LayoutNetwork),
+    ("sensors", Action::LayoutSensors),
+];
+
+#[derive(Debug, Clone)]
+pub struct LayoutSpec {
+    pub name: String,
+    pub title: String,
+    pub key: String,
+    pub action: Action,
+    pub root: Option<LayoutNode>,
+    pub definition: LayoutDefinition,
+}
+
+#[derive(Debug)]
+pub struct LayoutSet {
+    layouts: Vec<LayoutSpec>,
+    tabs: Vec<usize>,
+}
+
+impl LayoutSet {
+    pub fn from_config(config: &Config) -> Result<Self> {
+        let mut definitions = config::builtin_layouts();
+        let mut names: Vec<String> = BUILTIN.iter().map(|(name, _)| name.to_string()).collect();
+        for (name, definition) in &config.layouts {
+            if !names.contains(name) {
+                names.push(name.clone());
+            }
+            definitions.insert(name.clone(), definition.clone());
+        }
+
+        let mut errors = Vec::new();
+        let mut layouts = Vec::new();
+        for (index, name) in names.iter().enumerate() {
+            let definition = &definitions[name];
+            let mut layout_errors = Vec::new();
+            let root = LayoutNode::from_config(&definition.root, config, &mut layout_errors);
+            errors.extend(
+                layout_errors
+                    .into_iter()
+                    .map(|err| format!("layout `{}`: {}", name, err)),
+            );
+
+            let action = BUILTIN
+                .iter()
+                .find(|(builtin, _)| builtin == name)
+                .map(|(_, action)| *action)
+                .unwrap_or(Action::CustomLayout(index));
+            layouts.push(LayoutSpec {
+                name: name.clone(),
+                title: if definition.title.is_empty() {
+                    name.clone()
+                } else {
+                    definition.title.clone()
+                },
+                key: definition.key.clone(),
+                action,
+                root,
+                definition: definition.clone(),
+            });
+        }
+
+        let mut tabs = Vec::new();
+        if config.tabs.enabled {
+            for page in &config.tabs.pages {
+                match names.iter().position(|name| name == page) {
+                    Some(index) => tabs.push(index),
+                    None => errors.push(format!("unknown tab page `{}`", page)),
+                }
+            }
+        }
+
+        if !errors.is_empty() {
+            bail!("invalid layouts:\n  {}", errors.join("\n  "));
+        }
+        Ok(Self { layouts, tabs })
+    }
+
+    pub fn get(&self, index: usize) -> &LayoutSpec {
+        &self.layouts[index]
+    }
+
+    pub fn get_layouts(&self) -> &[LayoutSpec] {
+        &self.layouts
+    }
+
+    pub fn get_tabs(&self) -> &[usize] {
+        &self.tabs
+    }
+
+    pub fn find(&self, name: &str) -> Option<usize> {
+        self.layouts
+            .iter()
+            .position(|layout| layout.name.eq_ignore_ascii_case(name))
+    }
 
-    widgets::render_memory_widget(frame, content_chunks[0], &system.memory, theme);
-    widgets::render_process_widget(frame, content_chunks[1], &system.processes, config, theme);
+    pub fn find_action(&self, action: Action) -> Option<usize> {
+        self.layouts
+            .iter()
+            .position(|layout| layout.action == action)
+    }
+
+    pub fn key_bindings(&self) -> BTreeMap<Action, (String, String)> {
+        self.layouts
+            .iter()
+            .filter(|layout| !layout.key.is_empty())
+            .map(|layout| (layout.action, (layout.key.clone(), layout.title.clone())))
+            .collect()
+    }
+}
 
-    widgets::render_status_bar(frame, main_chunks[1], layout_name);
+pub struct View<'a> {
+    pub system: &'a SystemState,
+    pub config: &'a Config,
+    pub theme: &'a Theme,
+    pub keymap: &'a Keymap,
+    pub layout_name: &'a str,
+    pub layout_actions: Vec<Action>,
+    pub block_tree_state: &'a TreeState,
+    pub cgroup_tree_state: &'a TreeState,
+    pub connection_filter: &'a str,
+    pub editing_filter: bool,
+    pub connection_selected: usize,
+    pub unit_selected: usize,
+    pub pending_unit_action: Option<(UnitAction, &'a str)>,
+    pub process_selected: Option<u32>,
+    pub focused: Option<Pane>,
+    pub zoomed: Option<Pane>,
+    pub tabs: Vec<(&'a str, bool)>,
 }
 
-pub fn render_compact<B: Backend>(
+pub fn render_frame<B: Backend>(
     frame: &mut Frame<B>,
-    system: &SystemState,
-    config: &Config,
-    theme: &Theme,
-    layout_name: &str,
+    view: &View,
+    regions: &mut Regions,
+    root: Option<&LayoutNode>,
 ) {
-    let main_chunks = Layout::default()
-        .direction(Direction::Vertical)
-        .constraints([Constraint::Min(5), Constraint::Length(2)].as_ref())
-        .split(frame.size());
-
-    let horizontal_chunks = Layout::default()
-        .direction(Direction::Horizontal)
-        .margin(1)
-        .constraints([Constraint::Percentage(50), Constraint::Percentage(50)].as_ref())
-        .split(main_chunks[0]);
+    regions.clear();
 
-    let left_chunks = Layout::default()
+    let tab_height = if view.tabs.is_empty() { 0 } else { 1 };
+    let main_chunks = Layout::default()
         .direction(Direction::Vertical)
         .constraints(
             [
-                Constraint::Length(3),
-                Constraint::Length(5),
-                Constraint::Min(8),
+                Constraint::Length(tab_height),
+                Constraint::Min(5),
+                Constraint::Length(2),
             ]
             .as_ref(),
         )
-        .split(horizontal_chunks[0]);
-
-    let right_chunks = Layout::default()
-        .direction(Direction::Vertical)
-        .constraints([Constraint::Percentage(50), Constraint::Percentage(50)].as_ref())
-        .split(horizontal_chunks[1]);
+        .split(frame.size());
+    if !view.tabs.is_empty() {
+        widgets::render_tab_bar(frame, main_chunks[0], &view.tabs, view.theme, regions);
+    }
 
-    widgets::render_cpu_widget(frame, left_chunks[0], &system.cpu, theme);
-    widgets::render_memory_widget(frame, left_chunks[1], &system.memory, theme);
-    widgets::render_process_widget(frame, left_chunks[2], &system.processes, config, theme);
+    let area = main_chunks[1].inner(&Margin {
+        vertical: 1,
+        horizontal: 1,
+    });
+    match (view.zoomed, root) {
+        (Some(pane), _) => render_pane(frame, area, pane, view, regions),
+        (None, Some(root)) => render_node(frame, area, root, view, regions),
+        (None, None) => {}
+    }
 
-    widgets::render_disk_widget(frame, right_chunks[0], &system.disk, theme);
-    widgets::render_network_widget(frame, right_chunks[1], &system.network, theme);
+    widgets::render_status_bar(
+        frame,
+        main_chunks[2],
+        view.layout_name,
+        &view.layout_actions,
+        view.config,
+        &view.system.alerts,
+        view.theme,
+        view.keymap,
+        regions,
+    );
+}
 
-    widgets::render_status_bar(frame, main_chunks[1], layout_name);
+fn render_node<B: Backend>(
+    frame: &mut Frame<B>,
+    area: Rect,
+    node: &LayoutNode,
+    view: &View,
+    regions: &mut Regions,
+) {
+    match node {
+        LayoutNode::Pane(pane) => render_pane(frame, area, *pane, view, regions),
+        LayoutNode::Split {
+            direction,
+            children,
+        } => {
+            let total = match direction {
+                Direction::Vertical => area.height,
+                Direction::Horizontal => area.width,
+            };
+            let claimed: u16 = children
+                .iter()
+                .filter_map(|(size, _)| size.map(|size| size.length(total)))
+                .sum();
+            let unsized_count = children.iter().filter(|(size, _)| size.is_none()).count();
+            let share = total.saturating_sub(claimed) / unsized_count.max(1) as u16;
+            let constraints: Vec<Constraint> = children
+                .iter()
+                .map(|(size, _)| match size {
+                    Some(size) => size.constraint(),
+                    None => Constraint::Length(share),
+                })
+                .collect();
+            let chunks = Layout::default()
+                .direction(*direction)
+                .constraints(constraints)
+                .split(area);
+            for ((_, child), chunk) in children.iter().zip(chunks.iter()) {
+                render_node(frame, *chunk, child, view, regions);
+            }
+        }
+    }
 }
 
-pub fn render_with_graphs<B: Backend>(
+pub fn render_pane<B: Backend>(
     frame: &mut Frame<B>,
-    system: &SystemState,
-    config: &Config,
-    theme: &Theme,
-    layout_name: &str,
+    area: Rect,
+    pane: Pane,
+    view: &View,
+    regions: &mut Regions,
 ) {
-    let main_chunks = Layout::default()
-        .direction(Direction::Vertical)
-        .constraints([Constraint::Min(5), Constraint::Length(2)].as_ref())
-        .split(frame.size());
+    let system = view.system;
+    let config = view.config;
+    let theme = view.theme;
 
-    let content_chunks = Layout::default()
-        .direction(Direction::Vertical)
-        .margin(1)
-        .constraints([Constraint::Percentage(50), Constraint::Percentage(50)].as_ref())
-        .split(main_chunks[0]);
-
-    let top_chunks = Layout::default()
-        .direction(Direction::Horizontal)
-        .constraints([Constraint::Percentage(50), Constraint::Percentage(50)].as_ref())
-        .split(content_chunks[0]);
-
-    let bottom_chunks = Layout::default()
-        .direction(Direction::Horizontal)
-        .constraints([Constraint::Percentage(50), Constraint::Percentage(50)].as_ref())
-        .split(content_chunks[1]);
-
-    widgets::render_cpu_graph(frame, top_chunks[0], &system.cpu, theme);
-    widgets::render_memory_graph(frame, top_chunks[1], &system.memory, theme);
-    widgets::render_network_graph(frame, bottom_chunks[0], &system.network, theme);
-    widgets::render_process_widget(frame, bottom_chunks[1], &system.processes, config, theme);
-
-    widgets::render_status_bar(frame, main_chunks[1], layout_name);
+    match pane {
+        Pane::Cpu => widgets::render_cpu_widget(frame, area, &system.cpu, theme),
+        Pane::Memory => widgets::render_memory_widget(frame, area, &system.memory, config, theme),
+        Pane::Disk => widgets::render_disk_widget(frame, area, &system.disk, config, theme),
+        Pane::DiskIo => widgets::render_disk_io_widget(frame, area, &system.disk_io, config, theme),
+        Pane::DiskIoGraph => {
+            widgets::render_disk_io_graph(frame, area, &system.disk_io, config, theme)
+        }
+        Pane::Network => {
+            widgets::render_network_widget(frame, area, &system.network, config, theme)
+        }
+        Pane::Processes => widgets::render_process_widget(
+            frame,
+            area,
+            &system.processes,
+            &system.traffic,
+            &system.containers,
+            config,
+            view.process_selected,
+            theme,
+            regions,
+        ),
+        Pane::Pressure => widgets::render_pressure_widget(frame, area, &system.pressure, theme),
+        Pane::Paging => widgets::render_paging_widget(frame, area, &system.vmstat, theme),
+        Pane::PagingGraph => {
+            widgets::render_paging_graph(frame, area, &system.vmstat, config, theme)
+        }
+        Pane::CpuGraph => widgets::render_cpu_graph(frame, area, &system.cpu, config, theme),
+        Pane::MemoryGraph => {
+            widgets::render_memory_graph(frame, area, &system.memory, config, theme)
+        }
+        Pane::PressureGraph => {
+            widgets::render_pressure_graph(frame, area, &system.pressure, config, theme)
+        }
+        Pane::NetworkGraph => {
+            widgets::render_network_graph(frame, area, &system.network, config, theme)
+        }
+        Pane::BlockTree => widgets::render_block_tree_widget(
+            frame,
+            area,
+            &system.block_tree,
+            &system.disk_io,
+            config,
+            view.block_tree_state,
+            theme,
+            regions,
+        ),
+        Pane::Connections => widgets::render_connections_widget(
+            frame,
+            area,
+            &system.connections,
+            view.connection_filter,
+            view.editing_filter,
+            view.connection_selected,
+            theme,
+            regions,
+        ),
+        Pane::TopTalkers => {
+            widgets::render_top_talkers_widget(frame, area, &system.traffic, config, theme)
+        }
+        Pane::Cgroups => widgets::render_cgroup_tree_widget(
+            frame,
+            area,
+            &system.cgroups,
+            config,
+            view.cgroup_tree_state,
+            theme,
+            regions,
+        ),
+        Pane::Containers => {
+            widgets::render_containers_widget(frame, area, &system.containers, config, theme)
+        }
+        Pane::Units => widgets::render_units_widget(
+            frame,
+            area,
+            &system.systemd,
+            config,
+            view.unit_selected,
+            view.pending_unit_action,
+            theme,
+            regions,
+        ),
+        Pane::ActiveAlerts => {
+            widgets::render_active_alerts_widget(frame, area, &system.alerts, theme)
+        }
+        Pane::ResolvedAlerts => {
+            widgets::render_resolved_alerts_widget(frame, area, &system.alerts, theme)
+        }
+        Pane::AlertActions => {
+            widgets::render_alert_actions_widget(frame, area, &system.alerts, theme)
+        }
+        Pane::CpuCores => widgets::render_cpu_cores_widget(frame, area, &system.cpu, theme),
+        Pane::MemoryDetails => {
+            widgets::render_memory_details_widget(frame, area, &system.memory, config, theme)
+        }
+        Pane::ProcessDetails => widgets::render_process_details_widget(
+            frame,
+            area,
+            &system.processes,
+            config,
+            view.process_selected,
+            theme,
+        ),
+        Pane::Sensors => widgets::render_sensors_widget(frame, area, &system.sensors, theme),
+    }
+
+    regions.add_pane(pane, area);
+    if view.focused == Some(pane) {
+        widgets::render_focus_border(frame, area, theme);
+    }
 }
@@ -2,5 +2,11 @@ pub mod app;
 pub mod widgets;
 pub mod theme;
 pub mod layout;
+pub mod tree;
+pub mod keymap;
+pub mod palette;
+pub mod mouse;
+pub mod graph;
 
 pub use app::App;
+pub use keymap::Keymap;
@@ -0,0 +1,135 @@
+use std::time::{Duration, Instant};
+
+use ratatui::layout::Rect;
+
+use crate::ui::keymap::Action;
+use crate::ui::layout::Pane;
+
+const DOUBLE_CLICK: Duration = Duration::from_millis(400);
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum StatusTarget {
+    Action(Action),
+    Palette(&'static str),
+}
+
+#[derive(Debug, Clone, Copy)]
+struct RowRegion {
+    pane: Pane,
+    area: Rect,
+    offset: usize,
+}
+
+#[derive(Debug, Default)]
+pub struct Regions {
+    panes: Vec<(Pane, Rect)>,
+    rows: Vec<RowRegion>,
+    sort_headers: Vec<(Rect, &'static str)>,
+    status: Vec<(Rect, StatusTarget)>,
+    tabs: Vec<(Rect, usize)>,
+    process_pids: Vec<u32>,
+}
+
+fn contains(area: Rect, column: u16, row: u16) -> bool {
+    column >= area.x && column < area.x + area.width && row >= area.y && row < area.y + area.height
+}
+
+impl Regions {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn clear(&mut self) {
+        self.panes.clear();
+        self.rows.clear();
+        self.sort_headers.clear();
+        self.status.clear();
+        self.tabs.clear();
+        self.process_pids.clear();
+    }
+
+    pub fn add_pane(&mut self, pane: Pane, area: Rect) {
+        self.panes.push((pane, area));
+    }
+
+    pub fn add_rows(&mut self, pane: Pane, area: Rect, offset: usize) {
+        self.rows.push(RowRegion { pane, area, offset });
+    }
+
+    pub fn add_sort_header(&mut self, area: Rect, key: &'static str) {
+        self.sort_headers.push((area, key));
+    }
+
+    pub fn add_status(&mut self, area: Rect, target: StatusTarget) {
+        self.status.push((area, target));
+    }
+
+    pub fn add_tab(&mut self, area: Rect, index: usize) {
+        self.tabs.push((area, index));
+    }
+
+    pub fn set_process_pids(&mut self, pids: Vec<u32>) {
+        self.process_pids = pids;
+    }
+
+    pub fn get_process_pids(&self) -> &[u32] {
+        &self.process_pids
+    }
+
+    pub fn pane_at(&self, column: u16, row: u16) -> Option<Pane> {
+        self.panes
+            .iter()
+            .find(|(_, area)| contains(*area, column, row))
+            .map(|(pane, _)| *pane)
+    }
+
+    pub fn row_at(&self, column: u16, row: u16) -> Option<(Pane, usize)> {
+        self.rows
+            .iter()
+            .find(|region| contains(region.area, column, row))
+            .map(|region| (region.pane, region.offset + (row - region.area.y) as usize))
+    }
+
+    pub fn sort_header_at(&self, column: u16, row: u16) -> Option<&'static str> {
+        self.sort_headers
+            .iter()
+            .find(|(area, _)| contains(*area, column, row))
+            .map(|(_, key)| *key)
+    }
+
+    pub fn status_at(&self, column: u16, row: u16) -> Option<&StatusTarget> {
+        self.status
+            .iter()
+            .find(|(area, _)| contains(*area, column, row))
+            .map(|(_, target)| target)
+    }
+
+    pub fn tab_at(&self, column: u16, row: u16) -> Option<usize> {
+        self.tabs
+            .iter()
+            .find(|(area, _)| contains(*area, column, row))
+            .map(|(_, index)| *index)
+    }
+}
+
+#[derive(Debug, Default)]
+pub struct ClickTracker {
+    last: Option<(Instant, Pane)>,
+}
+
+impl ClickTracker {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn is_double_click(&mut self, pane: Pane) -> bool {
+        let double =
+            matches!(self.last, Some((at, last)) if last == pane && at.elapsed() < DOUBLE_CLICK);
+        self.last = if double {
+            None
+        } else {
+            Some((Instant::now(), pane))
+        };
+        double
+    }
+}
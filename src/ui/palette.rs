@@ -0,0 +1,320 @@
+use std::cmp::Reverse;
+
+use sysinfo::PidExt;
+
+use crate::system::ProcessList;
+use crate::ui::keymap::Action;
+use crate::ui::layout::LayoutSet;
+
+const THEMES: [&str; 4] = ["default", "dark", "light", "custom"];
+
+const SORT_KEYS: [(&str, &str); 4] = [
+    ("cpu", "CPU usage"),
+    ("mem", "memory usage"),
+    ("read", "disk read rate"),
+    ("write", "disk write rate"),
+];
+
+const SIGNALS: [(&str, i32); 8] = [
+    ("TERM", libc::SIGTERM),
+    ("KILL", libc::SIGKILL),
+    ("HUP", libc::SIGHUP),
+    ("INT", libc::SIGINT),
+    ("STOP", libc::SIGSTOP),
+    ("CONT", libc::SIGCONT),
+    ("USR1", libc::SIGUSR1),
+    ("USR2", libc::SIGUSR2),
+];
+
+const KILL_CANDIDATES: usize = 50;
+pub const VISIBLE_CANDIDATES: usize = 10;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Command {
+    Action(Action),
+    Sort(String),
+    Filter(Option<String>),
+    Layout(String),
+    Theme(String),
+    Kill(libc::pid_t, i32),
+}
+
+impl Command {
+    pub fn parse(input: &str) -> Result<Self, String> {
+        let words: Vec<&str> = input.split_whitespace().collect();
+        let (name, args) = match words.split_first() {
+            Some((name, args)) => (name.to_lowercase().replace('-', "_"), args),
+            None => return Err("empty command".to_string()),
+        };
+
+        match name.as_str() {
+            "sort" => match args {
+                [key] => {
+                    let key = key.to_lowercase();
+                    match key.as_str() {
+                        "cpu" | "read" | "write" => Ok(Command::Sort(key)),
+                        "mem" | "memory" => Ok(Command::Sort("memory".to_string())),
+                        _ => Err(format!("unknown sort key `{}`", key)),
+                    }
+                }
+                _ => Err("usage: sort cpu|mem|read|write".to_string()),
+            },
+            "filter" => match args.iter().find(|arg| arg.ends_with(':')) {
+                Some(arg) => Err(format!("missing value for `{}`", arg)),
+                None if args.is_empty() => Ok(Command::Filter(None)),
+                None => Ok(Command::Filter(Some(args.join(" ")))),
+            },
+            "layout" => match args {
+                [layout] => Ok(Command::Layout(layout.to_string())),
+                _ => Err("usage: layout <name>".to_string()),
+            },
+            "theme" => match args {
+                [theme] if THEMES.contains(&theme.to_lowercase().as_str()) => {
+                    Ok(Command::Theme(theme.to_lowercase()))
+                }
+                [theme] => Err(format!("unknown theme `{}`", theme)),
+                _ => Err("usage: theme default|dark|light|custom".to_string()),
+            },
+            "kill" => {
+                let (pid, signal) = match args {
+                    [pid] => (pid, libc::SIGTERM),
+                    [pid, signal] => (pid, parse_signal(signal)?),
+                    _ => return Err("usage: kill <pid> [signal]".to_string()),
+                };
+                let pid = pid
+                    .parse::<libc::pid_t>()
+                    .ok()
+                    .filter(|pid| *pid > 0)
+                    .ok_or_else(|| format!("invalid pid `{}`", pid))?;
+                Ok(Command::Kill(pid, signal))
+            }
+            _ => match Action::from_name(&name) {
+                Some(action) if args.is_empty() => Ok(Command::Action(action)),
+                Some(action) => Err(format!("`{}` takes no arguments", action.name())),
+                None => Err(format!("unknown command `{}`", name)),
+            },
+        }
+    }
+}
+
+fn parse_signal(text: &str) -> Result<i32, String> {
+    if let Ok(number) = text.parse::<i32>() {
+        return match number {
+            number if number > 0 => Ok(number),
+            _ => Err(format!("invalid signal `{}`", text)),
+        };
+    }
+    let upper = text.to_uppercase();
+    let name = upper.strip_prefix("SIG").unwrap_or(&upper);
+    SIGNALS
+        .iter()
+        .find(|(signal, _)| *signal == name)
+        .map(|(_, number)| *number)
+        .ok_or_else(|| format!("unknown signal `{}`", text))
+}
+
+pub fn signal_name(signal: i32) -> String {
+    SIGNALS
+        .iter()
+        .find(|(_, number)| *number == signal)
+        .map(|(name, _)| format!("SIG{}", name))
+        .unwrap_or_else(|| signal.to_string())
+}
+
+#[derive(Debug, Clone)]
+pub struct Candidate {
+    pub text: String,
+    pub hint: String,
+}
+
+impl Candidate {
+    fn new(text: impl Into<String>, hint: impl Into<String>) -> Self {
+        Self {
+            text: text.into(),
+            hint: hint.into(),
+        }
+    }
+}
+
+pub fn candidates(processes: &ProcessList, layouts: &LayoutSet) -> Vec<Candidate> {
+    let mut candidates = Vec::new();
+    for (key, label) in SORT_KEYS {
+        candidates.push(Candidate::new(
+            format!("sort {}", key),
+            format!("Sort processes by {}", label),
+        ));
+    }
+    candidates.push(Candidate::new("filter user:", "Show processes of a user"));
+    candidates.push(Candidate::new(
+        "filter name:",
+        "Show processes whose name matches",
+    ));
+    candidates.push(Candidate::new(
+        "filter cgroup:",
+        "Show processes in a cgroup",
+    ));
+    candidates.push(Candidate::new("filter", "Clear process filters"));
+    for layout in layouts.get_layouts() {
+        candidates.push(Candidate::new(
+            format!("layout {}", layout.name),
+            format!("Switch to the {} layout", layout.title),
+        ));
+    }
+    for theme in THEMES {
+        candidates.push(Candidate::new(
+            format!("theme {}", theme),
+            format!("Switch to the {} theme", theme),
+        ));
+    }
+    for action in Action::ALL {
+        if action != Action::CommandPalette {
+            candidates.push(Candidate::new(action.name(), action.description()));
+        }
+    }
+    for process in processes.get_sorted_by_cpu(Some(KILL_CANDIDATES)) {
+        candidates.push(Candidate::new(
+            format!("kill {}", process.get_pid().as_u32()),
+            format!("Send SIGTERM to {}", process.get_name()),
+        ));
+    }
+    candidates
+}
+
+fn fuzzy_score(query: &str, text: &str) -> Option<i64> {
+    let text: Vec<char> = text.to_lowercase().chars().collect();
+    let mut score = 0;
+    let mut position = 0;
+    let mut previous: Option<usize> = None;
+
+    for c in query.to_lowercase().chars().filter(|c| !c.is_whitespace()) {
+        let found = text[position..].iter().position(|t| *t == c)? + position;
+        score += 1;
+        if previous.map(|p| p + 1 == found).unwrap_or(found == 0) {
+            score += 5;
+        }
+        if found == 0 || matches!(text[found - 1], ' ' | '_' | ':') {
+            score += 3;
+        }
+        previous = Some(found);
+        position = found + 1;
+    }
+    Some(score * 100 - text.len() as i64)
+}
+
+#[derive(Debug, Default)]
+pub struct Palette {
+    input: String,
+    selected: usize,
+    error: Option<String>,
+}
+
+impl Palette {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_input(input: &str) -> Self {
+        Self {
+            input: input.to_string(),
+            ..Self::default()
+        }
+    }
+
+    pub fn get_input(&self) -> &str {
+        &self.input
+    }
+
+    pub fn get_selected(&self) -> usize {
+        self.selected
+    }
+
+    pub fn get_error(&self) -> Option<&str> {
+        self.error.as_deref()
+    }
+
+    pub fn set_error(&mut self, error: String) {
+        self.error = Some(error);
+    }
+
+    pub fn push(&mut self, c: char) {
+        self.input.push(c);
+        self.selected = 0;
+        self.error = None;
+    }
+
+    pub fn pop(&mut self) {
+        self.input.pop();
+        self.selected = 0;
+        self.error = None;
+    }
+
+    pub fn select_next(&mut self, count: usize) {
+        if count > 0 {
+            self.selected = (self.selected + 1) % count;
+        }
+    }
+
+    pub fn select_previous(&mut self, count: usize) {
+        if count > 0 {
+            self.selected = (self.selected + count - 1) % count;
+        }
+    }
+
+    pub fn complete(&mut self, candidate: &Candidate) {
+        self.input = candidate.text.clone();
+        if !self.input.ends_with(':') {
+            self.input.push(' ');
+        }
+        self.selected = 0;
+        self.error = None;
+    }
+
+    pub fn matches<'a>(&self, candidates: &'a [Candidate]) -> Vec<&'a Candidate> {
+        let mut scored: Vec<(i64, &Candidate)> = candidates
+            .iter()
+            .filter_map(|candidate| {
+                fuzzy_score(&self.input, &candidate.text).map(|score| (score, candidate))
+            })
+            .collect();
+        scored.sort_by_key(|(score, _)| Reverse(*score));
+        scored.into_iter().map(|(_, candidate)| candidate).collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn kill_parses_pid_and_signal() {
+        assert_eq!(
+            Command::parse("kill 42"),
+            Ok(Command::Kill(42, libc::SIGTERM))
+        );
+        assert_eq!(
+            Command::parse("kill 42 SIGKILL"),
+            Ok(Command::Kill(42, libc::SIGKILL))
+        );
+        assert_eq!(Command::parse("kill 42 1"), Ok(Command::Kill(42, 1)));
+    }
+
+    #[test]
+    fn kill_rejects_pids_that_target_groups() {
+        for pid in ["0", "-1", "-42", "2147483648", "4294967295"] {
+            assert_eq!(
+                Command::parse(&format!("kill {}", pid)),
+                Err(format!("invalid pid `{}`", pid))
+            );
+        }
+    }
+
+    #[test]
+    fn kill_rejects_non_positive_signals() {
+        for signal in ["0", "-9"] {
+            assert_eq!(
+                Command::parse(&format!("kill 42 {}", signal)),
+                Err(format!("invalid signal `{}`", signal))
+            );
+        }
+    }
+}
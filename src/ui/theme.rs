@@ -1,4 +1,5 @@
-use ratatui::style::Color;
+use crate::alerts::Severity;
+use ratatui::style::{Color, Modifier, Style};
 use std::collections::HashMap;
 
 pub struct Theme {
@@ -39,11 +40,28 @@ impl Theme {
         self.colors.insert("disk_low".to_string(), Color::Green);
         self.colors.insert("disk_medium".to_string(), Color::Yellow);
         self.colors.insert("disk_high".to_string(), Color::Red);
+        self.colors.insert("pressure_low".to_string(), Color::Green);
+        self.colors
+            .insert("pressure_medium".to_string(), Color::Yellow);
+        self.colors.insert("pressure_high".to_string(), Color::Red);
+        self.colors
+            .insert("temperature_low".to_string(), Color::Green);
+        self.colors
+            .insert("temperature_medium".to_string(), Color::Yellow);
+        self.colors
+            .insert("temperature_high".to_string(), Color::Red);
         self.colors.insert("network_rx".to_string(), Color::Blue);
         self.colors.insert("network_tx".to_string(), Color::Magenta);
+        self.colors.insert("network_error".to_string(), Color::Red);
+        self.colors.insert("alert_info".to_string(), Color::Cyan);
+        self.colors
+            .insert("alert_warning".to_string(), Color::Yellow);
+        self.colors.insert("alert_critical".to_string(), Color::Red);
         self.colors
             .insert("process_selected".to_string(), Color::Cyan);
         self.colors.insert("border".to_string(), Color::Gray);
+        self.colors
+            .insert("border_focused".to_string(), Color::Cyan);
         self.colors.insert("tab_active".to_string(), Color::Cyan);
         self.colors.insert("tab_inactive".to_string(), Color::Gray);
     }
@@ -67,6 +85,9 @@ impl Theme {
         theme.colors.insert("foreground".to_string(), Color::Black);
         theme.colors.insert("header".to_string(), Color::Blue);
         theme.colors.insert("border".to_string(), Color::Gray);
+        theme
+            .colors
+            .insert("border_focused".to_string(), Color::Blue);
         theme.colors.insert("cpu_low".to_string(), Color::Green);
         theme.colors.insert("cpu_medium".to_string(), Color::Yellow);
         theme.colors.insert("cpu_high".to_string(), Color::Red);
@@ -117,7 +138,6 @@ impl Theme {
         }
     }
 
-    #[allow(dead_code)]
     pub fn disk_color(&self, usage: f32) -> Color {
         if usage < 70.0 {
             self.get_color("disk_low")
@@ -128,6 +148,45 @@ impl Theme {
         }
     }
 
+    pub fn alert_color(&self, severity: Severity) -> Color {
+        match severity {
+            Severity::Info => self.get_color("alert_info"),
+            Severity::Warning => self.get_color("alert_warning"),
+            Severity::Critical => self.get_color("alert_critical"),
+        }
+    }
+
+    pub fn pressure_color(&self, stalled: f64) -> Color {
+        if stalled < 10.0 {
+            self.get_color("pressure_low")
+        } else if stalled < 40.0 {
+            self.get_color("pressure_medium")
+        } else {
+            self.get_color("pressure_high")
+        }
+    }
+
+    pub fn temperature_color(&self, celsius: f32, critical: Option<f32>) -> Color {
+        let critical = critical.filter(|critical| *critical > 0.0).unwrap_or(100.0);
+        if celsius < critical * 0.7 {
+            self.get_color("temperature_low")
+        } else if celsius < critical * 0.9 {
+            self.get_color("temperature_medium")
+        } else {
+            self.get_color("temperature_high")
+        }
+    }
+
+    pub fn tab_style(&self, active: bool) -> Style {
+        if active {
+            Style::default()
+                .fg(self.get_color("tab_active"))
+                .add_modifier(Modifier::BOLD | Modifier::REVERSED)
+        } else {
+            Style::default().fg(self.get_color("tab_inactive"))
+        }
+    }
+
     pub fn header_color(&self) -> Color {
         self.get_color("header")
     }
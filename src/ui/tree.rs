@@ -0,0 +1,132 @@
+use std::collections::HashSet;
+
+#[derive(Debug, Default)]
+pub struct TreeState {
+    selected: usize,
+    collapsed: HashSet<String>,
+}
+
+impl TreeState {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn get_selected(&self) -> usize {
+        self.selected
+    }
+
+    pub fn select(&mut self, index: usize) {
+        self.selected = index;
+    }
+
+    pub fn is_collapsed(&self, key: &str) -> bool {
+        self.collapsed.contains(key)
+    }
+
+    pub fn toggle(&mut self, key: &str) {
+        if !self.collapsed.remove(key) {
+            self.collapsed.insert(key.to_string());
+        }
+    }
+}
+
+pub struct TreeRow<'a, T> {
+    pub key: String,
+    pub prefix: String,
+    pub has_children: bool,
+    pub collapsed: bool,
+    pub item: &'a T,
+}
+
+pub fn flatten<'a, T>(
+    roots: &'a [T],
+    children: fn(&'a T) -> &'a [T],
+    name: fn(&T) -> &str,
+    state: &TreeState,
+) -> Vec<TreeRow<'a, T>> {
+    let mut rows = Vec::new();
+    for (idx, root) in roots.iter().enumerate() {
+        flatten_node(
+            root,
+            children,
+            name,
+            state,
+            String::new(),
+            "",
+            0,
+            idx + 1 == roots.len(),
+            &mut rows,
+        );
+    }
+    rows
+}
+
+#[allow(clippy::too_many_arguments)]
+fn flatten_node<'a, T>(
+    node: &'a T,
+    children: fn(&'a T) -> &'a [T],
+    name: fn(&T) -> &str,
+    state: &TreeState,
+    parent_key: String,
+    indent: &str,
+    depth: usize,
+    is_last: bool,
+    rows: &mut Vec<TreeRow<'a, T>>,
+) {
+    let key = if parent_key.is_empty() {
+        name(node).to_string()
+    } else {
+        format!("{}/{}", parent_key, name(node))
+    };
+    let node_children = children(node);
+    let collapsed = state.is_collapsed(&key);
+
+    let branch = if depth == 0 {
+        ""
+    } else if is_last {
+        "└─"
+    } else {
+        "├─"
+    };
+    let marker = if node_children.is_empty() {
+        "  "
+    } else if collapsed {
+        "▸ "
+    } else {
+        "▾ "
+    };
+
+    rows.push(TreeRow {
+        key: key.clone(),
+        prefix: format!("{}{}{}", indent, branch, marker),
+        has_children: !node_children.is_empty(),
+        collapsed,
+        item: node,
+    });
+
+    if collapsed {
+        return;
+    }
+
+    let child_indent = if depth == 0 {
+        String::new()
+    } else if is_last {
+        format!("{}  ", indent)
+    } else {
+        format!("{}│ ", indent)
+    };
+
+    for (idx, child) in node_children.iter().enumerate() {
+        flatten_node(
+            child,
+            children,
+            name,
+            state,
+            key.clone(),
+            &child_indent,
+            depth + 1,
+            idx + 1 == node_children.len(),
+            rows,
+        );
+    }
+}
@@ -1,15 +1,37 @@
 use ratatui::{
+    buffer::Buffer,
     layout::{Constraint, Direction, Layout, Rect},
     style::{Color, Modifier, Style},
-    symbols,
     text::{Line, Span},
-    widgets::{Axis, Block, Borders, Chart, Dataset, Gauge, GraphType, Paragraph, Row, Table},
+    widgets::{Block, Borders, Clear, Gauge, Paragraph, Row, Table, TableState, Widget},
     Frame,
 };
 
-use crate::config::Config;
-use crate::system::{CpuState, DiskState, MemoryState, NetworkState, ProcessList};
+use crate::alerts::{AlertState, Severity};
+use crate::config::{Config, NetworkConfig};
+use crate::system::blocktree::BlockNode;
+use crate::system::cgroup::CgroupNode;
+use crate::system::connections::{Connection, ConnectionFilter};
+use crate::system::network::{InterfaceSort, NetworkInterface};
+use crate::system::pressure::PressureResource;
+use crate::system::process::{MemoryColumn, ProcessFilter, SortKey};
+use crate::system::systemd::UnitAction;
+use crate::system::vmstat::VmCounter;
+use crate::system::{
+    BlockTree, CgroupState, ConnectionState, ContainerState, CpuState, DiskIoState, DiskState,
+    MemoryState, NetTrafficState, NetworkState, PressureState, ProcessList, SensorState,
+    SystemdState, VmStatState,
+};
+use crate::ui::graph::TimeChart;
+use crate::ui::keymap::{digit_label, Action, Keymap};
+use crate::ui::layout::Pane;
+use crate::ui::mouse::{Regions, StatusTarget};
+use crate::ui::palette::{Candidate, Palette, VISIBLE_CANDIDATES};
 use crate::ui::theme::Theme;
+use crate::ui::tree::{self, TreeRow, TreeState};
+use crate::util::{time, Units};
+use std::time::{SystemTime, UNIX_EPOCH};
+use sysinfo::PidExt;
 
 pub fn render_cpu_widget<B: ratatui::backend::Backend>(
     f: &mut Frame<B>,
@@ -35,12 +57,70 @@ pub fn render_cpu_widget<B: ratatui::backend::Backend>(
     f.render_widget(gauge, chunks[0]);
 }
 
+pub fn render_cpu_cores_widget<B: ratatui::backend::Backend>(
+    f: &mut Frame<B>,
+    area: Rect,
+    cpu: &CpuState,
+    theme: &Theme,
+) {
+    let (one, five, fifteen) = cpu.get_load_average();
+    let mut title = format!("CPU Cores (load {:.2} {:.2} {:.2})", one, five, fifteen);
+    if !cpu.get_brand().is_empty() {
+        title = format!("{} {}", title, cpu.get_brand());
+    }
+    let block = Block::default().borders(Borders::ALL).title(title);
+    let inner = block.inner(area);
+    f.render_widget(block, area);
+
+    let count = cpu.get_core_count();
+    if count == 0 || inner.height == 0 {
+        return;
+    }
+    let rows = (inner.height as usize).min(count);
+    let columns = count.div_ceil(rows);
+    let cell_width = inner.width as usize / columns;
+    let bar_width = cell_width.saturating_sub(27);
+
+    let lines: Vec<Line> = (0..rows)
+        .map(|row| {
+            let mut spans = Vec::new();
+            for core in (0..columns).map(|column| column * rows + row) {
+                let usage = match cpu.get_core_usage(core) {
+                    Some(usage) => usage,
+                    None => continue,
+                };
+                let filled = (bar_width as f32 * usage / 100.0).round() as usize;
+                let frequency = cpu
+                    .get_core_frequency(core)
+                    .map(|mhz| format!("{:>5} MHz", mhz))
+                    .unwrap_or_default();
+                spans.push(Span::raw(format!("{:<6}[", format!("cpu{}", core))));
+                spans.push(Span::styled(
+                    "|".repeat(filled.min(bar_width)),
+                    Style::default().fg(theme.cpu_color(usage)),
+                ));
+                spans.push(Span::raw(format!(
+                    "{}]{:>6.1}% {:<10} ",
+                    " ".repeat(bar_width.saturating_sub(filled)),
+                    usage,
+                    frequency
+                )));
+            }
+            Line::from(spans)
+        })
+        .collect();
+
+    f.render_widget(Paragraph::new(lines), inner);
+}
+
 pub fn render_memory_widget<B: ratatui::backend::Backend>(
     f: &mut Frame<B>,
     area: Rect,
     memory: &MemoryState,
+    config: &Config,
     theme: &Theme,
 ) {
+    let units = Units::from_config(&config.units);
     let memory_usage = (memory.get_memory_usage_percent() as u16).min(100);
     let swap_usage = (memory.get_swap_usage_percent() as u16).min(100);
 
@@ -53,102 +133,429 @@ pub fn render_memory_widget<B: ratatui::backend::Backend>(
     let memory_gauge = Gauge::default()
         .block(Block::default().title("Memory").borders(Borders::ALL))
         .gauge_style(Style::default().fg(theme.memory_color(memory_usage as f32)))
-        .percent(memory_usage);
+        .percent(memory_usage)
+        .label(format!(
+            "{} / {}",
+            units.bytes(memory.get_used_memory()),
+            units.bytes(memory.get_total_memory())
+        ));
 
     let swap_gauge = Gauge::default()
         .block(Block::default().title("Swap").borders(Borders::ALL))
         .gauge_style(Style::default().fg(theme.memory_color(swap_usage as f32)))
-        .percent(swap_usage);
+        .percent(swap_usage)
+        .label(format!(
+            "{} / {}",
+            units.bytes(memory.get_used_swap()),
+            units.bytes(memory.get_total_swap())
+        ));
 
     f.render_widget(memory_gauge, chunks[0]);
     f.render_widget(swap_gauge, chunks[1]);
 }
 
-pub fn render_process_widget<B: ratatui::backend::Backend>(
+pub fn render_memory_details_widget<B: ratatui::backend::Backend>(
     f: &mut Frame<B>,
     area: Rect,
-    processes: &ProcessList,
+    memory: &MemoryState,
     config: &Config,
     theme: &Theme,
 ) {
-    let block = Block::default().borders(Borders::ALL).title("Processes");
-
-    let header_cells = ["PID", "CPU%", "MEM", "Name", "Status"]
+    let units = Units::from_config(&config.units);
+    let total = memory.get_total_memory();
+    let header_cells = ["Memory", "Size", "% of RAM"]
         .iter()
         .map(|h| Span::styled(*h, Style::default().fg(theme.header_color())));
     let header = Row::new(header_cells).style(Style::default());
 
-    let processes = if config.sort_by == "cpu" {
-        processes.get_sorted_by_cpu(Some(area.height as usize - 3))
-    } else {
-        processes.get_sorted_by_memory(Some(area.height as usize - 3))
+    let percent = |bytes: u64| {
+        if total == 0 {
+            "-".to_string()
+        } else {
+            format!("{:.1}%", bytes as f64 / total as f64 * 100.0)
+        }
     };
+    let mut entries = vec![("Total", total), ("Used", memory.get_used_memory())];
+    entries.extend(memory.get_details().iter().copied());
+    entries.push(("Swap total", memory.get_total_swap()));
+    entries.push(("Swap used", memory.get_used_swap()));
+
+    let rows = entries.into_iter().map(|(label, bytes)| {
+        Row::new(vec![
+            Span::raw(label),
+            Span::raw(units.bytes(bytes)),
+            Span::raw(percent(bytes)),
+        ])
+    });
+
+    let widths = [
+        Constraint::Length(14),
+        Constraint::Length(12),
+        Constraint::Length(9),
+    ];
+    let table = Table::new(rows)
+        .header(header)
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .title("Memory Details"),
+        )
+        .widths(&widths);
+
+    f.render_widget(table, area);
+}
+
+#[allow(clippy::too_many_arguments)]
+pub fn render_process_widget<B: ratatui::backend::Backend>(
+    f: &mut Frame<B>,
+    area: Rect,
+    processes: &ProcessList,
+    traffic: &NetTrafficState,
+    containers: &ContainerState,
+    config: &Config,
+    selected: Option<u32>,
+    theme: &Theme,
+    regions: &mut Regions,
+) {
+    let units = Units::from_config(&config.units);
+    let show_containers = containers.has_containers();
+    let column = MemoryColumn::from_name(&config.process.memory_column);
+    let sort_key = SortKey::from_name(&config.sort_by, column);
+    let attributed = traffic.is_attributed();
+
+    let filter = ProcessFilter::parse(&config.filters);
+
+    let mut title = format!("Processes (sort: {}", sort_key.name());
+    for part in filter.describe() {
+        title.push_str(", ");
+        title.push_str(&part);
+    }
+    title.push(')');
+    let block = Block::default().borders(Borders::ALL).title(title);
+
+    let mut header_cells = vec!["PID", "CPU%", column.label(), "READ/s", "WRITE/s"];
+    if attributed {
+        header_cells.extend(["NET RX/s", "NET TX/s"]);
+    } else {
+        header_cells.push("CONNS");
+    }
+    if show_containers {
+        header_cells.push("Container");
+    }
+    header_cells.extend(["Name", "Status"]);
+    let header = Row::new(
+        header_cells
+            .into_iter()
+            .map(|h| Span::styled(h, Style::default().fg(theme.header_color()))),
+    )
+    .style(Style::default());
+
+    let limit = (area.height as usize).saturating_sub(3);
+    let processes = processes.get_sorted(sort_key, &filter, Some(limit));
 
     let rows = processes.iter().map(|p| {
         let pid = p.get_pid().to_string();
         let cpu = format!("{:.1}%", p.get_cpu_usage());
-        let mem = format!("{} MB", p.get_memory_usage() / 1024);
+        let mem = match p.get_memory(column) {
+            Some(bytes) => units.bytes(bytes),
+            None => "-".to_string(),
+        };
+        let read = units.rate(p.get_read_rate());
+        let write = units.rate(p.get_write_rate());
         let name = p.get_name();
         let status = p.get_status();
-
-        let row_data = vec![pid, cpu, mem, name.to_string(), status.to_string()];
-        let cells = row_data.into_iter().map(|c| Span::raw(c));
+        let net = traffic.get_process(p.get_pid().as_u32());
+
+        let mut row_data = vec![pid, cpu, mem, read, write];
+        if attributed {
+            row_data.push(units.network_rate(net.map(|t| t.rx_rate).unwrap_or(0.0)));
+            row_data.push(units.network_rate(net.map(|t| t.tx_rate).unwrap_or(0.0)));
+        } else {
+            row_data.push(net.map(|t| t.connections).unwrap_or(0).to_string());
+        }
+        if show_containers {
+            row_data.push(
+                containers
+                    .get_process_label(p.get_pid().as_u32())
+                    .unwrap_or_else(|| "-".to_string()),
+            );
+        }
+        row_data.push(name.to_string());
+        row_data.push(status.to_string());
+        let cells = row_data.into_iter().map(Span::raw);
 
         Row::new(cells)
     });
 
-    let widths = [
+    let mut widths = vec![
         Constraint::Length(7),
         Constraint::Length(6),
-        Constraint::Length(9),
-        Constraint::Percentage(70),
-        Constraint::Length(8),
+        Constraint::Length(10),
+        Constraint::Length(11),
+        Constraint::Length(11),
+    ];
+    if attributed {
+        widths.extend([Constraint::Length(11), Constraint::Length(11)]);
+    } else {
+        widths.push(Constraint::Length(6));
+    }
+    if show_containers {
+        widths.push(Constraint::Length(16));
+    }
+    widths.extend([Constraint::Percentage(50), Constraint::Length(8)]);
+
+    let pids: Vec<u32> = processes.iter().map(|p| p.get_pid().as_u32()).collect();
+    let mut table_state = TableState::default();
+    table_state.select(selected.and_then(|pid| pids.iter().position(|p| *p == pid)));
+
+    let highlight_symbol = ">> ";
+    let body = table_body(area);
+    let mut x = body.x;
+    if table_state.selected().is_some() {
+        x += highlight_symbol.len() as u16;
+    }
+    let sort_columns = [
+        None,
+        Some("cpu"),
+        Some("memory"),
+        Some("read"),
+        Some("write"),
     ];
+    for (key, width) in sort_columns.iter().zip(&widths) {
+        if let (Some(key), Constraint::Length(width)) = (key, width) {
+            let header = Rect::new(x, body.y.saturating_sub(1), *width, 1);
+            regions.add_sort_header(header.intersection(area), key);
+        }
+        if let Constraint::Length(width) = width {
+            x += width + 1;
+        }
+    }
 
     let table = Table::new(rows)
         .header(header)
         .block(block)
         .widths(&widths)
-        .highlight_style(Style::default().add_modifier(Modifier::BOLD))
-        .highlight_symbol(">> ");
+        .highlight_style(
+            Style::default()
+                .fg(theme.get_color("process_selected"))
+                .add_modifier(Modifier::BOLD),
+        )
+        .highlight_symbol(highlight_symbol);
 
-    f.render_widget(table, area);
+    f.render_stateful_widget(table, area, &mut table_state);
+    regions.add_rows(Pane::Processes, body, table_state.offset());
+    regions.set_process_pids(pids);
+}
+
+fn table_body(area: Rect) -> Rect {
+    let inner = Block::default().borders(Borders::ALL).inner(area);
+    Rect::new(
+        inner.x,
+        inner.y.saturating_add(1).min(inner.bottom()),
+        inner.width,
+        inner.height.saturating_sub(1),
+    )
+}
+
+struct FocusBorder {
+    style: Style,
+}
+
+impl Widget for FocusBorder {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        if area.width == 0 || area.height == 0 {
+            return;
+        }
+        for x in area.left()..area.right() {
+            buf.get_mut(x, area.top()).set_style(self.style);
+            buf.get_mut(x, area.bottom() - 1).set_style(self.style);
+        }
+        for y in area.top()..area.bottom() {
+            buf.get_mut(area.left(), y).set_style(self.style);
+            buf.get_mut(area.right() - 1, y).set_style(self.style);
+        }
+    }
+}
+
+pub fn render_process_details_widget<B: ratatui::backend::Backend>(
+    f: &mut Frame<B>,
+    area: Rect,
+    processes: &ProcessList,
+    config: &Config,
+    selected: Option<u32>,
+    theme: &Theme,
+) {
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .title("Process Details");
+    let process = match selected {
+        Some(pid) => processes.get_process(&sysinfo::Pid::from_u32(pid)),
+        None => processes.get_sorted_by_cpu(Some(1)).into_iter().next(),
+    };
+    let process = match process {
+        Some(process) => process,
+        None => {
+            f.render_widget(Paragraph::new("No process selected").block(block), area);
+            return;
+        }
+    };
+
+    let units = Units::from_config(&config.units);
+    let field = |name: &str, value: String| {
+        Line::from(vec![
+            Span::styled(
+                format!("{:<10}", name),
+                Style::default().fg(theme.header_color()),
+            ),
+            Span::raw(value),
+        ])
+    };
+    let mut lines = vec![
+        field("PID", process.get_pid().to_string()),
+        field("Name", process.get_name().to_string()),
+        field("User", process.get_user_id().unwrap_or("-").to_string()),
+        field("Status", process.get_status().to_string()),
+        field("CPU", format!("{:.1}%", process.get_cpu_usage())),
+    ];
+    for column in MemoryColumn::ALL {
+        if let Some(bytes) = process.get_memory(column) {
+            lines.push(field(column.label(), units.bytes(bytes)));
+        }
+    }
+    lines.extend([
+        field(
+            "Read",
+            format!(
+                "{} ({} total)",
+                units.rate(process.get_read_rate()),
+                units.bytes(process.get_total_read_bytes())
+            ),
+        ),
+        field(
+            "Write",
+            format!(
+                "{} ({} total)",
+                units.rate(process.get_write_rate()),
+                units.bytes(process.get_total_written_bytes())
+            ),
+        ),
+        field("Cgroup", process.get_cgroup().unwrap_or("-").to_string()),
+        field("Command", process.get_command().join(" ")),
+    ]);
+
+    let paragraph = Paragraph::new(lines)
+        .block(block)
+        .wrap(ratatui::widgets::Wrap { trim: false });
+    f.render_widget(paragraph, area);
+}
+
+pub fn render_focus_border<B: ratatui::backend::Backend>(
+    f: &mut Frame<B>,
+    area: Rect,
+    theme: &Theme,
+) {
+    let style = Style::default()
+        .fg(theme.get_color("border_focused"))
+        .add_modifier(Modifier::BOLD);
+    f.render_widget(FocusBorder { style }, area);
 }
 
 pub fn render_disk_widget<B: ratatui::backend::Backend>(
     f: &mut Frame<B>,
     area: Rect,
     disk: &DiskState,
+    config: &Config,
     theme: &Theme,
 ) {
-    let block = Block::default().borders(Borders::ALL).title("Disk Usage");
+    let units = Units::from_config(&config.units);
+    let title = if disk.is_hiding_pseudo() {
+        "Disk Usage"
+    } else {
+        "Disk Usage (all mounts)"
+    };
+    let block = Block::default().borders(Borders::ALL).title(title);
 
-    let header_cells = ["Mount", "Size", "Used", "Avail", "Use%", "FS"]
-        .iter()
-        .map(|h| Span::styled(*h, Style::default().fg(theme.header_color())));
+    let header_cells = [
+        "Mount", "Size", "Used", "Avail", "Use%", "Inode%", "FS", "Flags", "Full in", "Growth/m",
+    ]
+    .iter()
+    .map(|h| Span::styled(*h, Style::default().fg(theme.header_color())));
     let header = Row::new(header_cells).style(Style::default());
 
     let disks = disk.get_disks();
     let rows = disks.iter().map(|d| {
         let mount = d.get_mount_point().to_string();
-        let size = format!("{:.1}G", d.get_total_space() as f64 / 1_073_741_824.0);
-        let used = format!("{:.1}G", d.get_used_space() as f64 / 1_073_741_824.0);
-        let avail = format!("{:.1}G", d.get_available_space() as f64 / 1_073_741_824.0);
+        let size = units.bytes(d.get_total_space());
+        let used = units.bytes(d.get_used_space());
+        let avail = units.bytes(d.get_available_space());
         let use_percent = format!("{:.1}%", d.get_usage_percent());
+        let inode_percent = match d.get_inode_usage_percent() {
+            Some(percent) => format!("{:.1}%", percent),
+            None => "-".to_string(),
+        };
         let fs = d.get_file_system().to_string();
 
-        let row_data = vec![mount, size, used, avail, use_percent, fs];
-        let cells = row_data.into_iter().map(|c| Span::raw(c));
+        let mut flags = Vec::new();
+        if d.is_read_only() {
+            flags.push("ro");
+        }
+        if d.is_noexec() {
+            flags.push("noexec");
+        }
 
-        Row::new(cells)
+        let full_in = match d.get_time_to_full() {
+            Some(eta) => format_duration(eta),
+            None => "-".to_string(),
+        };
+        let growth = match d.get_growth_rate() {
+            Some(rate) if rate.abs() >= 1.0 => {
+                let per_minute = rate * 60.0;
+                let sign = if per_minute < 0.0 { "-" } else { "+" };
+                format!("{}{}", sign, units.bytes(per_minute.abs() as u64))
+            }
+            _ => "-".to_string(),
+        };
+
+        let row_data = vec![
+            mount,
+            size,
+            used,
+            avail,
+            use_percent,
+            inode_percent,
+            fs,
+            flags.join(","),
+            full_in,
+            growth,
+        ];
+        let cells = row_data.into_iter().map(Span::raw);
+
+        let usage = d
+            .get_usage_percent()
+            .max(d.get_inode_usage_percent().unwrap_or(0.0));
+        if disk.is_filling_soon(d) {
+            Row::new(cells).style(
+                Style::default()
+                    .fg(theme.get_color("disk_high"))
+                    .add_modifier(Modifier::BOLD),
+            )
+        } else if usage >= 90.0 {
+            Row::new(cells).style(Style::default().fg(theme.disk_color(usage as f32)))
+        } else {
+            Row::new(cells)
+        }
     });
 
     let widths = [
         Constraint::Percentage(20),
-        Constraint::Length(8),
-        Constraint::Length(8),
-        Constraint::Length(8),
+        Constraint::Length(10),
+        Constraint::Length(10),
+        Constraint::Length(10),
         Constraint::Length(6),
+        Constraint::Length(7),
+        Constraint::Length(10),
+        Constraint::Length(9),
+        Constraint::Length(9),
         Constraint::Length(10),
     ];
 
@@ -157,40 +564,55 @@ pub fn render_disk_widget<B: ratatui::backend::Backend>(
     f.render_widget(table, area);
 }
 
-pub fn render_network_widget<B: ratatui::backend::Backend>(
+pub fn render_disk_io_widget<B: ratatui::backend::Backend>(
     f: &mut Frame<B>,
     area: Rect,
-    network: &NetworkState,
+    disk_io: &DiskIoState,
+    config: &Config,
     theme: &Theme,
 ) {
-    let block = Block::default().borders(Borders::ALL).title("Network");
-
-    let header_cells = ["Interface", "RX", "TX", "RX/s", "TX/s"]
-        .iter()
-        .map(|h| Span::styled(*h, Style::default().fg(theme.header_color())));
-    let header = Row::new(header_cells).style(Style::default());
-
-    let interfaces = network.get_interfaces();
-    let rows = interfaces.iter().map(|i| {
-        let name = i.get_name().to_string();
+    let units = Units::from_config(&config.units);
+    let block = Block::default().borders(Borders::ALL).title("Disk I/O");
 
-        let rx = format_bytes(i.get_received_bytes());
-        let tx = format_bytes(i.get_transmitted_bytes());
-        let rx_rate = format_bytes_rate(i.get_receive_rate());
-        let tx_rate = format_bytes_rate(i.get_transmit_rate());
+    if !disk_io.is_available() {
+        let paragraph = Paragraph::new("/proc/diskstats not available").block(block);
+        f.render_widget(paragraph, area);
+        return;
+    }
 
-        let row_data = vec![name, rx, tx, rx_rate, tx_rate];
-        let cells = row_data.into_iter().map(|c| Span::raw(c));
+    let header_cells = [
+        "Device", "Read/s", "Write/s", "rIOPS", "wIOPS", "Await", "Queue", "Util%",
+    ]
+    .iter()
+    .map(|h| Span::styled(*h, Style::default().fg(theme.header_color())));
+    let header = Row::new(header_cells).style(Style::default());
 
-        Row::new(cells)
+    let devices = disk_io.get_physical_devices();
+    let rows = devices.iter().map(|d| {
+        let row_data = vec![
+            d.get_name().to_string(),
+            units.rate(d.get_read_rate()),
+            units.rate(d.get_write_rate()),
+            format!("{:.0}", d.get_read_iops()),
+            format!("{:.0}", d.get_write_iops()),
+            format!("{:.1}ms", d.get_latency_ms()),
+            format!("{:.2}", d.get_queue_depth()),
+            format!("{:.1}%", d.get_utilization()),
+        ];
+        let cells = row_data.into_iter().map(Span::raw);
+
+        Row::new(cells).style(Style::default().fg(theme.disk_color(d.get_utilization() as f32)))
     });
 
     let widths = [
-        Constraint::Percentage(20),
-        Constraint::Length(10),
-        Constraint::Length(10),
-        Constraint::Length(10),
-        Constraint::Length(10),
+        Constraint::Percentage(15),
+        Constraint::Length(11),
+        Constraint::Length(11),
+        Constraint::Length(6),
+        Constraint::Length(6),
+        Constraint::Length(8),
+        Constraint::Length(6),
+        Constraint::Length(6),
     ];
 
     let table = Table::new(rows).header(header).block(block).widths(&widths);
@@ -198,318 +620,1796 @@ pub fn render_network_widget<B: ratatui::backend::Backend>(
     f.render_widget(table, area);
 }
 
-pub fn render_cpu_graph<B: ratatui::backend::Backend>(
+pub fn render_disk_io_graph<B: ratatui::backend::Backend>(
     f: &mut Frame<B>,
     area: Rect,
-    cpu: &CpuState,
+    disk_io: &DiskIoState,
+    config: &Config,
     theme: &Theme,
 ) {
-    let block = Block::default()
-        .title("CPU Usage History")
-        .borders(Borders::ALL);
-
-    let cpu_history = cpu.get_history();
-    if cpu_history.is_empty() {
-        f.render_widget(block, area);
-        return;
-    }
+    let units = Units::from_config(&config.units);
+    let read_history: Vec<f64> = disk_io.get_read_rate_history().iter().copied().collect();
+    let write_history: Vec<f64> = disk_io.get_write_rate_history().iter().copied().collect();
 
-    let data: Vec<(f64, f64)> = cpu_history
+    let chart = TimeChart::new("Disk I/O History", config);
+    let max_value = [&read_history, &write_history]
         .iter()
-        .enumerate()
-        .map(|(i, &value)| (i as f64, value as f64))
-        .collect();
-
-    let datasets = vec![Dataset::default()
-        .name("CPU %")
-        .marker(symbols::Marker::Braille)
-        .graph_type(GraphType::Line)
-        .style(Style::default().fg(theme.cpu_color(cpu.get_average_usage())))
-        .data(&data)];
-
-    let chart = Chart::new(datasets)
-        .block(block)
-        .x_axis(
-            Axis::default()
-                .title(Span::styled("Time", Style::default().fg(Color::Gray)))
-                .style(Style::default().fg(Color::Gray))
-                .bounds([0.0, cpu_history.len() as f64])
-                .labels(vec![]),
+        .flat_map(|history| history[history.len() - chart.visible_count(history)..].iter())
+        .fold(0.0_f64, |a, &b| f64::max(a, b));
+    let (top, labels) = units.rate_axis(max_value);
+
+    let chart = chart
+        .series(
+            format!("Read: {}", units.rate(disk_io.get_total_read_rate())),
+            Style::default().fg(theme.get_color("network_rx")),
+            read_history,
         )
-        .y_axis(
-            Axis::default()
-                .title(Span::styled("CPU %", Style::default().fg(Color::Gray)))
-                .style(Style::default().fg(Color::Gray))
-                .bounds([0.0, 100.0])
-                .labels(vec![
-                    Span::styled("0", Style::default().fg(Color::Gray)),
-                    Span::styled("50", Style::default().fg(Color::Gray)),
-                    Span::styled("100", Style::default().fg(Color::Gray)),
-                ]),
-        );
+        .series(
+            format!("Write: {}", units.rate(disk_io.get_total_write_rate())),
+            Style::default().fg(theme.get_color("network_tx")),
+            write_history,
+        )
+        .y_axis("Throughput", top, labels);
 
     f.render_widget(chart, area);
 }
 
-pub fn render_memory_graph<B: ratatui::backend::Backend>(
+pub fn block_tree_rows<'a>(tree: &'a BlockTree, state: &TreeState) -> Vec<TreeRow<'a, BlockNode>> {
+    tree::flatten(
+        tree.get_roots(),
+        |node| &node.children,
+        |node| &node.name,
+        state,
+    )
+}
+
+#[allow(clippy::too_many_arguments)]
+pub fn render_block_tree_widget<B: ratatui::backend::Backend>(
     f: &mut Frame<B>,
     area: Rect,
-    memory: &MemoryState,
+    tree: &BlockTree,
+    disk_io: &DiskIoState,
+    config: &Config,
+    state: &TreeState,
     theme: &Theme,
+    regions: &mut Regions,
 ) {
-    let block = Block::default()
-        .title("Memory & Swap History")
-        .borders(Borders::ALL);
-
-    let mem_history = memory.get_memory_history();
-    let swap_history = memory.get_swap_history();
-
-    if mem_history.is_empty() && swap_history.is_empty() {
-        f.render_widget(block, area);
-        return;
-    }
-
-    let length = std::cmp::max(mem_history.len(), swap_history.len());
+    let units = Units::from_config(&config.units);
+    let title = if tree.has_degraded_raid() {
+        Line::from(vec![
+            Span::raw("Block Devices "),
+            Span::styled(
+                "[RAID DEGRADED]",
+                Style::default()
+                    .fg(theme.get_color("disk_high"))
+                    .add_modifier(Modifier::BOLD),
+            ),
+        ])
+    } else {
+        Line::from("Block Devices")
+    };
+    let block = Block::default().borders(Borders::ALL).title(title);
 
-    let mem_data: Vec<(f64, f64)> = mem_history
-        .iter()
-        .enumerate()
-        .map(|(i, &value)| (i as f64, value))
-        .collect();
+    let header_cells = [
+        "Device", "Type", "Size", "Mount", "Read/s", "Write/s", "State",
+    ]
+    .iter()
+    .map(|h| Span::styled(*h, Style::default().fg(theme.header_color())));
+    let header = Row::new(header_cells).style(Style::default());
 
-    let swap_data: Vec<(f64, f64)> = swap_history
-        .iter()
-        .enumerate()
-        .map(|(i, &value)| (i as f64, value))
-        .collect();
+    let rows = block_tree_rows(tree, state);
+    let table_rows = rows.iter().map(|row| {
+        let node = row.item;
+        let (read, write) = match disk_io.get_device(&node.name) {
+            Some(device) => (
+                units.rate(device.get_read_rate()),
+                units.rate(device.get_write_rate()),
+            ),
+            None => ("-".to_string(), "-".to_string()),
+        };
+        let kind = match &node.raid {
+            Some(raid) if !raid.level.is_empty() => raid.level.clone(),
+            _ => node.kind.label().to_string(),
+        };
+        let raid_state = node.raid.as_ref().map(|r| r.summary()).unwrap_or_default();
+
+        let row_data = vec![
+            format!("{}{}", row.prefix, node.display_name),
+            kind,
+            units.bytes(node.size),
+            node.mount_point.clone().unwrap_or_default(),
+            read,
+            write,
+            raid_state,
+        ];
+        let cells = row_data.into_iter().map(Span::raw);
+
+        let degraded = node.raid.as_ref().map(|r| r.degraded).unwrap_or(false)
+            || (row.collapsed && node.is_degraded());
+        if degraded {
+            Row::new(cells).style(Style::default().fg(theme.get_color("disk_high")))
+        } else {
+            Row::new(cells)
+        }
+    });
 
-    let datasets = vec![
-        Dataset::default()
-            .name("Memory %")
-            .marker(symbols::Marker::Braille)
-            .graph_type(GraphType::Line)
-            .style(
-                Style::default().fg(theme.memory_color(memory.get_memory_usage_percent() as f32)),
-            )
-            .data(&mem_data),
-        Dataset::default()
-            .name("Swap %")
-            .marker(symbols::Marker::Braille)
-            .graph_type(GraphType::Line)
-            .style(Style::default().fg(Color::LightMagenta))
-            .data(&swap_data),
+    let widths = [
+        Constraint::Percentage(30),
+        Constraint::Length(6),
+        Constraint::Length(10),
+        Constraint::Percentage(20),
+        Constraint::Length(11),
+        Constraint::Length(11),
+        Constraint::Percentage(25),
     ];
 
-    let chart = Chart::new(datasets)
+    let table = Table::new(table_rows)
+        .header(header)
         .block(block)
-        .x_axis(
-            Axis::default()
-                .title(Span::styled("Time", Style::default().fg(Color::Gray)))
-                .style(Style::default().fg(Color::Gray))
-                .bounds([0.0, length as f64])
-                .labels(vec![]),
+        .widths(&widths)
+        .highlight_style(
+            Style::default()
+                .fg(theme.get_color("process_selected"))
+                .add_modifier(Modifier::BOLD),
         )
-        .y_axis(
-            Axis::default()
-                .title(Span::styled("Usage %", Style::default().fg(Color::Gray)))
-                .style(Style::default().fg(Color::Gray))
-                .bounds([0.0, 100.0])
-                .labels(vec![
-                    Span::styled("0", Style::default().fg(Color::Gray)),
-                    Span::styled("50", Style::default().fg(Color::Gray)),
-                    Span::styled("100", Style::default().fg(Color::Gray)),
-                ]),
-        );
+        .highlight_symbol(">> ");
 
-    f.render_widget(chart, area);
+    let mut table_state = TableState::default();
+    if !rows.is_empty() {
+        table_state.select(Some(state.get_selected().min(rows.len() - 1)));
+    }
+
+    f.render_stateful_widget(table, area, &mut table_state);
+    regions.add_rows(Pane::BlockTree, table_body(area), table_state.offset());
 }
 
-pub fn render_network_graph<B: ratatui::backend::Backend>(
+pub enum NetworkRow<'a> {
+    Interface(&'a NetworkInterface),
+    VirtualGroup {
+        count: usize,
+        receive_rate: f64,
+        transmit_rate: f64,
+        collapsed: bool,
+        has_errors: bool,
+    },
+}
+
+pub fn network_rows<'a>(network: &'a NetworkState, config: &NetworkConfig) -> Vec<NetworkRow<'a>> {
+    let interfaces = network.get_sorted(InterfaceSort::from_name(&config.sort_by));
+    if !config.group_virtual {
+        return interfaces.into_iter().map(NetworkRow::Interface).collect();
+    }
+
+    let (virtual_interfaces, physical): (Vec<_>, Vec<_>) =
+        interfaces.into_iter().partition(|i| i.is_virtual());
+
+    let mut rows: Vec<NetworkRow> = physical.into_iter().map(NetworkRow::Interface).collect();
+    if !virtual_interfaces.is_empty() {
+        rows.push(NetworkRow::VirtualGroup {
+            count: virtual_interfaces.len(),
+            receive_rate: virtual_interfaces
+                .iter()
+                .map(|i| i.get_receive_rate())
+                .sum(),
+            transmit_rate: virtual_interfaces
+                .iter()
+                .map(|i| i.get_transmit_rate())
+                .sum(),
+            collapsed: config.collapse_virtual,
+            has_errors: virtual_interfaces.iter().any(|i| i.has_climbing_errors()),
+        });
+        if !config.collapse_virtual {
+            rows.extend(virtual_interfaces.into_iter().map(NetworkRow::Interface));
+        }
+    }
+    rows
+}
+
+pub fn cgroup_rows<'a>(
+    cgroups: &'a CgroupState,
+    state: &TreeState,
+) -> Vec<TreeRow<'a, CgroupNode>> {
+    tree::flatten(
+        cgroups.get_roots(),
+        |node| &node.children,
+        |node| &node.name,
+        state,
+    )
+}
+
+pub fn render_cgroup_tree_widget<B: ratatui::backend::Backend>(
     f: &mut Frame<B>,
     area: Rect,
-    network: &NetworkState,
-    _theme: &Theme,
+    cgroups: &CgroupState,
+    config: &Config,
+    state: &TreeState,
+    theme: &Theme,
+    regions: &mut Regions,
 ) {
+    let units = Units::from_config(&config.units);
     let block = Block::default()
-        .title("Network Traffic")
-        .borders(Borders::ALL);
-
-    let interfaces = network.get_interfaces();
-    if interfaces.is_empty() {
-        f.render_widget(block, area);
-        return;
-    }
-
-    let interface = interfaces[0];
-    let rx_history = interface.get_receive_rate_history();
-    let tx_history = interface.get_transmit_rate_history();
+        .borders(Borders::ALL)
+        .title("Control Groups");
 
-    if rx_history.is_empty() && tx_history.is_empty() {
-        f.render_widget(block, area);
+    if !cgroups.is_available() {
+        let paragraph = Paragraph::new("cgroup v2 hierarchy not available").block(block);
+        f.render_widget(paragraph, area);
         return;
     }
 
-    let length = std::cmp::max(rx_history.len(), tx_history.len());
-
-    let max_rx = rx_history.iter().fold(0.0_f64, |a, &b| f64::max(a, b));
-    let max_tx = tx_history.iter().fold(0.0_f64, |a, &b| f64::max(a, b));
-    let max_value = f64::max(max_rx, max_tx);
-
-    let rx_data: Vec<(f64, f64)> = rx_history
-        .iter()
-        .enumerate()
-        .map(|(i, &value)| {
-            let scaled = if max_value > 0.0 {
-                (value / max_value) * 100.0
-            } else {
-                0.0
-            };
-            (i as f64, scaled)
-        })
-        .collect();
+    let header_cells = [
+        "Cgroup", "CPU%", "Memory", "Limit", "Mem%", "Read/s", "Write/s", "Tasks", "Procs",
+    ]
+    .iter()
+    .map(|h| Span::styled(*h, Style::default().fg(theme.header_color())));
+    let header = Row::new(header_cells).style(Style::default());
 
-    let tx_data: Vec<(f64, f64)> = tx_history
-        .iter()
-        .enumerate()
-        .map(|(i, &value)| {
-            let scaled = if max_value > 0.0 {
-                (value / max_value) * 100.0
-            } else {
-                0.0
-            };
-            (i as f64, scaled)
-        })
-        .collect();
+    let rows = cgroup_rows(cgroups, state);
+    let table_rows = rows.iter().map(|row| {
+        let node = row.item;
+        let optional_bytes = |value: Option<u64>| match value {
+            Some(bytes) => units.bytes(bytes),
+            None => "-".to_string(),
+        };
+        let memory_percent = node.get_memory_percent();
+
+        let row_data = vec![
+            format!("{}{}", row.prefix, node.name),
+            format!("{:.1}%", node.cpu_percent),
+            optional_bytes(node.stats.memory_current),
+            optional_bytes(node.stats.memory_max),
+            memory_percent
+                .map(|percent| format!("{:.1}%", percent))
+                .unwrap_or_else(|| "-".to_string()),
+            units.rate(node.io_read_rate),
+            units.rate(node.io_write_rate),
+            node.stats
+                .pids_current
+                .map(|pids| pids.to_string())
+                .unwrap_or_else(|| "-".to_string()),
+            node.process_count().to_string(),
+        ];
+        let cells = row_data.into_iter().map(Span::raw);
+
+        match memory_percent {
+            Some(percent) if percent >= 90.0 => {
+                Row::new(cells).style(Style::default().fg(theme.memory_color(percent as f32)))
+            }
+            _ => Row::new(cells),
+        }
+    });
 
-    let datasets = vec![
-        Dataset::default()
-            .name(format!(
-                "RX: {}/s",
-                format_bytes_rate(interface.get_receive_rate())
-            ))
-            .marker(symbols::Marker::Braille)
-            .graph_type(GraphType::Line)
-            .style(Style::default().fg(Color::Blue))
-            .data(&rx_data),
-        Dataset::default()
-            .name(format!(
-                "TX: {}/s",
-                format_bytes_rate(interface.get_transmit_rate())
-            ))
-            .marker(symbols::Marker::Braille)
-            .graph_type(GraphType::Line)
-            .style(Style::default().fg(Color::Red))
-            .data(&tx_data),
+    let widths = [
+        Constraint::Percentage(35),
+        Constraint::Length(7),
+        Constraint::Length(10),
+        Constraint::Length(10),
+        Constraint::Length(6),
+        Constraint::Length(11),
+        Constraint::Length(11),
+        Constraint::Length(6),
+        Constraint::Length(6),
     ];
 
-    let max_label = format_bytes_rate(max_value);
-    let half_label = format_bytes_rate(max_value / 2.0);
-
-    let chart = Chart::new(datasets)
+    let table = Table::new(table_rows)
+        .header(header)
         .block(block)
-        .x_axis(
-            Axis::default()
-                .title(Span::styled("Time", Style::default().fg(Color::Gray)))
-                .style(Style::default().fg(Color::Gray))
-                .bounds([0.0, length as f64])
-                .labels(vec![]),
+        .widths(&widths)
+        .highlight_style(
+            Style::default()
+                .fg(theme.get_color("process_selected"))
+                .add_modifier(Modifier::BOLD),
         )
-        .y_axis(
-            Axis::default()
-                .title(Span::styled("Throughput", Style::default().fg(Color::Gray)))
-                .style(Style::default().fg(Color::Gray))
-                .bounds([0.0, 100.0])
-                .labels(vec![
-                    Span::styled("0", Style::default().fg(Color::Gray)),
-                    Span::styled(half_label, Style::default().fg(Color::Gray)),
-                    Span::styled(max_label, Style::default().fg(Color::Gray)),
-                ]),
-        );
+        .highlight_symbol(">> ");
 
-    f.render_widget(chart, area);
+    let mut table_state = TableState::default();
+    if !rows.is_empty() {
+        table_state.select(Some(state.get_selected().min(rows.len() - 1)));
+    }
+
+    f.render_stateful_widget(table, area, &mut table_state);
+    regions.add_rows(Pane::Cgroups, table_body(area), table_state.offset());
 }
 
-pub fn render_status_bar<B: ratatui::backend::Backend>(
+pub fn selected_interface<'a>(
+    network: &'a NetworkState,
+    config: &NetworkConfig,
+) -> Option<&'a NetworkInterface> {
+    network.get_interface(&config.interface).or_else(|| {
+        network_rows(network, config)
+            .into_iter()
+            .find_map(|row| match row {
+                NetworkRow::Interface(i) => Some(i),
+                NetworkRow::VirtualGroup { .. } => None,
+            })
+    })
+}
+
+pub fn render_network_widget<B: ratatui::backend::Backend>(
     f: &mut Frame<B>,
     area: Rect,
-    current_layout: &str,
+    network: &NetworkState,
+    config: &Config,
+    theme: &Theme,
 ) {
-    let controls = vec![
-        ("q", "Quit"),
-        ("c", "Cycle Theme"),
-        ("g", "Graph View"),
-        ("1-5", "Change Layout"),
-        ("", current_layout),
-        ("", ""),
-        ("Made by:", "C0d3-5t3w"),
+    let units = Units::from_config(&config.units);
+    let error_style = Style::default().fg(theme.get_color("network_error"));
+    let sort = InterfaceSort::from_name(&config.network.sort_by);
+    let mut title = vec![Span::raw(format!("Network (sort: {}) ", sort.name()))];
+    if network.has_climbing_errors() {
+        title.push(Span::styled(
+            "[ERRORS]",
+            error_style.add_modifier(Modifier::BOLD),
+        ));
+    }
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .title(Line::from(title));
+
+    let header_cells = [
+        "Interface",
+        "State",
+        "RX",
+        "TX",
+        "RX/s",
+        "TX/s",
+        "Err/s",
+        "Drop/s",
+    ]
+    .iter()
+    .map(|h| Span::styled(*h, Style::default().fg(theme.header_color())));
+    let header = Row::new(header_cells).style(Style::default());
+
+    let network_rows = network_rows(network, &config.network);
+    let selected = network_rows.iter().position(|row| match row {
+        NetworkRow::Interface(i) => i.get_name() == config.network.interface,
+        NetworkRow::VirtualGroup { .. } => false,
+    });
+    let rows = network_rows.iter().map(|row| match row {
+        NetworkRow::Interface(i) => {
+            let name = if config.network.group_virtual && i.is_virtual() {
+                format!("  {}", i.get_name())
+            } else {
+                i.get_name().to_string()
+            };
+            let state = i.get_link().operstate.clone();
+
+            let rx = units.bytes(i.get_received_bytes());
+            let tx = units.bytes(i.get_transmitted_bytes());
+            let rx_rate = units.network_rate(i.get_receive_rate());
+            let tx_rate = units.network_rate(i.get_transmit_rate());
+            let errors = format!("{:.1}", i.get_error_rates().errors());
+            let drops = format!("{:.1}", i.get_error_rates().drops());
+
+            let row_data = vec![name, state, rx, tx, rx_rate, tx_rate, errors, drops];
+            let cells = row_data.into_iter().map(Span::raw);
+
+            if i.has_climbing_errors() {
+                Row::new(cells).style(error_style)
+            } else {
+                Row::new(cells)
+            }
+        }
+        NetworkRow::VirtualGroup {
+            count,
+            receive_rate,
+            transmit_rate,
+            collapsed,
+            has_errors,
+        } => {
+            let marker = if *collapsed { "▸" } else { "▾" };
+            let row_data = vec![
+                format!("{} virtual ({})", marker, count),
+                String::new(),
+                String::new(),
+                String::new(),
+                units.network_rate(*receive_rate),
+                units.network_rate(*transmit_rate),
+                String::new(),
+                String::new(),
+            ];
+            let cells = row_data.into_iter().map(Span::raw);
+
+            if *has_errors && *collapsed {
+                Row::new(cells).style(error_style)
+            } else {
+                Row::new(cells).style(Style::default().fg(Color::Gray))
+            }
+        }
+    });
+
+    let widths = [
+        Constraint::Percentage(20),
+        Constraint::Length(8),
+        Constraint::Length(10),
+        Constraint::Length(10),
+        Constraint::Length(10),
+        Constraint::Length(10),
+        Constraint::Length(7),
+        Constraint::Length(7),
     ];
 
-    let mut control_spans = Vec::new();
-    for (key, desc) in controls {
-        if !key.is_empty() {
-            control_spans.push(Span::styled(
-                format!("[{}]", key),
+    let table = Table::new(rows)
+        .header(header)
+        .block(block)
+        .widths(&widths)
+        .highlight_style(
+            Style::default()
+                .fg(theme.get_color("process_selected"))
+                .add_modifier(Modifier::BOLD),
+        );
+
+    let mut table_state = TableState::default();
+    table_state.select(selected);
+
+    f.render_stateful_widget(table, area, &mut table_state);
+}
+
+pub fn render_interface_details<B: ratatui::backend::Backend>(
+    f: &mut Frame<B>,
+    area: Rect,
+    interface: &NetworkInterface,
+    config: &Config,
+    theme: &Theme,
+) {
+    let units = Units::from_config(&config.units);
+    let link = interface.get_link();
+    let counters = interface.get_error_counters();
+    let rates = interface.get_error_rates();
+    let label = |text: &str| {
+        Span::styled(
+            format!("{:<12}", text),
+            Style::default().fg(theme.header_color()),
+        )
+    };
+    let optional = |value: Option<String>| value.unwrap_or_else(|| "-".to_string());
+
+    let mut lines = vec![
+        Line::from(vec![label("State"), Span::raw(link.operstate.clone())]),
+        Line::from(vec![label("MAC"), Span::raw(optional(link.mac.clone()))]),
+        Line::from(vec![
+            label("MTU"),
+            Span::raw(optional(link.mtu.map(|mtu| mtu.to_string()))),
+        ]),
+        Line::from(vec![
+            label("Speed"),
+            Span::raw(match (link.speed, &link.duplex) {
+                (Some(speed), Some(duplex)) => format!("{} Mb/s, {} duplex", speed, duplex),
+                (Some(speed), None) => format!("{} Mb/s", speed),
+                _ => "-".to_string(),
+            }),
+        ]),
+    ];
+
+    let addresses = interface.get_addresses();
+    if addresses.is_empty() {
+        lines.push(Line::from(vec![label("Address"), Span::raw("-")]));
+    }
+    for (idx, address) in addresses.iter().enumerate() {
+        let name = if idx == 0 { "Address" } else { "" };
+        lines.push(Line::from(vec![
+            label(name),
+            Span::raw(address.to_string()),
+        ]));
+    }
+
+    lines.push(Line::from(""));
+    lines.push(Line::from(vec![
+        label("RX"),
+        Span::raw(format!(
+            "{} ({} packets)",
+            units.bytes(interface.get_received_bytes()),
+            interface.get_received_packets()
+        )),
+    ]));
+    lines.push(Line::from(vec![
+        label("TX"),
+        Span::raw(format!(
+            "{} ({} packets)",
+            units.bytes(interface.get_transmitted_bytes()),
+            interface.get_transmitted_packets()
+        )),
+    ]));
+
+    let counter_line = |name: &str, total: u64, rate: f64| {
+        let style = if rate > 0.0 {
+            Style::default().fg(theme.get_color("network_error"))
+        } else {
+            Style::default()
+        };
+        Line::from(vec![
+            label(name),
+            Span::styled(format!("{} ({:.1}/s)", total, rate), style),
+        ])
+    };
+    lines.push(counter_line(
+        "RX errors",
+        counters.rx_errors,
+        rates.rx_errors,
+    ));
+    lines.push(counter_line(
+        "TX errors",
+        counters.tx_errors,
+        rates.tx_errors,
+    ));
+    lines.push(counter_line(
+        "RX dropped",
+        counters.rx_dropped,
+        rates.rx_dropped,
+    ));
+    lines.push(counter_line(
+        "TX dropped",
+        counters.tx_dropped,
+        rates.tx_dropped,
+    ));
+    lines.push(counter_line(
+        "Collisions",
+        counters.collisions,
+        rates.collisions,
+    ));
+
+    let width = area.width.min(60);
+    let height = area.height.min(lines.len() as u16 + 2);
+    let popup = Rect::new(
+        area.x + (area.width - width) / 2,
+        area.y + (area.height - height) / 2,
+        width,
+        height,
+    );
+
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .title(format!("Interface {}", interface.get_name()));
+
+    f.render_widget(Clear, popup);
+    f.render_widget(Paragraph::new(lines).block(block), popup);
+}
+
+pub fn render_help<B: ratatui::backend::Backend>(
+    f: &mut Frame<B>,
+    area: Rect,
+    keymap: &Keymap,
+    theme: &Theme,
+) {
+    let mut lines = Vec::new();
+    for (context, entries) in keymap.help() {
+        if !lines.is_empty() {
+            lines.push(Line::from(""));
+        }
+        lines.push(Line::from(Span::styled(
+            context,
+            Style::default()
+                .fg(theme.header_color())
+                .add_modifier(Modifier::BOLD),
+        )));
+        for (keys, action) in entries {
+            lines.push(Line::from(vec![
+                Span::styled(format!("{:<14}", keys), Style::default().fg(Color::Yellow)),
+                Span::raw(keymap.describe(action)),
+            ]));
+        }
+    }
+
+    let available = area.height.saturating_sub(4).max(1) as usize;
+    let columns = lines.len().div_ceil(available).max(1);
+    let rows = lines.len().div_ceil(columns);
+    let width = area.width.min(62 * columns as u16);
+    let height = area.height.min(rows as u16 + 2);
+    let popup = Rect::new(
+        area.x + (area.width - width) / 2,
+        area.y + (area.height - height) / 2,
+        width,
+        height,
+    );
+
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .title("Help (any key to close)");
+    let inner = block.inner(popup);
+    f.render_widget(Clear, popup);
+    f.render_widget(block, popup);
+
+    let chunks = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints(vec![Constraint::Ratio(1, columns as u32); columns])
+        .split(inner);
+    for (chunk, column) in chunks.iter().zip(lines.chunks(rows)) {
+        let chunk = Rect {
+            width: chunk.width.saturating_sub(2),
+            ..*chunk
+        };
+        f.render_widget(Paragraph::new(column.to_vec()), chunk);
+    }
+}
+
+pub fn render_palette<B: ratatui::backend::Backend>(
+    f: &mut Frame<B>,
+    area: Rect,
+    palette: &Palette,
+    matches: &[&Candidate],
+    theme: &Theme,
+) {
+    let visible = matches.len().min(VISIBLE_CANDIDATES);
+    let width = area.width.min(80);
+    let height = area.height.min(visible as u16 + 5);
+    let popup = Rect::new(
+        area.x + (area.width - width) / 2,
+        area.y + area.height - height,
+        width,
+        height,
+    );
+    f.render_widget(Clear, popup);
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Min(2), Constraint::Length(3)].as_ref())
+        .split(popup);
+
+    let offset = palette
+        .get_selected()
+        .saturating_sub(visible.saturating_sub(1));
+    let lines: Vec<Line> = matches
+        .iter()
+        .enumerate()
+        .skip(offset)
+        .take(visible)
+        .map(|(idx, candidate)| {
+            let style = if idx == palette.get_selected() {
                 Style::default()
-                    .fg(Color::Yellow)
-                    .add_modifier(Modifier::BOLD),
-            ));
-            control_spans.push(Span::raw(" "));
-            control_spans.push(Span::styled(
-                format!("{}", desc),
-                Style::default().fg(Color::White),
-            ));
-            control_spans.push(Span::raw("  "));
-        } else if !desc.is_empty() {
-            control_spans.push(Span::styled(
-                format!("Current: {}", desc),
+                    .fg(Color::Black)
+                    .bg(theme.get_color("process_selected"))
+            } else {
                 Style::default()
-                    .fg(Color::Cyan)
-                    .add_modifier(Modifier::BOLD),
-            ));
+            };
+            Line::from(vec![
+                Span::styled(format!("{:<28}", candidate.text), style),
+                Span::styled(
+                    format!(" {}", candidate.hint),
+                    Style::default().fg(Color::DarkGray),
+                ),
+            ])
+        })
+        .collect();
+    let list = Paragraph::new(lines).block(
+        Block::default()
+            .borders(Borders::LEFT | Borders::RIGHT | Borders::TOP)
+            .title(format!("Commands ({})", matches.len())),
+    );
+    f.render_widget(list, chunks[0]);
+
+    let title = match palette.get_error() {
+        Some(error) => Span::styled(error.to_string(), Style::default().fg(Color::Red)),
+        None => Span::raw("Tab complete, Enter run, Esc cancel"),
+    };
+    let input = Paragraph::new(Line::from(vec![
+        Span::styled(":", Style::default().fg(Color::Yellow)),
+        Span::raw(format!("{}_", palette.get_input())),
+    ]))
+    .block(Block::default().borders(Borders::ALL).title(title));
+    f.render_widget(input, chunks[1]);
+}
+
+pub fn render_message<B: ratatui::backend::Backend>(
+    f: &mut Frame<B>,
+    area: Rect,
+    message: &str,
+    theme: &Theme,
+) {
+    if area.height == 0 {
+        return;
+    }
+    let line = Rect::new(area.x, area.y + area.height - 1, area.width, 1);
+    f.render_widget(Clear, line);
+    f.render_widget(
+        Paragraph::new(Span::styled(
+            message.to_string(),
+            Style::default()
+                .fg(theme.header_color())
+                .add_modifier(Modifier::BOLD),
+        ))
+        .alignment(ratatui::layout::Alignment::Center),
+        line,
+    );
+}
+
+pub fn connection_rows<'a>(connections: &'a ConnectionState, filter: &str) -> Vec<&'a Connection> {
+    connections.get_filtered(&ConnectionFilter::parse(filter))
+}
+
+#[allow(clippy::too_many_arguments)]
+pub fn render_connections_widget<B: ratatui::backend::Backend>(
+    f: &mut Frame<B>,
+    area: Rect,
+    connections: &ConnectionState,
+    filter: &str,
+    editing: bool,
+    selected: usize,
+    theme: &Theme,
+    regions: &mut Regions,
+) {
+    let rows = connection_rows(connections, filter);
+
+    let mut title = vec![Span::raw(format!(
+        "Connections ({}/{}) ",
+        rows.len(),
+        connections.get_connections().len()
+    ))];
+    if editing {
+        title.push(Span::styled(
+            format!("filter: {}_", filter),
+            Style::default()
+                .fg(theme.header_color())
+                .add_modifier(Modifier::BOLD),
+        ));
+    } else if !filter.is_empty() {
+        title.push(Span::raw(format!("filter: {}", filter)));
+    }
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .title(Line::from(title));
+
+    let header_cells = ["Proto", "Local", "Remote", "State", "PID", "Process"]
+        .iter()
+        .map(|h| Span::styled(*h, Style::default().fg(theme.header_color())));
+    let header = Row::new(header_cells).style(Style::default());
+
+    let table_rows = rows.iter().map(|connection| {
+        let row_data = vec![
+            connection.protocol.label().to_string(),
+            connection.local(),
+            connection.remote(),
+            connection.state.clone(),
+            connection
+                .pid
+                .map(|pid| pid.to_string())
+                .unwrap_or_else(|| "-".to_string()),
+            connection.process.clone().unwrap_or_default(),
+        ];
+        Row::new(row_data.into_iter().map(Span::raw))
+    });
+
+    let widths = [
+        Constraint::Length(5),
+        Constraint::Percentage(30),
+        Constraint::Percentage(30),
+        Constraint::Length(11),
+        Constraint::Length(8),
+        Constraint::Percentage(20),
+    ];
+
+    let table = Table::new(table_rows)
+        .header(header)
+        .block(block)
+        .widths(&widths)
+        .highlight_style(
+            Style::default()
+                .fg(theme.get_color("process_selected"))
+                .add_modifier(Modifier::BOLD),
+        )
+        .highlight_symbol(">> ");
+
+    let mut table_state = TableState::default();
+    if !rows.is_empty() {
+        table_state.select(Some(selected.min(rows.len() - 1)));
+    }
+
+    f.render_stateful_widget(table, area, &mut table_state);
+    regions.add_rows(Pane::Connections, table_body(area), table_state.offset());
+}
+
+pub fn render_top_talkers_widget<B: ratatui::backend::Backend>(
+    f: &mut Frame<B>,
+    area: Rect,
+    traffic: &NetTrafficState,
+    config: &Config,
+    theme: &Theme,
+) {
+    let units = Units::from_config(&config.units);
+    let attributed = traffic.is_attributed();
+    let title = if attributed {
+        "Top Talkers".to_string()
+    } else {
+        "Top Talkers (connection counts)".to_string()
+    };
+    let block = Block::default().borders(Borders::ALL).title(title);
+
+    let header_cells = if attributed {
+        vec!["PID", "Process", "RX/s", "TX/s", "Conns"]
+    } else {
+        vec!["PID", "Process", "Conns"]
+    };
+    let header = Row::new(
+        header_cells
+            .into_iter()
+            .map(|h| Span::styled(h, Style::default().fg(theme.header_color()))),
+    )
+    .style(Style::default());
+
+    let limit = (area.height as usize).saturating_sub(3);
+    let rows = traffic.get_top_talkers(limit).into_iter().map(|talker| {
+        let mut row_data = vec![talker.pid.to_string(), talker.name.clone()];
+        if attributed {
+            row_data.push(units.network_rate(talker.rx_rate));
+            row_data.push(units.network_rate(talker.tx_rate));
         }
+        row_data.push(talker.connections.to_string());
+        Row::new(row_data.into_iter().map(Span::raw))
+    });
+
+    let widths = if attributed {
+        vec![
+            Constraint::Length(8),
+            Constraint::Percentage(40),
+            Constraint::Length(11),
+            Constraint::Length(11),
+            Constraint::Length(6),
+        ]
+    } else {
+        vec![
+            Constraint::Length(8),
+            Constraint::Percentage(60),
+            Constraint::Length(6),
+        ]
+    };
+
+    let table = Table::new(rows).header(header).block(block).widths(&widths);
+
+    f.render_widget(table, area);
+}
+
+pub fn render_containers_widget<B: ratatui::backend::Backend>(
+    f: &mut Frame<B>,
+    area: Rect,
+    containers: &ContainerState,
+    config: &Config,
+    theme: &Theme,
+) {
+    let units = Units::from_config(&config.units);
+    let title = match containers.get_engine_error() {
+        Some(err) => format!("Containers (engine: {})", err),
+        None => "Containers".to_string(),
+    };
+    let block = Block::default().borders(Borders::ALL).title(title);
+
+    if containers.get_summaries().is_empty() {
+        let paragraph = Paragraph::new("No containers detected").block(block);
+        f.render_widget(paragraph, area);
+        return;
     }
 
-    let status_line = Line::from(control_spans);
+    let header_cells = [
+        "Container",
+        "Runtime",
+        "Pod",
+        "Image",
+        "Procs",
+        "CPU%",
+        "Memory",
+        "RX/s",
+        "TX/s",
+    ]
+    .iter()
+    .map(|h| Span::styled(*h, Style::default().fg(theme.header_color())));
+    let header = Row::new(header_cells).style(Style::default());
+
+    let rows = containers.get_summaries().iter().map(|summary| {
+        let row_data = vec![
+            summary.name.clone(),
+            summary.container.runtime.label().to_string(),
+            summary.pod.clone().unwrap_or_else(|| "-".to_string()),
+            summary.image.clone().unwrap_or_else(|| "-".to_string()),
+            summary.processes.to_string(),
+            format!("{:.1}%", summary.cpu_usage),
+            units.bytes(summary.memory),
+            units.network_rate(summary.rx_rate),
+            units.network_rate(summary.tx_rate),
+        ];
+        Row::new(row_data.into_iter().map(Span::raw))
+    });
+
+    let widths = [
+        Constraint::Percentage(20),
+        Constraint::Length(10),
+        Constraint::Percentage(15),
+        Constraint::Percentage(20),
+        Constraint::Length(6),
+        Constraint::Length(7),
+        Constraint::Length(10),
+        Constraint::Length(11),
+        Constraint::Length(11),
+    ];
+
+    let table = Table::new(rows).header(header).block(block).widths(&widths);
+
+    f.render_widget(table, area);
+}
+
+#[allow(clippy::too_many_arguments)]
+pub fn render_units_widget<B: ratatui::backend::Backend>(
+    f: &mut Frame<B>,
+    area: Rect,
+    systemd: &SystemdState,
+    config: &Config,
+    selected: usize,
+    pending: Option<(UnitAction, &str)>,
+    theme: &Theme,
+    regions: &mut Regions,
+) {
+    let units = Units::from_config(&config.units);
+    let rows = systemd.get_units();
+
+    let mut title = vec![Span::raw(format!("Units ({}) ", rows.len()))];
+    if let Some((action, unit)) = pending {
+        title.push(Span::styled(
+            format!("{} {}? [y/N]", action.label(), unit),
+            Style::default()
+                .fg(theme.header_color())
+                .add_modifier(Modifier::BOLD),
+        ));
+    } else if let Some(status) = systemd.get_action_status() {
+        title.push(Span::styled(
+            status.to_string(),
+            Style::default().fg(theme.get_color("network_error")),
+        ));
+    } else if let Some(err) = systemd.get_bus_error() {
+        title.push(Span::raw(format!("(bus unavailable: {})", err)));
+    }
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .title(Line::from(title));
+
+    let header_cells = [
+        "Unit",
+        "Load",
+        "Active",
+        "Sub",
+        "Main PID",
+        "Tasks",
+        "CPU%",
+        "Memory",
+        "Description",
+    ]
+    .iter()
+    .map(|h| Span::styled(*h, Style::default().fg(theme.header_color())));
+    let header = Row::new(header_cells).style(Style::default());
+
+    let table_rows = rows.iter().map(|unit| {
+        let dash = |value: &str| {
+            if value.is_empty() {
+                "-".to_string()
+            } else {
+                value.to_string()
+            }
+        };
+        let row_data = vec![
+            unit.name.clone(),
+            dash(&unit.load_state),
+            dash(&unit.active_state),
+            dash(&unit.sub_state),
+            unit.main_pid
+                .map(|pid| pid.to_string())
+                .unwrap_or_else(|| "-".to_string()),
+            unit.tasks.to_string(),
+            format!("{:.1}%", unit.cpu_usage),
+            units.bytes(unit.memory),
+            unit.description.clone(),
+        ];
+        let style = if unit.is_failed() {
+            Style::default().fg(theme.get_color("network_error"))
+        } else {
+            Style::default()
+        };
+        Row::new(row_data.into_iter().map(Span::raw)).style(style)
+    });
+
+    let widths = [
+        Constraint::Percentage(25),
+        Constraint::Length(8),
+        Constraint::Length(12),
+        Constraint::Length(10),
+        Constraint::Length(9),
+        Constraint::Length(6),
+        Constraint::Length(7),
+        Constraint::Length(10),
+        Constraint::Percentage(30),
+    ];
+
+    let table = Table::new(table_rows)
+        .header(header)
+        .block(block)
+        .widths(&widths)
+        .highlight_style(
+            Style::default()
+                .fg(theme.get_color("process_selected"))
+                .add_modifier(Modifier::BOLD),
+        )
+        .highlight_symbol(">> ");
+
+    let mut table_state = TableState::default();
+    if !rows.is_empty() {
+        table_state.select(Some(selected.min(rows.len() - 1)));
+    }
+
+    f.render_stateful_widget(table, area, &mut table_state);
+    regions.add_rows(Pane::Units, table_body(area), table_state.offset());
+}
+
+pub fn render_active_alerts_widget<B: ratatui::backend::Backend>(
+    f: &mut Frame<B>,
+    area: Rect,
+    alerts: &AlertState,
+    theme: &Theme,
+) {
+    let active = alerts.get_active();
+    let title = format!(
+        "Active Alerts ({}/{} rules)",
+        active.len(),
+        alerts.get_rules().len()
+    );
+    let block = Block::default().borders(Borders::ALL).title(title);
+
+    if alerts.get_rules().is_empty() && alerts.get_errors().is_empty() {
+        let paragraph = Paragraph::new("No alert rules configured").block(block);
+        f.render_widget(paragraph, area);
+        return;
+    }
+
+    let header_cells = ["Severity", "Rule", "Since", "For", "Detail"]
+        .iter()
+        .map(|h| Span::styled(*h, Style::default().fg(theme.header_color())));
+    let header = Row::new(header_cells).style(Style::default());
+
+    let now = SystemTime::now();
+    let mut rows: Vec<Row> = active
+        .iter()
+        .map(|alert| {
+            let elapsed = now.duration_since(alert.started).unwrap_or_default();
+            let row_data = vec![
+                alert.severity.label().to_string(),
+                alert.rule.clone(),
+                time::clock(alert.started),
+                format_duration(elapsed),
+                alert.detail.clone(),
+            ];
+            Row::new(row_data.into_iter().map(Span::raw))
+                .style(Style::default().fg(theme.alert_color(alert.severity)))
+        })
+        .collect();
+    rows.extend(alerts.get_errors().iter().map(|err| {
+        Row::new(vec![
+            Span::raw("invalid"),
+            Span::raw(""),
+            Span::raw(""),
+            Span::raw(""),
+            Span::raw(err.clone()),
+        ])
+        .style(Style::default().fg(theme.get_color("network_error")))
+    }));
+
+    let widths = [
+        Constraint::Length(9),
+        Constraint::Percentage(20),
+        Constraint::Length(9),
+        Constraint::Length(8),
+        Constraint::Percentage(60),
+    ];
+
+    let table = Table::new(rows).header(header).block(block).widths(&widths);
+
+    f.render_widget(table, area);
+}
+
+pub fn render_resolved_alerts_widget<B: ratatui::backend::Backend>(
+    f: &mut Frame<B>,
+    area: Rect,
+    alerts: &AlertState,
+    theme: &Theme,
+) {
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .title("Recently Resolved");
+
+    let header_cells = [
+        "Severity",
+        "Rule",
+        "Started",
+        "Resolved",
+        "Lasted",
+        "Condition",
+    ]
+    .iter()
+    .map(|h| Span::styled(*h, Style::default().fg(theme.header_color())));
+    let header = Row::new(header_cells).style(Style::default());
+
+    let rows = alerts.get_resolved().iter().map(|alert| {
+        let resolved = alert.resolved.unwrap_or(alert.started);
+        let lasted = resolved.duration_since(alert.started).unwrap_or_default();
+        let row_data = vec![
+            alert.severity.label().to_string(),
+            alert.rule.clone(),
+            time::clock(alert.started),
+            time::clock(resolved),
+            format_duration(lasted),
+            alert.condition.clone(),
+        ];
+        Row::new(row_data.into_iter().map(Span::raw))
+    });
+
+    let widths = [
+        Constraint::Length(9),
+        Constraint::Percentage(20),
+        Constraint::Length(9),
+        Constraint::Length(9),
+        Constraint::Length(8),
+        Constraint::Percentage(50),
+    ];
+
+    let table = Table::new(rows).header(header).block(block).widths(&widths);
+
+    f.render_widget(table, area);
+}
+
+pub fn render_alert_actions_widget<B: ratatui::backend::Backend>(
+    f: &mut Frame<B>,
+    area: Rect,
+    alerts: &AlertState,
+    theme: &Theme,
+) {
+    let title = if alerts.is_dry_run() {
+        "Actions (dry run)"
+    } else {
+        "Actions"
+    };
+    let block = Block::default().borders(Borders::ALL).title(title);
+
+    let header_cells = ["Time", "Rule", "Action", "Outcome"]
+        .iter()
+        .map(|h| Span::styled(*h, Style::default().fg(theme.header_color())));
+    let header = Row::new(header_cells).style(Style::default());
+
+    let rows = alerts.get_action_records().iter().map(|record| {
+        let style = if record.failed {
+            Style::default().fg(theme.alert_color(Severity::Critical))
+        } else {
+            Style::default()
+        };
+        let row_data = vec![
+            time::clock(record.time),
+            record.rule.clone(),
+            record.action.clone(),
+            record.outcome.clone(),
+        ];
+        Row::new(row_data.into_iter().map(Span::raw)).style(style)
+    });
+
+    let widths = [
+        Constraint::Length(9),
+        Constraint::Percentage(20),
+        Constraint::Percentage(15),
+        Constraint::Percentage(65),
+    ];
+
+    let table = Table::new(rows).header(header).block(block).widths(&widths);
+
+    f.render_widget(table, area);
+}
+
+pub fn render_cpu_graph<B: ratatui::backend::Backend>(
+    f: &mut Frame<B>,
+    area: Rect,
+    cpu: &CpuState,
+    config: &Config,
+    theme: &Theme,
+) {
+    let history: Vec<f64> = cpu
+        .get_history()
+        .iter()
+        .map(|value| *value as f64)
+        .collect();
+
+    let chart = TimeChart::new("CPU Usage History", config)
+        .series(
+            "CPU %".to_string(),
+            Style::default().fg(theme.cpu_color(cpu.get_average_usage())),
+            history,
+        )
+        .y_axis("CPU %", 100.0, percent_labels());
+
+    f.render_widget(chart, area);
+}
+
+pub fn render_memory_graph<B: ratatui::backend::Backend>(
+    f: &mut Frame<B>,
+    area: Rect,
+    memory: &MemoryState,
+    config: &Config,
+    theme: &Theme,
+) {
+    let chart = TimeChart::new("Memory & Swap History", config)
+        .series(
+            "Memory %".to_string(),
+            Style::default().fg(theme.memory_color(memory.get_memory_usage_percent() as f32)),
+            memory.get_memory_history().iter().copied().collect(),
+        )
+        .series(
+            "Swap %".to_string(),
+            Style::default().fg(Color::LightMagenta),
+            memory.get_swap_history().iter().copied().collect(),
+        )
+        .y_axis("Usage %", 100.0, percent_labels());
+
+    f.render_widget(chart, area);
+}
+
+fn percent_labels() -> Vec<String> {
+    ["0", "50", "100"]
+        .iter()
+        .map(|label| label.to_string())
+        .collect()
+}
+
+pub fn render_network_graph<B: ratatui::backend::Backend>(
+    f: &mut Frame<B>,
+    area: Rect,
+    network: &NetworkState,
+    config: &Config,
+    _theme: &Theme,
+) {
+    let units = Units::from_config(&config.units);
+    let interface = selected_interface(network, &config.network);
+
+    let title = match interface {
+        Some(interface) => format!("Network Traffic ({})", interface.get_name()),
+        None => "Network Traffic".to_string(),
+    };
+
+    let interface = match interface {
+        Some(interface) => interface,
+        None => {
+            f.render_widget(Block::default().title(title).borders(Borders::ALL), area);
+            return;
+        }
+    };
+    let rx_history: Vec<f64> = interface
+        .get_receive_rate_history()
+        .iter()
+        .copied()
+        .collect();
+    let tx_history: Vec<f64> = interface
+        .get_transmit_rate_history()
+        .iter()
+        .copied()
+        .collect();
+
+    let chart = TimeChart::new(&title, config);
+    let max_value = [&rx_history, &tx_history]
+        .iter()
+        .flat_map(|history| history[history.len() - chart.visible_count(history)..].iter())
+        .fold(0.0_f64, |a, &b| f64::max(a, b));
+    let (top, labels) = units.network_axis(max_value);
+
+    let chart = chart
+        .series(
+            format!("RX: {}", units.network_rate(interface.get_receive_rate())),
+            Style::default().fg(Color::Blue),
+            rx_history,
+        )
+        .series(
+            format!("TX: {}", units.network_rate(interface.get_transmit_rate())),
+            Style::default().fg(Color::Red),
+            tx_history,
+        )
+        .y_axis("Throughput", top, labels);
+
+    f.render_widget(chart, area);
+}
+
+pub fn render_pressure_widget<B: ratatui::backend::Backend>(
+    f: &mut Frame<B>,
+    area: Rect,
+    pressure: &PressureState,
+    theme: &Theme,
+) {
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .title("Pressure Stall (PSI)");
+
+    if !pressure.is_available() {
+        let paragraph =
+            Paragraph::new("PSI not available (requires Linux 4.20+ with CONFIG_PSI)").block(block);
+        f.render_widget(paragraph, area);
+        return;
+    }
+
+    let header_cells = [
+        "Resource", "Some10", "Some60", "Some300", "Full10", "Full60", "Full300", "Some/s",
+        "Full/s",
+    ]
+    .iter()
+    .map(|h| Span::styled(*h, Style::default().fg(theme.header_color())));
+    let header = Row::new(header_cells).style(Style::default());
+
+    let mut resources: Vec<(String, &PressureResource)> = pressure
+        .get_resources()
+        .iter()
+        .map(|r| (r.get_name().to_string(), *r))
+        .collect();
+    for group in pressure.get_cgroups() {
+        for r in group.get_resources() {
+            resources.push((format!("{}:{}", group.get_name(), r.get_name()), r));
+        }
+    }
+
+    let rows = resources.into_iter().map(|(name, r)| {
+        let some = r.get_some().unwrap_or_default();
+        let full = r.get_full();
+        let fmt_full = |value: Option<f64>| match value {
+            Some(v) => format!("{:.2}", v),
+            None => "-".to_string(),
+        };
+
+        let row_data = vec![
+            name,
+            format!("{:.2}", some.avg10),
+            format!("{:.2}", some.avg60),
+            format!("{:.2}", some.avg300),
+            fmt_full(full.map(|l| l.avg10)),
+            fmt_full(full.map(|l| l.avg60)),
+            fmt_full(full.map(|l| l.avg300)),
+            format!("{:.1}%", r.get_some_stall_rate()),
+            match full {
+                Some(_) => format!("{:.1}%", r.get_full_stall_rate()),
+                None => "-".to_string(),
+            },
+        ];
+        let cells = row_data.into_iter().map(Span::raw);
+
+        Row::new(cells).style(Style::default().fg(theme.pressure_color(some.avg10)))
+    });
+
+    let widths = [
+        Constraint::Percentage(25),
+        Constraint::Length(7),
+        Constraint::Length(7),
+        Constraint::Length(8),
+        Constraint::Length(7),
+        Constraint::Length(7),
+        Constraint::Length(8),
+        Constraint::Length(8),
+        Constraint::Length(8),
+    ];
+
+    let table = Table::new(rows).header(header).block(block).widths(&widths);
+
+    f.render_widget(table, area);
+}
+
+pub fn render_pressure_graph<B: ratatui::backend::Backend>(
+    f: &mut Frame<B>,
+    area: Rect,
+    pressure: &PressureState,
+    config: &Config,
+    theme: &Theme,
+) {
+    if !pressure.is_available() {
+        let block = Block::default()
+            .title("Pressure History")
+            .borders(Borders::ALL);
+        f.render_widget(block, area);
+        return;
+    }
+
+    let resources = pressure.get_resources();
+    let histories: Vec<Vec<f64>> = resources
+        .iter()
+        .map(|r| r.get_history().iter().copied().collect())
+        .collect();
+
+    let mut chart = TimeChart::new("Pressure History", config);
+    let max_value = histories
+        .iter()
+        .flat_map(|history| history[history.len() - chart.visible_count(history)..].iter())
+        .fold(0.0_f64, |a, &b| f64::max(a, b));
+    let upper = if max_value > 50.0 {
+        100.0
+    } else if max_value > 10.0 {
+        50.0
+    } else {
+        10.0
+    };
+    let length = histories.iter().map(Vec::len).max().unwrap_or(0);
+
+    let line_colors = [Color::Cyan, Color::LightMagenta, Color::LightYellow];
+    for ((r, history), color) in resources.iter().zip(histories).zip(line_colors) {
+        chart = chart.series(
+            format!("{} {:.1}%", r.get_name(), r.get_some_avg10()),
+            Style::default().fg(color),
+            history,
+        );
+    }
+    for level in [10.0, 40.0].into_iter().filter(|level| *level < upper) {
+        chart = chart.series(
+            format!("> {}%", level),
+            Style::default().fg(theme.pressure_color(level)),
+            vec![level; length],
+        );
+    }
+
+    let labels = [0.0, upper / 2.0, upper]
+        .iter()
+        .map(|value| format!("{}", value))
+        .collect();
+    f.render_widget(chart.y_axis("Stall % (some avg10)", upper, labels), area);
+}
+
+pub fn render_paging_widget<B: ratatui::backend::Backend>(
+    f: &mut Frame<B>,
+    area: Rect,
+    vmstat: &VmStatState,
+    theme: &Theme,
+) {
+    let alert_style = Style::default()
+        .fg(theme.get_color("memory_high"))
+        .add_modifier(Modifier::BOLD);
+
+    let mut title = vec![Span::raw("Paging Activity")];
+    if vmstat.is_swapping() {
+        title.push(Span::raw(" "));
+        title.push(Span::styled("[SWAPPING]", alert_style));
+    }
+    if vmstat.recent_oom_kill() {
+        title.push(Span::raw(" "));
+        title.push(Span::styled(
+            "[OOM KILL]",
+            alert_style.add_modifier(Modifier::SLOW_BLINK),
+        ));
+    }
+
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .title(Line::from(title));
+
+    if !vmstat.is_available() {
+        let paragraph = Paragraph::new("/proc/vmstat not available").block(block);
+        f.render_widget(paragraph, area);
+        return;
+    }
+
+    let header_cells = ["Counter", "Rate/s", "Peak/s", "Total"]
+        .iter()
+        .map(|h| Span::styled(*h, Style::default().fg(theme.header_color())));
+    let header = Row::new(header_cells).style(Style::default());
+
+    let rows = VmCounter::ALL.iter().map(|counter| {
+        let rate = vmstat.get_rate(*counter);
+        let peak = vmstat
+            .get_history(*counter)
+            .map(|h| h.iter().fold(0.0_f64, |a, &b| f64::max(a, b)))
+            .unwrap_or(0.0);
+
+        let row_data = vec![
+            counter.label().to_string(),
+            format!("{:.1}", rate),
+            format!("{:.1}", peak),
+            vmstat.get_total(*counter).to_string(),
+        ];
+        let cells = row_data.into_iter().map(Span::raw);
+
+        let active = match counter {
+            VmCounter::SwapIn | VmCounter::SwapOut | VmCounter::MajorFault => rate > 0.0,
+            VmCounter::OomKill => vmstat.recent_oom_kill(),
+            _ => false,
+        };
+
+        if active {
+            Row::new(cells).style(alert_style)
+        } else {
+            Row::new(cells)
+        }
+    });
+
+    let widths = [
+        Constraint::Percentage(40),
+        Constraint::Length(10),
+        Constraint::Length(10),
+        Constraint::Length(14),
+    ];
+
+    let table = Table::new(rows).header(header).block(block).widths(&widths);
+
+    f.render_widget(table, area);
+}
+
+pub fn render_paging_graph<B: ratatui::backend::Backend>(
+    f: &mut Frame<B>,
+    area: Rect,
+    vmstat: &VmStatState,
+    config: &Config,
+    theme: &Theme,
+) {
+    let series = [
+        (VmCounter::SwapIn, theme.get_color("network_rx")),
+        (VmCounter::SwapOut, theme.get_color("network_tx")),
+        (VmCounter::MajorFault, Color::LightYellow),
+    ];
+
+    let histories: Vec<(VmCounter, Color, Vec<f64>)> = series
+        .iter()
+        .filter_map(|(counter, color)| {
+            let history = vmstat.get_history(*counter)?;
+            Some((*counter, *color, history.iter().copied().collect()))
+        })
+        .collect();
+
+    let mut chart = TimeChart::new("Swap & Fault Rates", config);
+    let max_value = histories
+        .iter()
+        .flat_map(|(_, _, history)| history[history.len() - chart.visible_count(history)..].iter())
+        .fold(10.0_f64, |a, &b| f64::max(a, b));
+
+    for (counter, color, history) in histories {
+        chart = chart.series(
+            format!("{} {:.0}", counter.label(), vmstat.get_rate(counter)),
+            Style::default().fg(color),
+            history,
+        );
+    }
+
+    let labels = [0.0, max_value / 2.0, max_value]
+        .iter()
+        .map(|value| format!("{:.0}", value))
+        .collect();
+    f.render_widget(chart.y_axis("Pages/s", max_value, labels), area);
+}
+
+#[allow(clippy::too_many_arguments)]
+pub fn render_status_bar<B: ratatui::backend::Backend>(
+    f: &mut Frame<B>,
+    area: Rect,
+    current_layout: &str,
+    layout_actions: &[Action],
+    config: &Config,
+    alerts: &AlertState,
+    theme: &Theme,
+    keymap: &Keymap,
+    regions: &mut Regions,
+) {
+    let units = Units::from_config(&config.units).label();
+    let mut layouts: Vec<(u32, Action)> = layout_actions
+        .iter()
+        .filter_map(|action| Some((digit_label(&keymap.label(*action)?)?, *action)))
+        .collect();
+    layouts.sort();
+    let layouts: Vec<Action> = layouts.into_iter().map(|(_, action)| action).collect();
+    let controls = vec![
+        (
+            keymap.label(Action::Quit),
+            "Quit",
+            StatusTarget::Action(Action::Quit),
+        ),
+        (
+            keymap.label(Action::CycleTheme),
+            "Cycle Theme",
+            StatusTarget::Action(Action::CycleTheme),
+        ),
+        (
+            keymap.label(Action::ToggleGraphs),
+            "Graph View",
+            StatusTarget::Action(Action::ToggleGraphs),
+        ),
+        (
+            keymap.range_label(&layouts),
+            "Change Layout",
+            StatusTarget::Palette("layout "),
+        ),
+        (
+            keymap.range_label(&[Action::ToggleByteBase, Action::ToggleBits]),
+            &units,
+            StatusTarget::Action(Action::ToggleByteBase),
+        ),
+    ];
+
+    let mut entries: Vec<(Vec<Span>, Option<StatusTarget>)> = Vec::new();
+    if let Some(severity) = alerts.highest_severity() {
+        let color = theme.alert_color(severity);
+        let flash = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d.as_secs() % 2 == 0)
+            .unwrap_or(true);
+        let style = if flash {
+            Style::default()
+                .fg(Color::Black)
+                .bg(color)
+                .add_modifier(Modifier::BOLD)
+        } else {
+            Style::default().fg(color).add_modifier(Modifier::BOLD)
+        };
+        let hint = match keymap.label(Action::LayoutAlerts) {
+            Some(key) => format!("[{}] ", key),
+            None => String::new(),
+        };
+        entries.push((
+            vec![Span::styled(
+                format!(" {}{} ALERT(S) ", hint, alerts.get_active().len()),
+                style,
+            )],
+            Some(StatusTarget::Action(Action::LayoutAlerts)),
+        ));
+        entries.push((vec![Span::raw("  ")], None));
+    }
+    for (key, desc, target) in controls {
+        if let Some(key) = key {
+            entries.push((
+                vec![
+                    Span::styled(
+                        format!("[{}]", key),
+                        Style::default()
+                            .fg(Color::Yellow)
+                            .add_modifier(Modifier::BOLD),
+                    ),
+                    Span::raw(" "),
+                    Span::styled(desc.to_string(), Style::default().fg(Color::White)),
+                ],
+                Some(target),
+            ));
+            entries.push((vec![Span::raw("  ")], None));
+        }
+    }
+    entries.push((
+        vec![Span::styled(
+            format!("Current: {}", current_layout),
+            Style::default()
+                .fg(Color::Cyan)
+                .add_modifier(Modifier::BOLD),
+        )],
+        None,
+    ));
+    if let Some(pending) = keymap.get_pending() {
+        entries.push((
+            vec![
+                Span::raw("  "),
+                Span::styled(
+                    format!("[{} …]", pending),
+                    Style::default()
+                        .fg(Color::Yellow)
+                        .add_modifier(Modifier::BOLD),
+                ),
+            ],
+            None,
+        ));
+    }
+    entries.push((
+        vec![
+            Span::styled(
+                "[Made by:]",
+                Style::default()
+                    .fg(Color::Yellow)
+                    .add_modifier(Modifier::BOLD),
+            ),
+            Span::raw(" "),
+            Span::styled("C0d3-5t3w", Style::default().fg(Color::White)),
+        ],
+        None,
+    ));
 
     let status_bar = Block::default()
         .borders(Borders::TOP)
         .border_style(Style::default().fg(Color::DarkGray));
 
-    let paragraph = Paragraph::new(status_line)
+    let inner = status_bar.inner(area);
+    let line_width: usize = entries
+        .iter()
+        .flat_map(|(spans, _)| spans.iter().map(Span::width))
+        .sum();
+    let mut x = inner.x + (inner.width / 2).saturating_sub(line_width as u16 / 2);
+    let mut control_spans = Vec::new();
+    for (spans, target) in entries {
+        let width: usize = spans.iter().map(Span::width).sum();
+        if let Some(target) = target {
+            let entry = Rect::new(x, inner.y, width as u16, inner.height.min(1));
+            regions.add_status(entry.intersection(inner), target);
+        }
+        x = x.saturating_add(width as u16);
+        control_spans.extend(spans);
+    }
+
+    let paragraph = Paragraph::new(Line::from(control_spans))
         .block(status_bar)
         .alignment(ratatui::layout::Alignment::Center);
 
     f.render_widget(paragraph, area);
 }
 
-fn format_bytes(bytes: u64) -> String {
-    const KB: u64 = 1024;
-    const MB: u64 = KB * 1024;
-    const GB: u64 = MB * 1024;
-
-    if bytes >= GB {
-        format!("{:.2} GB", bytes as f64 / GB as f64)
-    } else if bytes >= MB {
-        format!("{:.2} MB", bytes as f64 / MB as f64)
-    } else if bytes >= KB {
-        format!("{:.2} KB", bytes as f64 / KB as f64)
-    } else {
-        format!("{} B", bytes)
+pub fn render_sensors_widget<B: ratatui::backend::Backend>(
+    f: &mut Frame<B>,
+    area: Rect,
+    sensors: &SensorState,
+    theme: &Theme,
+) {
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .title("Temperature Sensors");
+    if sensors.get_sensors().is_empty() {
+        f.render_widget(
+            Paragraph::new("No temperature sensors found").block(block),
+            area,
+        );
+        return;
     }
+
+    let header_cells = ["Sensor", "Temp", "Max", "Critical", ""]
+        .iter()
+        .map(|h| Span::styled(*h, Style::default().fg(theme.header_color())));
+    let header = Row::new(header_cells).style(Style::default());
+
+    let bar_width = (area.width as usize).saturating_sub(62).max(1);
+    let rows = sensors.get_sensors().iter().map(|sensor| {
+        let color = theme.temperature_color(sensor.temperature, sensor.critical);
+        let scale = sensor.critical.filter(|c| *c > 0.0).unwrap_or(100.0);
+        let filled = ((sensor.temperature / scale).clamp(0.0, 1.0) * bar_width as f32) as usize;
+        Row::new(vec![
+            Span::raw(sensor.label.clone()),
+            Span::styled(
+                format!("{:.1}°C", sensor.temperature),
+                Style::default().fg(color),
+            ),
+            Span::raw(format!("{:.1}°C", sensor.max)),
+            Span::raw(
+                sensor
+                    .critical
+                    .map(|critical| format!("{:.1}°C", critical))
+                    .unwrap_or_else(|| "-".to_string()),
+            ),
+            Span::styled("|".repeat(filled), Style::default().fg(color)),
+        ])
+    });
+
+    let widths = [
+        Constraint::Length(28),
+        Constraint::Length(9),
+        Constraint::Length(9),
+        Constraint::Length(9),
+        Constraint::Min(1),
+    ];
+    let table = Table::new(rows).header(header).block(block).widths(&widths);
+
+    f.render_widget(table, area);
 }
 
-fn format_bytes_rate(bytes_per_sec: f64) -> String {
-    const KB: f64 = 1024.0;
-    const MB: f64 = KB * 1024.0;
-    const GB: f64 = MB * 1024.0;
+pub fn render_tab_bar<B: ratatui::backend::Backend>(
+    f: &mut Frame<B>,
+    area: Rect,
+    tabs: &[(&str, bool)],
+    theme: &Theme,
+    regions: &mut Regions,
+) {
+    let mut spans = Vec::new();
+    let mut x = area.x;
+    for (index, (title, active)) in tabs.iter().enumerate() {
+        let label = format!(" {} ", title);
+        let width = label.chars().count() as u16;
+        regions.add_tab(Rect::new(x, area.y, width, 1).intersection(area), index);
+        spans.push(Span::styled(label, theme.tab_style(*active)));
+        spans.push(Span::raw(" "));
+        x = x.saturating_add(width + 1);
+    }
+
+    f.render_widget(Paragraph::new(Line::from(spans)), area);
+}
 
-    if bytes_per_sec >= GB {
-        format!("{:.2} GB/s", bytes_per_sec / GB)
-    } else if bytes_per_sec >= MB {
-        format!("{:.2} MB/s", bytes_per_sec / MB)
-    } else if bytes_per_sec >= KB {
-        format!("{:.2} KB/s", bytes_per_sec / KB)
+fn format_duration(duration: std::time::Duration) -> String {
+    let secs = duration.as_secs();
+    let (days, hours, minutes) = (secs / 86_400, (secs % 86_400) / 3_600, (secs % 3_600) / 60);
+
+    if days >= 365 {
+        ">1y".to_string()
+    } else if days > 0 {
+        format!("{}d {}h", days, hours)
+    } else if hours > 0 {
+        format!("{}h {}m", hours, minutes)
+    } else if minutes > 0 {
+        format!("{}m", minutes)
     } else {
-        format!("{:.2} B/s", bytes_per_sec)
+        format!("{}s", secs)
     }
 }
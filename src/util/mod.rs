@@ -0,0 +1,6 @@
+pub mod glob;
+pub mod time;
+pub mod units;
+
+pub use glob::matches_any;
+pub use units::Units;
@@ -0,0 +1,162 @@
+use crate::config::UnitsConfig;
+
+const DECIMAL_PREFIXES: [&str; 6] = ["", "k", "M", "G", "T", "P"];
+const BINARY_PREFIXES: [&str; 6] = ["", "Ki", "Mi", "Gi", "Ti", "Pi"];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ByteBase {
+    Decimal,
+    Binary,
+}
+
+impl ByteBase {
+    pub fn from_value(value: u32) -> Self {
+        match value {
+            1000 => ByteBase::Decimal,
+            _ => ByteBase::Binary,
+        }
+    }
+
+    pub fn value(&self) -> u32 {
+        match self {
+            ByteBase::Decimal => 1000,
+            ByteBase::Binary => 1024,
+        }
+    }
+
+    fn divisor(&self) -> f64 {
+        self.value() as f64
+    }
+
+    fn prefixes(&self) -> &'static [&'static str; 6] {
+        match self {
+            ByteBase::Decimal => &DECIMAL_PREFIXES,
+            ByteBase::Binary => &BINARY_PREFIXES,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct Units {
+    base: ByteBase,
+    network_bits: bool,
+    precision: usize,
+}
+
+impl Units {
+    pub fn from_config(config: &UnitsConfig) -> Self {
+        Self {
+            base: ByteBase::from_value(config.byte_base),
+            network_bits: config.network_bits,
+            precision: config.precision,
+        }
+    }
+
+    fn scale(&self, value: f64, unit: &str) -> String {
+        let divisor = self.base.divisor();
+        let prefixes = self.base.prefixes();
+
+        let mut scaled = value;
+        let mut idx = 0;
+        while scaled.abs() >= divisor && idx + 1 < prefixes.len() {
+            scaled /= divisor;
+            idx += 1;
+        }
+
+        if idx == 0 {
+            format!("{:.0} {}", scaled, unit)
+        } else {
+            format!("{:.*} {}{}", self.precision, scaled, prefixes[idx], unit)
+        }
+    }
+
+    pub fn bytes(&self, bytes: u64) -> String {
+        self.scale(bytes as f64, "B")
+    }
+
+    pub fn rate(&self, bytes_per_sec: f64) -> String {
+        self.scale(bytes_per_sec, "B/s")
+    }
+
+    pub fn network_rate(&self, bytes_per_sec: f64) -> String {
+        if self.network_bits {
+            self.scale(bytes_per_sec * 8.0, "bit/s")
+        } else {
+            self.rate(bytes_per_sec)
+        }
+    }
+
+    pub fn network_axis(&self, max_bytes_per_sec: f64) -> (f64, Vec<String>) {
+        if self.network_bits {
+            self.axis(max_bytes_per_sec, 8.0, "bit/s")
+        } else {
+            self.axis(max_bytes_per_sec, 1.0, "B/s")
+        }
+    }
+
+    pub fn rate_axis(&self, max_bytes_per_sec: f64) -> (f64, Vec<String>) {
+        self.axis(max_bytes_per_sec, 1.0, "B/s")
+    }
+
+    fn axis(&self, max_bytes_per_sec: f64, factor: f64, unit: &str) -> (f64, Vec<String>) {
+        let divisor = self.base.divisor();
+        let prefixes = self.base.prefixes();
+
+        let mut scaled = (max_bytes_per_sec * factor).max(4.0);
+        let mut multiplier = 1.0;
+        let mut idx = 0;
+        while scaled >= divisor && idx + 1 < prefixes.len() {
+            scaled /= divisor;
+            multiplier *= divisor;
+            idx += 1;
+        }
+
+        let step = nice_step(scaled / 4.0);
+        let ticks = (scaled / step).ceil().max(1.0) as usize;
+        let decimals = if step >= 1.0 {
+            0
+        } else {
+            (-step.log10()).ceil() as usize
+        };
+        let labels = (0..=ticks)
+            .map(|tick| match tick {
+                0 => "0".to_string(),
+                _ => format!(
+                    "{:.*} {}{}",
+                    decimals,
+                    tick as f64 * step,
+                    prefixes[idx],
+                    unit
+                ),
+            })
+            .collect();
+        (ticks as f64 * step * multiplier / factor, labels)
+    }
+
+    pub fn label(&self) -> String {
+        let base = match self.base {
+            ByteBase::Decimal => "SI",
+            ByteBase::Binary => "IEC",
+        };
+        if self.network_bits {
+            format!("{}, bit/s", base)
+        } else {
+            base.to_string()
+        }
+    }
+}
+
+fn nice_step(rough: f64) -> f64 {
+    let magnitude = 10f64.powf(rough.log10().floor());
+    let fraction = rough / magnitude;
+    let nice = if fraction <= 1.0 {
+        1.0
+    } else if fraction <= 2.0 {
+        2.0
+    } else if fraction <= 5.0 {
+        5.0
+    } else {
+        10.0
+    };
+    nice * magnitude
+}